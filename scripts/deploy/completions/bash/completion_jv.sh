@@ -18,7 +18,7 @@ _jv_completion() {
     local base_commands="create init direct unstain account update \
                          sheet status here move mv docs exit use sheets accounts \
                          as make drop track hold throw login \
-                         jump align info share"
+                         jump align info share diff restore checkout log"
 
     # Subcommands - Account
     local account_commands="list as add remove movekey mvkey mvk genpub help"
@@ -215,8 +215,8 @@ _jv_completion() {
         return 0
     fi
 
-    # Completion info
-    if [[ "$subcmd" == "info" ]]; then
+    # Completion info / diff / restore / checkout / log
+    if [[ "$subcmd" == "info" || "$subcmd" == "diff" || "$subcmd" == "restore" || "$subcmd" == "checkout" || "$subcmd" == "log" ]]; then
         if [[ $cword -eq 2 ]]; then
             COMPREPLY=($(compgen -f -- "$cur"))
         fi
@@ -16,7 +16,7 @@ Register-ArgumentCompleter -Native -CommandName jv -ScriptBlock {
         "create", "init", "direct", "unstain", "account", "update",
         "sheet", "status", "here", "move", "mv", "docs", "exit", "use", "sheets", "accounts",
         "as", "make", "drop", "track", "hold", "throw", "login",
-        "jump", "align", "info", "share"
+        "jump", "align", "info", "share", "diff", "restore", "checkout", "log"
     )
 
     # Account subcommands
@@ -166,8 +166,8 @@ Register-ArgumentCompleter -Native -CommandName jv -ScriptBlock {
         return @()
     }
 
-    # Completion for info command
-    if ($subcmd -eq "info") {
+    # Completion for info, diff, restore, checkout and log command
+    if ($subcmd -eq "info" -or $subcmd -eq "diff" -or $subcmd -eq "restore" -or $subcmd -eq "checkout" -or $subcmd -eq "log") {
         if ($currentIndex -eq 2) {
             # File completion for the file argument
             return Get-ChildItem -Name -File -Path "." | Where-Object { $_ -like "$wordToComplete*" }
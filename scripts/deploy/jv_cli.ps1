@@ -4,6 +4,9 @@ $SCRIPT_DIR = Split-Path -Parent $MyInvocation.MyCommand.Definition
 ### CONFIG ###
 ##############
 
+# The variables below override the values set with `jv config`
+# See `jv config --help` to set them persistently instead
+
 # Use JV_LANG to set CLI language
 # Supported: en, zh-CN
 # $env:JV_LANG = "en"
@@ -22,7 +25,7 @@ $env:JV_AUTO_UPDATE = "yes"
 # $env:JV_OUTDATED_MINUTES = "5"
 
 # Use JV_TEXT_EDITOR to set text editor for `jv track --work` `jv align --work`
-# DEFAULT: $EDITOR environment variable, falling back to "jvii" if not set
+# DEFAULT: `text_editor` of `jv config`, then the $EDITOR environment variable, falling back to "jvii" if not set
 # $env:JV_TEXT_EDITOR = "nano"
 
 ###############
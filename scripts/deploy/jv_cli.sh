@@ -5,6 +5,9 @@ SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 ### CONFIG ###
 ##############
 
+# The variables below override the values set with `jv config`
+# See `jv config --help` to set them persistently instead
+
 # Use JV_LANG to set CLI language
 # Supported: en, zh-CN
 # export JV_LANG=en
@@ -23,7 +26,7 @@ export JV_AUTO_UPDATE=yes
 # export JV_OUTDATED_MINUTES=5
 
 # Use JV_TEXT_EDITOR to set text editor for `jv track --work` `jv align --work`
-# DEFAULT: $EDITOR environment variable, falling back to "jvii" if not set
+# DEFAULT: `text_editor` of `jv config`, then the $EDITOR environment variable, falling back to "jvii" if not set
 # export JV_TEXT_EDITOR=nano
 
 ###############
@@ -67,13 +67,13 @@ use just_enough_vcs::{
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     env::{current_dir, set_current_dir},
-    io::Error,
+    io::{Error, IsTerminal},
     net::SocketAddr,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::exit,
     str::FromStr,
     sync::Arc,
-    time::SystemTime,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use clap::{Parser, Subcommand};
@@ -83,26 +83,59 @@ use just_enough_vcs::{
 };
 use just_enough_vcs_cli::{
     data::{
+        base_snapshot::{base_snapshot_path, read_base_snapshot, save_base_snapshot},
         compile_info::CompileInfo,
+        config::{
+            current_config, read_config_file, user_config_path, workspace_config_path,
+            write_config_file,
+        },
         ipaddress_history::{get_recent_ip_address, insert_recent_ip_address},
     },
     output::{
         accounts::{AccountItem, AccountListJsonResult},
         align::{AlignJsonResult, AlignTaskMapping},
         analyzer_result::{AnalyzerJsonResult, ModifiedItem, ModifiedType, MovedItem},
+        config::{ConfigItem, ConfigListJsonResult},
+        diff::{DiffItem, DiffJsonResult, DiffStatus},
+        direct::{DirectJsonResult, DirectResult},
+        dry_run::{DryRunJsonResult, DryRunOperation},
+        edit_right::{
+            EditRightFailReason, EditRightFailedItem, EditRightJsonResult, EditRightResult,
+        },
         here::{HereJsonResult, HereJsonResultItem},
         info::{InfoHistory, InfoJsonResult},
+        log::{LogEntry, LogJsonResult},
+        mapping_edit::{MappingEditItem, MappingEditJsonResult, MappingEditResult},
         share::{SeeShareResult, ShareItem, ShareListResult},
         sheets::{SheetItem, SheetListJsonResult},
+        track::{TrackJsonResult, TrackResult},
+        update::{UpdateJsonResult, UpdateResult},
     },
     utils::{
-        display::{SimpleTable, display_width, md, render_share_path_tree, size_str},
-        env::{auto_update_outdate, current_locales, enable_auto_update},
+        alias::{AliasError, Aliases},
+        cmd_error::{CmdError, CmdResult},
+        config::{Config, ConfigEntry, ConfigKey, ConfigLayer, ConfigTable},
+        diff::{is_binary, unified_diff},
+        display::{
+            SimpleTable, display_width, md, render_share_path_tree, render_unified_diff, size_str,
+        },
+        env::{
+            auto_update_outdate, base_cache_enabled, config_list, config_value, current_locales,
+            enable_auto_update, glob_case_insensitive,
+        },
         fs::move_across_partitions,
-        globber::{GlobItem, Globber},
-        input::{confirm_hint, confirm_hint_or, input_with_editor, show_in_pager},
-        push_version::push_version,
+        globber::{GlobItem, Globber, PathTrie},
+        hooks::{HookContext, HookOutput, HookStage, run_hook},
+        ignore::IgnoreRules,
+        input::{
+            confirm_hint, confirm_hint_or, input_with_editor, read_path_list, read_text,
+            show_in_pager,
+        },
+        progress::{TransferProgress, duration_str, progress_bar},
+        push_version::{VersionBump, VersionSchemeRules, compare_versions},
+        similarity::{closest_match, path_similarity},
         socket_addr_helper,
+        update_desc::{DescriptionRules, UpdateDescriptions},
     },
 };
 use rust_i18n::{set_locale, t};
@@ -161,6 +194,18 @@ enum JustEnoughVcsWorkspaceCommand {
     /// Display detailed information about the specified file
     Info(InfoArgs),
 
+    /// Compare modified files with their base versions
+    Diff(DiffArgs),
+
+    /// Restore files to their base versions
+    Restore(RestoreArgs),
+
+    /// Retrieve a specific version of a file
+    Checkout(CheckoutArgs),
+
+    /// Display the version history of the current sheet
+    Log(LogArgs),
+
     // Sheet management
     /// Manage sheets in the workspace
     #[command(subcommand, alias = "sh")]
@@ -203,18 +248,22 @@ enum JustEnoughVcsWorkspaceCommand {
     /// Query built-in documentation
     Docs(DocsArgs),
 
+    /// Inspect and edit the configuration
+    #[command(subcommand)]
+    Config(ConfigManage),
+
     // Lazy commands
     /// Try exit current sheet
-    Exit,
+    Exit(SheetExitArgs),
 
     /// Try exit current sheet and use another sheet
-    Use(UseArgs),
+    Use(SheetUseArgs),
 
     /// List all sheets
-    Sheets,
+    Sheets(SheetListArgs),
 
     /// List all accounts
-    Accounts,
+    Accounts(AccountListArgs),
 
     /// Align file structure
     Align(SheetAlignArgs),
@@ -420,6 +469,18 @@ struct SheetAlignArgs {
     #[arg(short, long)]
     work: bool,
 
+    /// Match lost items to created items automatically
+    #[arg(short = 'a', long = "auto")]
+    auto: bool,
+
+    /// Whether to skip confirmation
+    #[arg(short = 'C', long)]
+    confirm: bool,
+
+    /// Print the operations without performing them
+    #[arg(short = 'n', long = "dry-run")]
+    dry_run: bool,
+
     /// Show raw output (for list)
     #[arg(short, long)]
     raw: bool,
@@ -511,13 +572,128 @@ struct InfoArgs {
     #[arg(short, long)]
     help: bool,
 
-    /// File pattern
-    file_pattern: Option<String>,
+    /// File patterns, patterns starting with `!` exclude files
+    file_patterns: Vec<String>,
+
+    /// Match the file patterns case-insensitively
+    #[arg(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// Exclude the files matching these patterns
+    #[arg(long = "exclude", value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Read the files from a list, one path per line (or NUL separated), `-` reads stdin
+    #[arg(long = "from-file", value_name = "PATH")]
+    from_file: Option<String>,
 
     /// Full histories output
     #[arg(short, long = "full")]
     full: bool,
 
+    /// Only show the latest N versions
+    #[arg(short = 'n', long)]
+    limit: Option<usize>,
+
+    /// Only show versions created since this date, rejected as the vault keeps no time for versions
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Only show versions created by this member
+    #[arg(long)]
+    creator: Option<String>,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
+}
+
+#[derive(Parser, Debug)]
+struct DiffArgs {
+    /// Show help information
+    #[arg(short, long)]
+    help: bool,
+
+    /// File pattern
+    file_pattern: Option<String>,
+
+    /// Lines of context around each change
+    #[arg(short = 'U', long = "unified", default_value_t = 3)]
+    context: usize,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
+}
+
+#[derive(Parser, Debug)]
+struct RestoreArgs {
+    /// Show help information
+    #[arg(short, long)]
+    help: bool,
+
+    /// File pattern
+    file_pattern: Option<String>,
+
+    /// Move the current content aside before restoring
+    #[arg(short = 'b', long = "keep-backup")]
+    keep_backup: bool,
+
+    /// Whether to skip confirmation
+    #[arg(short = 'C', long)]
+    confirm: bool,
+}
+
+#[derive(Parser, Debug)]
+struct CheckoutArgs {
+    /// Show help information
+    #[arg(short, long)]
+    help: bool,
+
+    /// File pattern
+    file_pattern: Option<String>,
+
+    /// Version to retrieve
+    version: Option<String>,
+
+    /// Write the version to this path instead of the mapped file
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Whether to skip confirmation
+    #[arg(short = 'C', long)]
+    confirm: bool,
+}
+
+#[derive(Parser, Debug)]
+struct LogArgs {
+    /// Show help information
+    #[arg(short, long)]
+    help: bool,
+
+    /// File pattern
+    file_pattern: Option<String>,
+
+    /// Only show versions created by this member
+    #[arg(short, long)]
+    member: Option<String>,
+
+    /// Only show the first N entries
+    #[arg(short = 'n', long)]
+    limit: Option<usize>,
+
+    /// Print directly instead of using the pager
+    #[arg(short, long)]
+    direct: bool,
+
     /// Show json output
     #[arg(long = "json")]
     json_output: bool,
@@ -612,8 +788,20 @@ struct TrackFileArgs {
     #[arg(short, long)]
     help: bool,
 
-    /// Track file pattern
-    track_file_pattern: Option<String>,
+    /// Track file patterns, patterns starting with `!` exclude files
+    track_file_patterns: Vec<String>,
+
+    /// Match the file patterns case-insensitively
+    #[arg(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// Exclude the files matching these patterns
+    #[arg(long = "exclude", value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Read the files from a list, one path per line (or NUL separated), `-` reads stdin
+    #[arg(long = "from-file", value_name = "PATH")]
+    from_file: Option<String>,
 
     /// Overwrite modified
     #[arg(short = 'o', long = "overwrite")]
@@ -623,13 +811,38 @@ struct TrackFileArgs {
     #[arg(short, long)]
     desc: Option<String>,
 
+    /// Commit - Read the description from a file, `-` reads stdin
+    #[arg(
+        short = 'F',
+        long = "desc-file",
+        value_name = "PATH",
+        conflicts_with = "desc"
+    )]
+    desc_file: Option<String>,
+
     /// Commit - Description
     #[arg(short = 'v', long = "version")]
     next_version: Option<String>,
 
+    /// Commit - Part of the version to increase (major, minor or patch)
+    #[arg(long = "bump", value_name = "PART")]
+    bump: Option<String>,
+
     /// Commit - Editor mode
     #[arg(short, long)]
     work: bool,
+
+    /// Print the operations without performing them
+    #[arg(short = 'n', long = "dry-run")]
+    dry_run: bool,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -638,8 +851,20 @@ struct HoldFileArgs {
     #[arg(short, long)]
     help: bool,
 
-    /// Hold file pattern
-    hold_file_pattern: Option<String>,
+    /// Hold file patterns, patterns starting with `!` exclude files
+    hold_file_patterns: Vec<String>,
+
+    /// Match the file patterns case-insensitively
+    #[arg(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// Exclude the files matching these patterns
+    #[arg(long = "exclude", value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Read the files from a list, one path per line (or NUL separated), `-` reads stdin
+    #[arg(long = "from-file", value_name = "PATH")]
+    from_file: Option<String>,
 
     /// Show fail details
     #[arg(short = 'd', long = "details")]
@@ -652,6 +877,14 @@ struct HoldFileArgs {
     /// Skip check
     #[arg(short = 'F', long)]
     force: bool,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -660,8 +893,20 @@ struct ThrowFileArgs {
     #[arg(short, long)]
     help: bool,
 
-    /// Throw file pattern
-    throw_file_pattern: Option<String>,
+    /// Throw file patterns, patterns starting with `!` exclude files
+    throw_file_patterns: Vec<String>,
+
+    /// Match the file patterns case-insensitively
+    #[arg(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// Exclude the files matching these patterns
+    #[arg(long = "exclude", value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Read the files from a list, one path per line (or NUL separated), `-` reads stdin
+    #[arg(long = "from-file", value_name = "PATH")]
+    from_file: Option<String>,
 
     /// Show fail details
     #[arg(short = 'd', long = "details")]
@@ -674,6 +919,14 @@ struct ThrowFileArgs {
     /// Skip check
     #[arg(short = 'F', long)]
     force: bool,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -688,6 +941,14 @@ struct MoveMappingArgs {
     /// To mapping pattern
     to_mapping_pattern: Option<String>,
 
+    /// Match the file patterns case-insensitively
+    #[arg(short = 'i', long = "ignore-case")]
+    ignore_case: bool,
+
+    /// Exclude the files matching these patterns
+    #[arg(long = "exclude", value_name = "PATTERN")]
+    exclude: Vec<String>,
+
     /// Erase
     #[arg(short = 'e', long)]
     erase: bool,
@@ -695,6 +956,18 @@ struct MoveMappingArgs {
     /// Only modify upstream mapping
     #[arg(short = 'r', long)]
     only_remote: bool,
+
+    /// Print the operations without performing them
+    #[arg(short = 'n', long = "dry-run")]
+    dry_run: bool,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -728,6 +1001,10 @@ struct ShareMappingArgs {
     #[arg(short = 'R', long)]
     reject: bool,
 
+    /// Read the files from a list, one path per line (or NUL separated), `-` reads stdin
+    #[arg(long = "from-file", value_name = "PATH")]
+    from_file: Option<String>,
+
     /// Show raw output
     #[arg(short = 'r', long)]
     raw: bool,
@@ -743,6 +1020,10 @@ struct ShareMappingArgs {
     /// Share - Editor mode
     #[arg(short, long)]
     work: bool,
+
+    /// Print the operations without performing them
+    #[arg(short = 'n', long = "dry-run")]
+    dry_run: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -754,6 +1035,14 @@ struct UpdateArgs {
     /// Silent mode
     #[arg(short, long)]
     silent: bool,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -768,6 +1057,14 @@ struct DirectArgs {
     /// Whether to skip confirmation
     #[arg(short = 'C', long)]
     confirm: bool,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -799,9 +1096,85 @@ struct DocsArgs {
     raw: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum ConfigManage {
+    /// Show help information
+    #[command(alias = "--help", alias = "-h")]
+    Help,
+
+    /// List all configuration values and where they come from
+    #[command(alias = "ls")]
+    List(ConfigListArgs),
+
+    /// Show a configuration value
+    Get(ConfigGetArgs),
+
+    /// Set a configuration value
+    Set(ConfigSetArgs),
+
+    /// Remove a configuration value
+    #[command(alias = "rm")]
+    Unset(ConfigUnsetArgs),
+}
+
 #[derive(Parser, Debug)]
-struct UseArgs {
-    sheet_name: String,
+struct ConfigListArgs {
+    /// Show help information
+    #[arg(short, long)]
+    help: bool,
+
+    /// Show json output
+    #[arg(long = "json")]
+    json_output: bool,
+
+    /// Show json output pretty
+    #[arg(long = "pretty")]
+    pretty: bool,
+}
+
+#[derive(Parser, Debug)]
+struct ConfigGetArgs {
+    /// Show help information
+    #[arg(short, long)]
+    help: bool,
+
+    /// Configuration key
+    key: String,
+
+    /// Also show where the value comes from
+    #[arg(short, long)]
+    origin: bool,
+}
+
+#[derive(Parser, Debug)]
+struct ConfigSetArgs {
+    /// Show help information
+    #[arg(short, long)]
+    help: bool,
+
+    /// Configuration key
+    key: String,
+
+    /// Value
+    value: String,
+
+    /// Set the value for the current workspace instead of the user
+    #[arg(short, long)]
+    workspace: bool,
+}
+
+#[derive(Parser, Debug)]
+struct ConfigUnsetArgs {
+    /// Show help information
+    #[arg(short, long)]
+    help: bool,
+
+    /// Configuration key
+    key: String,
+
+    /// Remove the value of the current workspace instead of the user
+    #[arg(short, long)]
+    workspace: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -812,6 +1185,12 @@ struct DebugGlobArgs {
 
 #[tokio::main]
 async fn main() {
+    if let Err(err) = run().await {
+        exit(err.exit_code());
+    }
+}
+
+async fn run() -> CmdResult {
     // Init i18n
     set_locale(&current_locales());
 
@@ -838,14 +1217,16 @@ async fn main() {
             Ok(path) => path,
             Err(e) => {
                 eprintln!("{}", t!("jv.fail.get_current_dir", error = e.to_string()));
-                return;
+                return Err(CmdError::Failed);
             }
         };
         // Update
         // This will change the current current_dir
-        jv_update(UpdateArgs {
+        let _ = jv_update(UpdateArgs {
             help: false,
             silent: true,
+            json_output: false,
+            pretty: false,
         })
         .await;
         // Restore current directory
@@ -858,7 +1239,7 @@ async fn main() {
                     error = e
                 )
             );
-            return;
+            return Err(CmdError::Failed);
         }
     } else
     // If automatic update and timeout update are enabled,
@@ -883,11 +1264,13 @@ async fn main() {
                         if duration_secs > required_outdated_minutes as u64 * 60 {
                             // Update
                             // This will change the current current_dir
-                            jv_update(UpdateArgs {
+                            let _ = jv_update(UpdateArgs {
                                 help: false,
                                 silent: true,
+                                json_output: false,
+                                pretty: false,
                             })
-                            .await
+                            .await;
                         }
                     }
                 }
@@ -895,8 +1278,44 @@ async fn main() {
         };
     }
 
-    let Ok(parser) = JustEnoughVcsWorkspace::try_parse() else {
-        eprintln!("{}", md(t!("jv.fail.parse.parser_failed")));
+    // Expand the aliases, an alias may run several commands one after another
+    let aliases = current_config().aliases();
+    let commands = match aliases.expand(std::env::args().collect(), is_builtin_command) {
+        Ok(commands) => commands,
+        Err(AliasError::Recursive(name)) => {
+            eprintln!("{}", md(t!("jv.fail.alias.recursive", name = name)));
+            return Err(CmdError::Usage);
+        }
+        Err(AliasError::Invalid(name)) => {
+            eprintln!("{}", md(t!("jv.fail.alias.invalid", name = name)));
+            return Err(CmdError::Usage);
+        }
+    };
+
+    // Every command starts from the same directory, the ones before may move to the workspace root
+    let dir = current_dir().ok();
+    for args in commands {
+        if let Some(dir) = &dir {
+            let _ = set_current_dir(dir);
+        }
+        run_command(args, &aliases, required_outdated_minutes).await?;
+    }
+
+    Ok(())
+}
+
+async fn run_command(
+    args: Vec<String>,
+    aliases: &Aliases,
+    required_outdated_minutes: i64,
+) -> CmdResult {
+    let parsed = JustEnoughVcsWorkspace::try_parse_from(&args);
+    if let Err(err) = &parsed {
+        print_parse_error(err);
+    }
+
+    let Ok(parser) = parsed else {
+        suggest_subcommand(&args, aliases);
 
         // Tips
         // Guide to create
@@ -905,28 +1324,28 @@ async fn main() {
             let Some(local_dir) = current_local_path() else {
                 println!();
                 println!("{}", t!("jv.tip.not_workspace").trim().yellow());
-                return;
+                return Err(CmdError::Usage);
             };
 
             let _ = correct_current_dir();
 
             // Check if account list is not empty
             let Some(dir) = UserDirectory::current_cfg_dir() else {
-                return;
+                return Err(CmdError::Usage);
             };
 
             if let Ok(ids) = dir.account_ids() {
                 if ids.len() < 1 {
                     println!();
                     println!("{}", t!("jv.tip.no_account").trim().yellow());
-                    return;
+                    return Err(CmdError::Usage);
                 }
             }
 
             // Check if the workspace has a registered account (account = unknown)
             let Some(local_cfg) = LocalConfig::read().await.ok() else {
                 eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-                return;
+                return Err(CmdError::Usage);
             };
 
             // Account exists check
@@ -950,7 +1369,7 @@ async fn main() {
                         .trim()
                         .yellow()
                     );
-                    return;
+                    return Err(CmdError::Usage);
                 }
             }
 
@@ -961,7 +1380,7 @@ async fn main() {
             ))
             .await
             else {
-                return;
+                return Err(CmdError::Usage);
             };
             if let Some(instant) = latest_info.update_instant {
                 let now = SystemTime::now();
@@ -982,7 +1401,7 @@ async fn main() {
             }
         }
 
-        return;
+        return Err(CmdError::Usage);
     };
 
     match parser.command {
@@ -1065,7 +1484,7 @@ async fn main() {
                 Some(dir) => dir,
                 None => {
                     eprintln!("{}", t!("jv.fail.account.no_user_dir"));
-                    return;
+                    return Err(CmdError::ConfigNotFound);
                 }
             };
 
@@ -1076,223 +1495,292 @@ async fn main() {
                 AccountManage::Add(account_add_args) => {
                     if account_add_args.help {
                         println!("{}", md(t!("jv.account")));
-                        return;
+                        return Ok(());
                     }
-                    jv_account_add(user_dir, account_add_args).await;
+                    jv_account_add(user_dir, account_add_args).await?;
                 }
                 AccountManage::Remove(account_remove_args) => {
                     if account_remove_args.help {
                         println!("{}", md(t!("jv.account")));
-                        return;
+                        return Ok(());
                     }
-                    jv_account_remove(user_dir, account_remove_args).await;
+                    jv_account_remove(user_dir, account_remove_args).await?;
                 }
                 AccountManage::List(account_list_args) => {
                     if account_list_args.help {
                         println!("{}", md(t!("jv.account")));
-                        return;
+                        return Ok(());
                     }
-                    jv_account_list(user_dir, account_list_args).await;
+                    jv_account_list(user_dir, account_list_args).await?;
                 }
                 AccountManage::As(set_local_workspace_account_args) => {
                     if set_local_workspace_account_args.help {
                         println!("{}", md(t!("jv.account")));
-                        return;
+                        return Ok(());
                     }
-                    jv_account_as(user_dir, set_local_workspace_account_args).await;
+                    jv_account_as(user_dir, set_local_workspace_account_args).await?;
                 }
                 AccountManage::MoveKey(move_key_to_account_args) => {
                     if move_key_to_account_args.help {
                         println!("{}", md(t!("jv.account")));
-                        return;
+                        return Ok(());
                     }
-                    jv_account_move_key(user_dir, move_key_to_account_args).await;
+                    jv_account_move_key(user_dir, move_key_to_account_args).await?;
                 }
                 AccountManage::GeneratePublicKey(generate_public_key_args) => {
                     if generate_public_key_args.help {
                         println!("{}", md(t!("jv.account")));
-                        return;
+                        return Ok(());
                     }
-                    jv_account_generate_pub_key(user_dir, generate_public_key_args).await;
+                    jv_account_generate_pub_key(user_dir, generate_public_key_args).await?;
                 }
             }
         }
         JustEnoughVcsWorkspaceCommand::Create(create_workspace_args) => {
             if create_workspace_args.help {
                 println!("{}", md(t!("jv.create")));
-                return;
+                return Ok(());
             }
-            jv_create(create_workspace_args).await;
+            jv_create(create_workspace_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Init(init_workspace_args) => {
             if init_workspace_args.help {
                 println!("{}", md(t!("jv.init")));
-                return;
+                return Ok(());
             }
-            jv_init(init_workspace_args).await;
+            jv_init(init_workspace_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Here(here_args) => {
             if here_args.help {
                 println!("{}", md(t!("jv.here")));
-                return;
+                return Ok(());
             }
-            jv_here(here_args).await;
+            jv_here(here_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Status(status_args) => {
             if status_args.help {
                 println!("{}", md(t!("jv.status")));
-                return;
+                return Ok(());
             }
-            jv_status(status_args).await;
+            jv_status(status_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Info(info_args) => {
             if info_args.help {
                 println!("{}", md(t!("jv.info")));
-                return;
+                return Ok(());
+            }
+            jv_info(info_args).await?;
+        }
+        JustEnoughVcsWorkspaceCommand::Diff(diff_args) => {
+            if diff_args.help {
+                println!("{}", md(t!("jv.diff")));
+                return Ok(());
+            }
+            jv_diff(diff_args).await?;
+        }
+        JustEnoughVcsWorkspaceCommand::Restore(restore_args) => {
+            if restore_args.help {
+                println!("{}", md(t!("jv.restore")));
+                return Ok(());
+            }
+            jv_restore(restore_args).await?;
+        }
+        JustEnoughVcsWorkspaceCommand::Checkout(checkout_args) => {
+            if checkout_args.help {
+                println!("{}", md(t!("jv.checkout")));
+                return Ok(());
+            }
+            jv_checkout(checkout_args).await?;
+        }
+        JustEnoughVcsWorkspaceCommand::Log(log_args) => {
+            if log_args.help {
+                println!("{}", md(t!("jv.log")));
+                return Ok(());
             }
-            jv_info(info_args).await;
+            jv_log(log_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Sheet(sheet_manage) => match sheet_manage {
             SheetManage::Help => {
                 println!("{}", md(t!("jv.sheet")));
-                return;
-            }
-            SheetManage::List(sheet_list_args) => jv_sheet_list(sheet_list_args).await,
-            SheetManage::Use(sheet_use_args) => jv_sheet_use(sheet_use_args).await,
-            SheetManage::Exit(sheet_exit_args) => {
-                let _ = jv_sheet_exit(sheet_exit_args).await;
-            }
-            SheetManage::Make(sheet_make_args) => jv_sheet_make(sheet_make_args).await,
-            SheetManage::Drop(sheet_drop_args) => jv_sheet_drop(sheet_drop_args).await,
-            SheetManage::Align(sheet_align_args) => jv_sheet_align(sheet_align_args).await,
+                return Ok(());
+            }
+            SheetManage::List(sheet_list_args) => jv_sheet_list(sheet_list_args).await?,
+            SheetManage::Use(sheet_use_args) => jv_sheet_use(sheet_use_args).await?,
+            SheetManage::Exit(sheet_exit_args) => jv_sheet_exit(sheet_exit_args).await?,
+            SheetManage::Make(sheet_make_args) => jv_sheet_make(sheet_make_args).await?,
+            SheetManage::Drop(sheet_drop_args) => jv_sheet_drop(sheet_drop_args).await?,
+            SheetManage::Align(sheet_align_args) => jv_sheet_align(sheet_align_args).await?,
         },
         JustEnoughVcsWorkspaceCommand::Track(track_file_args) => {
             if track_file_args.help {
                 println!("{}", md(t!("jv.track")));
-                return;
+                return Ok(());
             }
-            jv_track(track_file_args).await;
+            jv_track(track_file_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Hold(hold_file_args) => {
             if hold_file_args.help {
                 println!("{}", md(t!("jv.hold")));
-                return;
+                return Ok(());
             }
-            jv_hold(hold_file_args).await;
+            jv_hold(hold_file_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Throw(throw_file_args) => {
             if throw_file_args.help {
                 println!("{}", md(t!("jv.throw")));
-                return;
+                return Ok(());
             }
-            jv_throw(throw_file_args).await;
+            jv_throw(throw_file_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Move(move_file_args) => {
             if move_file_args.help {
                 println!("{}", md(t!("jv.move")));
-                return;
+                return Ok(());
             }
-            jv_move(move_file_args).await;
+            jv_move(move_file_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Share(share_file_args) => {
             if share_file_args.help {
                 println!("{}", md(t!("jv.share")));
-                return;
+                return Ok(());
             }
-            jv_share(share_file_args).await;
+            jv_share(share_file_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Update(update_file_args) => {
             if update_file_args.help {
                 println!("{}", md(t!("jv.update")));
-                return;
-            }
-            jv_update(update_file_args).await;
+                return Ok(());
+            }
+            // Hooks only run for explicit updates, not for the automatic ones.
+            // What changes is only known afterwards, so the post hook gets the changed files
+            let before = sheet_file_versions().await;
+            run_pre_hook("update", &[]).await?;
+            let result = jv_update(update_file_args).await;
+            let after = sheet_file_versions().await;
+            let mut changed: Vec<PathBuf> = before
+                .keys()
+                .chain(after.keys())
+                .filter(|path| before.get(*path) != after.get(*path))
+                .cloned()
+                .collect();
+            changed.sort();
+            changed.dedup();
+            run_post_hook("update", &changed, &result).await;
+            result?;
         }
         JustEnoughVcsWorkspaceCommand::Direct(direct_args) => {
             if direct_args.help {
                 println!("{}", md(t!("jv.direct")));
-                return;
+                return Ok(());
             }
-            jv_direct(direct_args).await;
+            jv_direct(direct_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Unstain(unstain_args) => {
             if unstain_args.help {
                 println!("{}", md(t!("jv.unstain")));
-                return;
+                return Ok(());
             }
-            jv_unstain(unstain_args).await;
+            jv_unstain(unstain_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Docs(docs_args) => {
             if docs_args.help {
                 println!("{}", md(t!("jv.docs")));
-                return;
+                return Ok(());
             }
-            jv_docs(docs_args).await;
+            jv_docs(docs_args).await?;
         }
-        JustEnoughVcsWorkspaceCommand::Exit => {
-            let _ = jv_sheet_exit(SheetExitArgs { help: false }).await;
-        }
-        JustEnoughVcsWorkspaceCommand::Use(use_args) => {
-            if let Ok(_) = jv_sheet_exit(SheetExitArgs { help: false }).await {
-                jv_sheet_use(SheetUseArgs {
-                    help: false,
-                    sheet_name: use_args.sheet_name,
-                })
-                .await;
+        JustEnoughVcsWorkspaceCommand::Config(config_manage) => match config_manage {
+            ConfigManage::Help => {
+                println!("{}", md(t!("jv.config")));
+                return Ok(());
             }
+            ConfigManage::List(config_list_args) => {
+                if config_list_args.help {
+                    println!("{}", md(t!("jv.config")));
+                    return Ok(());
+                }
+                jv_config_list(config_list_args).await?;
+            }
+            ConfigManage::Get(config_get_args) => {
+                if config_get_args.help {
+                    println!("{}", md(t!("jv.config")));
+                    return Ok(());
+                }
+                jv_config_get(config_get_args).await?;
+            }
+            ConfigManage::Set(config_set_args) => {
+                if config_set_args.help {
+                    println!("{}", md(t!("jv.config")));
+                    return Ok(());
+                }
+                jv_config_set(config_set_args).await?;
+            }
+            ConfigManage::Unset(config_unset_args) => {
+                if config_unset_args.help {
+                    println!("{}", md(t!("jv.config")));
+                    return Ok(());
+                }
+                jv_config_unset(config_unset_args).await?;
+            }
+        },
+        JustEnoughVcsWorkspaceCommand::Exit(sheet_exit_args) => {
+            if sheet_exit_args.help {
+                println!("{}", md(t!("jv.sheet")));
+                return Ok(());
+            }
+            jv_sheet_exit(sheet_exit_args).await?;
+        }
+        JustEnoughVcsWorkspaceCommand::Use(sheet_use_args) => {
+            if sheet_use_args.help {
+                println!("{}", md(t!("jv.sheet")));
+                return Ok(());
+            }
+            jv_sheet_exit(SheetExitArgs { help: false }).await?;
+            jv_sheet_use(sheet_use_args).await?;
+        }
+        JustEnoughVcsWorkspaceCommand::Sheets(sheet_list_args) => {
+            if sheet_list_args.help {
+                println!("{}", md(t!("jv.sheet")));
+                return Ok(());
+            }
+            jv_sheet_list(sheet_list_args).await?;
         }
-        JustEnoughVcsWorkspaceCommand::Sheets => {
-            jv_sheet_list(SheetListArgs {
-                help: false,
-                others: false,
-                all: false,
-                raw: false,
-                json_output: false,
-                pretty: false,
-            })
-            .await
-        }
-        JustEnoughVcsWorkspaceCommand::Accounts => {
+        JustEnoughVcsWorkspaceCommand::Accounts(account_list_args) => {
+            if account_list_args.help {
+                println!("{}", md(t!("jv.account")));
+                return Ok(());
+            }
             let user_dir = match UserDirectory::current_cfg_dir() {
                 Some(dir) => dir,
                 None => {
                     eprintln!("{}", t!("jv.fail.account.no_user_dir"));
-                    return;
+                    return Err(CmdError::ConfigNotFound);
                 }
             };
-            jv_account_list(
-                user_dir,
-                AccountListArgs {
-                    help: false,
-                    raw: false,
-                    json_output: false,
-                    pretty: false,
-                },
-            )
-            .await
+            jv_account_list(user_dir, account_list_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Align(sheet_align_args) => {
             if sheet_align_args.help {
                 println!("{}", md(t!("jv.align")));
-                return;
+                return Ok(());
             }
-            jv_sheet_align(sheet_align_args).await
+            jv_sheet_align(sheet_align_args).await?;
         }
         JustEnoughVcsWorkspaceCommand::As(args) => {
             let user_dir = match UserDirectory::current_cfg_dir() {
                 Some(dir) => dir,
                 None => {
                     eprintln!("{}", t!("jv.fail.account.no_user_dir"));
-                    return;
+                    return Err(CmdError::ConfigNotFound);
                 }
             };
-            jv_account_as(user_dir, args).await
+            jv_account_as(user_dir, args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Make(args) => {
-            jv_sheet_make(args).await;
+            jv_sheet_make(args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Drop(args) => {
-            jv_sheet_drop(args).await;
+            jv_sheet_drop(args).await?;
         }
         JustEnoughVcsWorkspaceCommand::Login(args) => {
             if !args.confirm {
@@ -1306,14 +1794,17 @@ async fn main() {
                     .trim()
                     .yellow()
                 );
-                confirm_hint_or(t!("common.confirm"), || exit(1)).await;
+                confirm_hint_or(t!("common.confirm"), || {
+                    exit(CmdError::Cancelled.exit_code())
+                })
+                .await;
             }
 
             let user_dir = match UserDirectory::current_cfg_dir() {
                 Some(dir) => dir,
                 None => {
                     eprintln!("{}", t!("jv.fail.account.no_user_dir"));
-                    return;
+                    return Err(CmdError::ConfigNotFound);
                 }
             };
 
@@ -1324,20 +1815,24 @@ async fn main() {
                     account_name: args.login_member_id,
                 },
             )
-            .await;
+            .await?;
 
             jv_direct(DirectArgs {
                 help: false,
                 upstream: Some(args.upstream.clone()),
                 confirm: true,
+                json_output: false,
+                pretty: false,
             })
-            .await;
+            .await?;
 
             jv_update(UpdateArgs {
                 help: false,
                 silent: true,
+                json_output: false,
+                pretty: false,
             })
-            .await;
+            .await?;
 
             if let Some(local_dir) = current_local_path() {
                 let _ = fs::remove_file(local_dir.join(CLIENT_FILE_TODOLIST)).await;
@@ -1354,30 +1849,30 @@ async fn main() {
         JustEnoughVcsWorkspaceCommand::GetWorkspaceDir => {
             if let Some(local_dir) = current_local_path() {
                 println!("{}", local_dir.display());
-                return;
+                return Ok(());
             };
-            exit(1)
+            return Err(CmdError::NotWorkspace);
         }
         JustEnoughVcsWorkspaceCommand::GetCurrentAccount => {
             let _ = correct_current_dir();
             if let Ok(local_config) = LocalConfig::read().await {
                 if local_config.is_host_mode() {
                     println!("host/{}", local_config.current_account());
-                    return;
+                    return Ok(());
                 } else {
                     println!("{}", local_config.current_account());
-                    return;
+                    return Ok(());
                 }
             };
-            exit(1)
+            return Err(CmdError::ConfigNotFound);
         }
         JustEnoughVcsWorkspaceCommand::GetCurrentUpstream => {
             let _ = correct_current_dir();
             if let Ok(local_config) = LocalConfig::read().await {
                 println!("{}", local_config.upstream_addr());
-                return;
+                return Ok(());
             };
-            exit(1)
+            return Err(CmdError::ConfigNotFound);
         }
         JustEnoughVcsWorkspaceCommand::GetCurrentSheet => {
             let _ = correct_current_dir();
@@ -1385,60 +1880,66 @@ async fn main() {
                 let sheet_name = local_config.sheet_in_use().clone().unwrap_or_default();
                 if sheet_name.len() > 0 {
                     println!("{}", sheet_name);
-                    return;
+                    return Ok(());
                 }
             };
-            exit(1)
+            return Err(CmdError::NoSheetInUse);
         }
 
         // Debug Tools
         JustEnoughVcsWorkspaceCommand::DebugGlob(glob_args) => {
-            jv_debug_glob(glob_args).await;
+            jv_debug_glob(glob_args).await?;
         }
     }
+
+    Ok(())
 }
 
-async fn jv_create(args: CreateWorkspaceArgs) {
+async fn jv_create(args: CreateWorkspaceArgs) -> CmdResult {
     let Some(path) = args.path else {
         println!("{}", md(t!("jv.create")));
-        return;
+        return Err(CmdError::Usage);
     };
 
     if !args.force && path.exists() && !is_directory_empty(&path).await {
         eprintln!("{}", t!("jv.fail.init_create_dir_not_empty").trim());
-        return;
+        return Err(CmdError::Rejected);
     }
 
     match LocalWorkspace::setup_local_workspace(path).await {
         Ok(_) => {
             println!("{}", t!("jv.success.create"));
+            Ok(())
         }
         Err(e) => {
             eprintln!("{}", t!("jv.fail.create", error = e.to_string()));
+            Err(CmdError::Failed)
         }
     }
 }
 
-async fn jv_init(_args: InitWorkspaceArgs) {
+async fn jv_init(_args: InitWorkspaceArgs) -> CmdResult {
     let path = match current_dir() {
         Ok(path) => path,
         Err(e) => {
             eprintln!("{}", t!("jv.fail.get_current_dir", error = e.to_string()));
-            return;
+            return Err(CmdError::Failed);
         }
     };
 
     if path.exists() && !is_directory_empty(&path).await {
         eprintln!("{}", t!("jv.fail.init_create_dir_not_empty").trim());
-        return;
+        return Err(CmdError::Rejected);
     }
 
     match LocalWorkspace::setup_local_workspace(path).await {
         Ok(_) => {
             println!("{}", t!("jv.success.init"));
+            Ok(())
         }
         Err(e) => {
             eprintln!("{}", t!("jv.fail.init", error = e.to_string()));
+            Err(CmdError::Failed)
         }
     }
 }
@@ -1450,15 +1951,15 @@ async fn is_directory_empty(path: &PathBuf) -> bool {
     }
 }
 
-async fn jv_here(args: HereArgs) {
+async fn jv_here(args: HereArgs) -> CmdResult {
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(local_cfg) = LocalConfig::read_from(local_dir.join(CLIENT_FILE_WORKSPACE)).await else {
         eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
@@ -1474,7 +1975,7 @@ async fn jv_here(args: HereArgs) {
                 account = &local_cfg.current_account()
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Ok(latest_file_data_path) = LatestFileData::data_path(&local_cfg.current_account()) else {
@@ -1485,7 +1986,7 @@ async fn jv_here(args: HereArgs) {
                 account = &local_cfg.current_account()
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
@@ -1496,7 +1997,7 @@ async fn jv_here(args: HereArgs) {
                 account = &local_cfg.current_account()
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     // Print path information
@@ -1515,17 +2016,17 @@ async fn jv_here(args: HereArgs) {
                 sheet = &sheet_name
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
         eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(analyzed) = AnalyzeResult::analyze_local_status(&local_workspace).await else {
         eprintln!("{}", md(t!("jv.fail.status.analyze")).trim());
-        return;
+        return Err(CmdError::Failed);
     };
 
     // Read local sheet
@@ -1541,14 +2042,14 @@ async fn jv_here(args: HereArgs) {
                 sheet = &sheet_name
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let path = match current_dir() {
         Ok(path) => path,
         Err(_) => {
             eprintln!("{}", t!("jv.fail.get_current_dir"));
-            return;
+            return Err(CmdError::Failed);
         }
     };
 
@@ -1556,7 +2057,7 @@ async fn jv_here(args: HereArgs) {
         Some(path) => path,
         None => {
             eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-            return;
+            return Err(CmdError::NotWorkspace);
         }
     };
 
@@ -1710,7 +2211,7 @@ async fn jv_here(args: HereArgs) {
         }
 
         print_json(json_result, args.pretty);
-        return;
+        return Ok(());
     }
 
     let account_str = if local_cfg.is_host_mode() {
@@ -2019,17 +2520,19 @@ async fn jv_here(args: HereArgs) {
         )
         .trim()
     );
+
+    Ok(())
 }
 
-async fn jv_status(args: StatusArgs) {
+async fn jv_status(args: StatusArgs) -> CmdResult {
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(local_cfg) = LocalConfig::read_from(local_dir.join(CLIENT_FILE_WORKSPACE)).await else {
         eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
@@ -2045,7 +2548,7 @@ async fn jv_status(args: StatusArgs) {
                 account = &local_cfg.current_account()
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let account = local_cfg.current_account();
@@ -2058,7 +2561,7 @@ async fn jv_status(args: StatusArgs) {
                 account = &account
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
@@ -2069,17 +2572,17 @@ async fn jv_status(args: StatusArgs) {
                 account = &account
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
         eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
-        return;
+        return Err(CmdError::NoSheetInUse);
     };
 
     let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
         eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
@@ -2091,17 +2594,26 @@ async fn jv_status(args: StatusArgs) {
                 sheet = &sheet_name
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let in_ref_sheet = latest_info.reference_sheets.contains(&sheet_name);
     let is_host_mode = local_cfg.is_host_mode();
 
-    let Ok(analyzed) = AnalyzeResult::analyze_local_status(&local_workspace).await else {
+    let Ok(mut analyzed) = AnalyzeResult::analyze_local_status(&local_workspace).await else {
         eprintln!("{}", md(t!("jv.fail.status.analyze")).trim());
-        return;
+        return Err(CmdError::Failed);
     };
 
+    // Files matching `.jvignore` are not shown
+    filter_ignored(&local_dir, &mut analyzed, |path| {
+        local_sheet
+            .mapping_data(path)
+            .ok()
+            .map(|mapping| mapping.hash_when_updated().clone())
+    })
+    .await;
+
     let mut created_items: Vec<String>;
     let mut erased_items: Vec<String>;
     let mut lost_items: Vec<String>;
@@ -2182,7 +2694,7 @@ async fn jv_status(args: StatusArgs) {
         };
 
         print_json(json_result, args.pretty);
-        return;
+        return Ok(());
     } else {
         // Format created items
         created_items = analyzed
@@ -2421,49 +2933,205 @@ async fn jv_status(args: StatusArgs) {
     if is_host_mode {
         println!("\n{}", md(t!("jv.success.status.hint_as_host")));
     }
+
+    Ok(())
 }
 
-async fn jv_info(args: InfoArgs) {
+async fn jv_diff(args: DiffArgs) -> CmdResult {
     let local_dir = match current_local_path() {
         Some(dir) => dir,
         None => {
             eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-            return;
+            return Err(CmdError::NotWorkspace);
         }
     };
 
     let query_file_paths = if let Some(pattern) = args.file_pattern.clone() {
-        let files = glob(pattern, &local_dir).await;
+        let files = glob(pattern, &local_dir, false).await;
         files
             .iter()
             .filter_map(|f| PathBuf::from_str(f.0).ok())
             .collect::<Vec<_>>()
     } else {
-        println!("{}", md(t!("jv.info")));
-        return;
+        println!("{}", md(t!("jv.diff")));
+        return Err(CmdError::Usage);
     };
 
     let _ = correct_current_dir();
 
     let Ok(local_cfg) = LocalConfig::read().await else {
         eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
-    let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
-        &local_dir,
-        &local_cfg.current_account(),
-    ))
-    .await
-    else {
+    let account = local_cfg.current_account();
+
+    let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
+        eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
+        return Err(CmdError::NoSheetInUse);
+    };
+
+    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
+        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
+        return Err(CmdError::NotWorkspace);
+    };
+
+    let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
         eprintln!(
             "{}",
             md(t!(
-                "jv.fail.cfg_not_found.latest_info",
-                account = &local_cfg.current_account()
+                "jv.fail.cfg_not_found.local_sheet",
+                account = &account,
+                sheet = &sheet_name
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let mut items = Vec::new();
+    for path in query_file_paths {
+        // Only mapped files have a base version to compare with
+        let Ok(mapping) = local_sheet.mapping_data(&path) else {
+            continue;
+        };
+
+        let vfid = mapping.mapping_vfid().clone();
+        let base_version = mapping.version_when_updated().clone();
+        let full_path = local_dir.join(&path);
+
+        let base = read_base_snapshot(&local_dir, &vfid, &base_version).await;
+        let current = fs::read(&full_path).await.ok();
+
+        let mut item = DiffItem {
+            mapping: format_path_str(path.display().to_string()).unwrap_or_default(),
+            vfid,
+            base_version,
+            status: DiffStatus::Lost,
+            is_binary: false,
+            base_hash: mapping.hash_when_updated().clone(),
+            current_hash: String::new(),
+            base_size: base.as_ref().map(|b| b.len() as u64),
+            current_size: current.as_ref().map(|c| c.len() as u64),
+            diff: None,
+        };
+
+        if let Some(current) = current {
+            item.current_hash = match sha1_hash::calc_sha1(&full_path, 4096usize).await {
+                Ok(hash) => hash.hash,
+                Err(_) => String::new(),
+            };
+            item.is_binary = is_binary(&current) || base.as_ref().is_some_and(|b| is_binary(b));
+            item.status = if item.current_hash == item.base_hash {
+                DiffStatus::Unchanged
+            } else if let Some(base) = base {
+                if !item.is_binary {
+                    item.diff = Some(unified_diff(
+                        &String::from_utf8_lossy(&base),
+                        &String::from_utf8_lossy(&current),
+                        &format!("{}@{}", item.mapping, item.base_version),
+                        &item.mapping,
+                        args.context,
+                    ));
+                }
+                DiffStatus::Modified
+            } else {
+                DiffStatus::BaseNotCached
+            };
+        }
+
+        items.push(item);
+    }
+
+    if args.json_output {
+        print_json(DiffJsonResult { items }, args.pretty);
+        return Ok(());
+    }
+
+    if items.is_empty() {
+        eprintln!("{}", md(t!("jv.fail.diff.no_selection")));
+        return Err(CmdError::NotFound);
+    }
+
+    if items
+        .iter()
+        .all(|item| item.status == DiffStatus::Unchanged)
+    {
+        println!("{}", md(t!("jv.success.diff.no_changes")));
+        return Ok(());
+    }
+
+    for item in items {
+        match item.status {
+            DiffStatus::Unchanged => {}
+            DiffStatus::Modified => {
+                if let Some(diff) = item.diff {
+                    println!("{}", render_unified_diff(&diff));
+                } else {
+                    println!(
+                        "{}",
+                        md(t!(
+                            "jv.success.diff.binary",
+                            path = item.mapping,
+                            version = item.base_version,
+                            base_size = size_str(item.base_size.unwrap_or_default() as usize),
+                            current_size = size_str(item.current_size.unwrap_or_default() as usize),
+                            base_hash = item.base_hash,
+                            current_hash = item.current_hash
+                        ))
+                    );
+                }
+            }
+            DiffStatus::BaseNotCached => {
+                println!(
+                    "{}",
+                    md(t!(
+                        "jv.success.diff.base_not_cached",
+                        path = item.mapping,
+                        version = item.base_version,
+                        current_size = size_str(item.current_size.unwrap_or_default() as usize),
+                        base_hash = item.base_hash,
+                        current_hash = item.current_hash
+                    ))
+                    .yellow()
+                );
+            }
+            DiffStatus::Lost => {
+                println!(
+                    "{}",
+                    md(t!("jv.success.diff.lost", path = item.mapping)).red()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn jv_restore(args: RestoreArgs) -> CmdResult {
+    let local_dir = match current_local_path() {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+            return Err(CmdError::NotWorkspace);
+        }
+    };
+
+    let query_file_paths = if let Some(pattern) = args.file_pattern.clone() {
+        let files = glob(pattern, &local_dir, false).await;
+        files
+            .iter()
+            .filter_map(|f| PathBuf::from_str(f.0).ok())
+            .collect::<Vec<_>>()
+    } else {
+        println!("{}", md(t!("jv.restore")));
+        return Err(CmdError::Usage);
+    };
+
+    let _ = correct_current_dir();
+
+    let Ok(local_cfg) = LocalConfig::read().await else {
+        eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
+        return Err(CmdError::ConfigNotFound);
     };
 
     let account = local_cfg.current_account();
@@ -2476,10 +3144,9 @@ async fn jv_info(args: InfoArgs) {
                 account = &account
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
-    // Get latest file data
     let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
         eprintln!(
             "{}",
@@ -2488,20 +3155,20 @@ async fn jv_info(args: InfoArgs) {
                 account = &account
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
         eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
-        return;
+        return Err(CmdError::NoSheetInUse);
     };
 
     let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
         eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
-    let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
+    let Ok(mut local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
         eprintln!(
             "{}",
             md(t!(
@@ -2510,715 +3177,578 @@ async fn jv_info(args: InfoArgs) {
                 sheet = &sheet_name
             ))
         );
-        return;
-    };
-
-    if query_file_paths.len() < 1 {
-        return;
-    }
-    // File to query
-    let query_file_path = query_file_paths[0].to_path_buf();
-    let Ok(mapping) = local_sheet.mapping_data(&query_file_path) else {
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
-    let vfid = mapping.mapping_vfid();
-
-    let query_file_path_string =
-        format_path_str(query_file_path.display().to_string()).unwrap_or_default();
-
-    // JSON output handling
-    if args.json_output {
-        let mut json_result = InfoJsonResult::default();
-        json_result.mapping = query_file_path_string.clone();
 
-        // Get reference sheet path
-        json_result.in_ref = if let Some(path) = latest_info.ref_sheet_vfs_mapping.get(vfid) {
-            path.display().to_string()
-        } else {
-            vfid.clone()
+    // Collect modified and lost files, and where their base content is kept.
+    // Without a snapshot, the base version is downloaded again if it is still the latest one
+    let mut restore_files: Vec<(PathBuf, PathBuf, bool)> = Vec::new();
+    let mut sync_files: Vec<PathBuf> = Vec::new();
+    let mut not_cached_files: Vec<PathBuf> = Vec::new();
+    for path in query_file_paths {
+        let Ok(mapping) = local_sheet.mapping_data(&path) else {
+            continue;
         };
 
-        json_result.vfid = vfid.clone();
+        let full_path = local_dir.join(&path);
+        let lost = !full_path.exists();
+        if !lost {
+            let Ok(hash_calc) = sha1_hash::calc_sha1(&full_path, 4096usize).await else {
+                continue;
+            };
+            if &hash_calc.hash == mapping.hash_when_updated() {
+                continue;
+            }
+        }
 
-        // Get file version in reference sheet
-        let version_in_ref = if let Some(mapping) = latest_info
-            .ref_sheet_content
-            .mapping()
-            .get(&query_file_path)
+        let vfid = mapping.mapping_vfid();
+        let snapshot = base_snapshot_path(&local_dir, vfid, mapping.version_when_updated());
+        if snapshot.exists() {
+            restore_files.push((path, snapshot, lost));
+        } else if !lost
+            && latest_file_data.file_version(vfid) == Some(mapping.version_when_updated())
+            && latest_file_data.file_holder(vfid) != Some(&account)
         {
-            mapping.version.clone()
+            sync_files.push(path);
         } else {
-            "".to_string()
-        };
+            not_cached_files.push(path);
+        }
+    }
 
-        // Get current file version
-        let version_current = latest_file_data
-            .file_version(vfid)
-            .cloned()
-            .unwrap_or_else(|| "".to_string());
+    if !not_cached_files.is_empty() {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.restore.base_not_cached",
+                files = not_cached_files
+                    .iter()
+                    .map(|f| f.display().to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ))
+            .yellow()
+        );
+    }
 
-        // Check if file is being edited based on latest version (regardless of hold status)
-        let modified_correctly = if let Ok(mapping) = local_sheet.mapping_data(&query_file_path) {
-            // If base editing version is correct
-            if mapping.version_when_updated() == &version_current {
-                mapping.last_modifiy_check_result() // Return detection result
-            } else {
-                false
-            }
-        } else {
-            false
-        };
+    if restore_files.is_empty() && sync_files.is_empty() {
+        println!("{}", md(t!("jv.success.restore.nothing")));
+        return Ok(());
+    }
 
-        // Build history list
-        if let Some(histories) = latest_file_data.file_histories(vfid) {
-            for (version, description) in histories {
-                json_result.histories.push(InfoHistory {
-                    version: version.clone(),
-                    version_creator: description.creator.clone(),
-                    version_description: description.description.clone(),
-                    is_current_version: version == &version_current,
-                    is_ref_version: version == &version_in_ref,
-                });
+    if !args.confirm {
+        println!(
+            "{}",
+            md(t!(
+                "jv.confirm.restore",
+                count = restore_files.len() + sync_files.len(),
+                files = restore_files
+                    .iter()
+                    .map(|(path, _, lost)| if *lost {
+                        format!("{} (lost)", path.display())
+                    } else {
+                        path.display().to_string()
+                    })
+                    .chain(sync_files.iter().map(|path| path.display().to_string()))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ))
+            .yellow()
+        );
+        confirm_hint_or(t!("common.confirm"), || {
+            exit(CmdError::Cancelled.exit_code())
+        })
+        .await;
+    }
+
+    let backup_dir = local_dir
+        .join(CLIENT_PATH_WORKSPACE_ROOT)
+        .join(".backup")
+        .join(chrono::Local::now().format("%Y-%m-%d-%H-%M-%S").to_string());
+
+    let mut restored = 0;
+    for (path, snapshot, lost) in restore_files {
+        let full_path = local_dir.join(&path);
+
+        // Move the current content aside
+        if args.keep_backup && !lost {
+            let backup_path = backup_dir.join(&path);
+            if let Some(parent) = backup_path.parent() {
+                let _ = fs::create_dir_all(parent).await;
+            }
+            if let Err(e) = move_across_partitions(&full_path, &backup_path).await {
+                eprintln!(
+                    "{}",
+                    md(t!(
+                        "jv.fail.restore.backup_failed",
+                        path = path.display(),
+                        error = e
+                    ))
+                );
+                continue;
             }
         }
 
-        // Add current modification if exists
-        if modified_correctly {
-            json_result.histories.insert(
-                0,
-                InfoHistory {
-                    version: "CURRENT".to_string(),
-                    version_creator: local_workspace
-                        .config()
-                        .lock()
-                        .await
-                        .current_account()
-                        .clone(),
-                    version_description: t!("jv.success.info.oneline.description_current")
-                        .to_string(),
-                    is_current_version: true,
-                    is_ref_version: false,
-                },
+        if let Some(parent) = full_path.parent() {
+            let _ = fs::create_dir_all(parent).await;
+        }
+        if let Err(e) = fs::copy(&snapshot, &full_path).await {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.restore.restore_failed",
+                    path = path.display(),
+                    error = e
+                ))
             );
+            continue;
         }
 
-        print_json(json_result, args.pretty);
-        return;
+        // The file now matches the base version again
+        if let Ok(mapping) = local_sheet.mapping_data_mut(&path) {
+            mapping.set_last_modifiy_check_hash(Some(mapping.hash_when_updated().clone()));
+        }
+        restored += 1;
     }
 
-    // Render initial location
-    {
-        println!("{}", query_file_path_string);
+    if let Err(e) = local_sheet.write().await {
+        eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
+        return Err(CmdError::Failed);
     }
 
-    // Render reference sheet location, use ID if not found
-    {
-        let path_in_ref = if let Some(path) = latest_info.ref_sheet_vfs_mapping.get(vfid) {
-            path.display().to_string()
-        } else {
-            vfid.clone()
-        };
+    // The sync replaces the files in place, so their content is copied aside
+    let mut sync_result = Ok(());
+    if !sync_files.is_empty() {
+        if args.keep_backup {
+            for path in &sync_files {
+                let backup_path = backup_dir.join(path);
+                if let Some(parent) = backup_path.parent() {
+                    let _ = fs::create_dir_all(parent).await;
+                }
+                if let Err(e) = fs::copy(local_dir.join(path), &backup_path).await {
+                    eprintln!(
+                        "{}",
+                        md(t!(
+                            "jv.fail.restore.backup_failed",
+                            path = path.display(),
+                            error = e
+                        ))
+                    );
+                    return Err(CmdError::Failed);
+                }
+            }
+        }
 
-        // Offset string
-        let offset_string = " ".repeat(display_width(
-            if let Some(last_slash) = query_file_path_string.rfind('/') {
-                &query_file_path_string[..last_slash]
-            } else {
-                ""
-            },
-        ));
+        match sync_latest_versions(&local_cfg, sync_files.into_iter().collect()).await {
+            Ok(synced) => restored += synced.len(),
+            Err(err) => sync_result = Err(err),
+        }
+    }
 
+    println!("{}", md(t!("jv.success.restore.done", count = restored)));
+    if args.keep_backup && backup_dir.exists() {
         println!(
-            "{}{}{}",
-            offset_string,
-            "\\_ ".truecolor(128, 128, 128),
-            path_in_ref.cyan()
-        );
+            "{}",
+            md(t!(
+                "jv.success.restore.backup_dir",
+                dir = backup_dir.display()
+            ))
+        );
     }
 
-    // Render complete file history
-    {
-        if let Some(histories) = latest_file_data.file_histories(vfid) {
-            // Get file version in reference sheet
-            let version_in_ref = if let Some(mapping) = latest_info
-                .ref_sheet_content
-                .mapping()
-                .get(&query_file_path)
-            {
-                mapping.version.clone()
-            } else {
-                "".to_string()
-            };
-
-            // Get current file version
-            let version_current = latest_file_data
-                .file_version(vfid)
-                .cloned()
-                .unwrap_or_else(|| "".to_string());
+    sync_result
+}
 
-            // Check if file is being edited based on latest version (regardless of hold status)
-            let modified_correctly = if let Ok(mapping) = local_sheet.mapping_data(&query_file_path)
-            {
-                // If base editing version is correct
-                if mapping.version_when_updated() == &version_current {
-                    mapping.last_modifiy_check_result() // Return detection result
-                } else {
-                    false
-                }
-            } else {
-                false
-            };
+async fn jv_checkout(args: CheckoutArgs) -> CmdResult {
+    let local_dir = match current_local_path() {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+            return Err(CmdError::NotWorkspace);
+        }
+    };
 
-            // Text
-            let (prefix_str, version_str, creator_str, description_str) = (
-                t!("jv.success.info.oneline.table_headers.prefix"),
-                t!("jv.success.info.oneline.table_headers.version"),
-                t!("jv.success.info.oneline.table_headers.creator"),
-                t!("jv.success.info.oneline.table_headers.description"),
-            );
+    let (Some(pattern), Some(version)) = (args.file_pattern.clone(), args.version.clone()) else {
+        println!("{}", md(t!("jv.checkout")));
+        return Err(CmdError::Usage);
+    };
 
-            // Single-line output
-            if !args.full {
-                // Create table
-                let mut table =
-                    SimpleTable::new(vec![prefix_str, version_str, creator_str, description_str]);
-
-                // Append data
-                for (version, description) in histories {
-                    // If it's reference version, render "@"
-                    // Current version, render "\_"
-                    // Other versions, render "|"
-                    let prefix = if version == &version_in_ref {
-                        "@".cyan().to_string()
-                    } else if version == &version_current {
-                        "|->".yellow().to_string()
-                    } else {
-                        "|".truecolor(128, 128, 128).to_string()
-                    };
+    // Resolve the output path before the current directory changes
+    let output = args
+        .output
+        .as_ref()
+        .map(|output| current_dir().unwrap_or_default().join(output));
 
-                    table.insert_item(
-                        0,
-                        vec![
-                            prefix,
-                            version.to_string(),
-                            format!("@{}: ", &description.creator.cyan()),
-                            truncate_first_line(description.description.to_string()),
-                        ],
-                    );
-                }
+    let query_file_paths = glob(pattern, &local_dir, false)
+        .await
+        .iter()
+        .filter_map(|f| PathBuf::from_str(f.0).ok())
+        .collect::<Vec<_>>();
 
-                // If file has new version, append
-                if modified_correctly {
-                    table.insert_item(
-                        0,
-                        vec![
-                            "+".green().to_string(),
-                            "CURRENT".green().to_string(),
-                            format!(
-                                "@{}: ",
-                                local_workspace
-                                    .config()
-                                    .lock()
-                                    .await
-                                    .current_account()
-                                    .cyan()
-                            ),
-                            format!(
-                                "{}",
-                                t!("jv.success.info.oneline.description_current").green()
-                            ),
-                        ],
-                    );
-                }
+    let _ = correct_current_dir();
 
-                // Render table
-                let table_str = table.to_string();
-                if table_str.lines().count() > 1 {
-                    println!();
-                }
-                for line in table_str.lines().skip(1) {
-                    println!("{}", line);
-                }
-            } else {
-                // Multi-line output
-                if histories.len() > 0 {
-                    println!();
-                }
-                for (version, description) in histories {
-                    println!("{}: {}", version_str, version);
-                    println!("{}: {}", creator_str, description.creator.cyan());
-                    println!("{}", description.description);
-                    if version != &histories.last().unwrap().0 {
-                        println!("{}", "-".repeat(45));
-                    }
-                }
-            }
-        }
-    }
-}
+    let Ok(local_cfg) = LocalConfig::read().await else {
+        eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
+        return Err(CmdError::ConfigNotFound);
+    };
 
-async fn jv_sheet_list(args: SheetListArgs) {
-    let _ = correct_current_dir();
+    let account = local_cfg.current_account();
 
-    let Some(local_dir) = current_local_path() else {
-        if !args.raw {
-            eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        }
-        return;
+    let Ok(latest_file_data_path) = LatestFileData::data_path(&account) else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_file_data",
+                account = &account
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
     };
 
-    let Ok(local_cfg) = LocalConfig::read().await else {
-        if !args.raw {
-            eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        }
-        return;
+    let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_file_data",
+                account = &account
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
     };
 
-    let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
-        &local_dir,
-        &local_cfg.current_account(),
-    ))
-    .await
-    else {
-        if !args.raw {
-            eprintln!(
-                "{}",
-                md(t!(
-                    "jv.fail.cfg_not_found.latest_info",
-                    account = &local_cfg.current_account()
-                ))
-            );
-        }
-        return;
+    let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
+        eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
+        return Err(CmdError::NoSheetInUse);
     };
 
-    let mut your_sheet_counts = 0;
-    let mut other_sheet_counts = 0;
+    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
+        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
+        return Err(CmdError::NotWorkspace);
+    };
 
-    // JSON output handling
-    if args.json_output {
-        let mut json_result = SheetListJsonResult::default();
+    let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.local_sheet",
+                account = &account,
+                sheet = &sheet_name
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
 
-        // Populate reference sheets
-        for sheet_name in &latest_info.reference_sheets {
-            json_result.reference_sheets.push(SheetItem {
-                name: sheet_name.clone(),
-                holder: local_cfg.current_account().clone(),
-            });
+    // Exactly one mapping can be checked out at a time
+    let mapped_files: Vec<PathBuf> = query_file_paths
+        .into_iter()
+        .filter(|p| local_sheet.mapping_data(p).is_ok())
+        .collect();
+    let [path] = mapped_files.as_slice() else {
+        if mapped_files.is_empty() {
+            eprintln!("{}", md(t!("jv.fail.checkout.no_selection")));
+            return Err(CmdError::NotFound);
         }
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.checkout.multiple_files",
+                count = mapped_files.len()
+            ))
+        );
+        return Err(CmdError::Usage);
+    };
+    let Ok(mapping) = local_sheet.mapping_data(path) else {
+        return Err(CmdError::NotFound);
+    };
+    let vfid = mapping.mapping_vfid();
 
-        // Populate other sheets
-        for sheet in &latest_info.invisible_sheets {
-            json_result.other_sheets.push(SheetItem {
-                name: sheet.sheet_name.clone(),
-                holder: sheet.holder_name.clone().unwrap_or_default(),
-            });
-        }
+    let version_exists = latest_file_data
+        .file_histories(vfid)
+        .is_some_and(|histories| histories.iter().any(|(v, _)| v == &version));
+    if !version_exists {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.checkout.version_not_found",
+                path = path.display(),
+                version = &version
+            ))
+        );
+        return Err(CmdError::NotFound);
+    }
 
-        // Populate my sheets (excluding reference sheets)
-        for sheet_name in &latest_info.visible_sheets {
-            if !latest_info.reference_sheets.contains(sheet_name) {
-                json_result.my_sheets.push(SheetItem {
-                    name: sheet_name.clone(),
-                    holder: local_cfg.current_account().clone(),
-                });
-            }
+    // The content of a version is kept as a snapshot, or still in the local file if it has no edits.
+    // The vault offers no download of a given version, and the mapped file is never synced for it
+    let full_path = local_dir.join(path);
+    let mut source = Some(base_snapshot_path(&local_dir, vfid, &version)).filter(|s| s.exists());
+    if source.is_none() && mapping.version_when_updated() == &version {
+        let unmodified = match sha1_hash::calc_sha1(&full_path, 4096usize).await {
+            Ok(hash_calc) => &hash_calc.hash == mapping.hash_when_updated(),
+            Err(_) => false,
+        };
+        if unmodified {
+            source = Some(full_path.clone());
         }
-
-        print_json(json_result, args.pretty);
-        return;
     }
+    let Some(source) = source else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.checkout.version_not_available",
+                path = path.display(),
+                version = &version
+            ))
+        );
+        return Err(CmdError::NotFound);
+    };
 
-    if args.raw {
-        // Print your sheets
-        if !args.others && !args.all || !args.others {
-            latest_info
-                .visible_sheets
-                .iter()
-                .for_each(|s| println!("{}", s));
-        }
-        // Print other sheets
-        if args.others || args.all {
-            latest_info
-                .invisible_sheets
-                .iter()
-                .for_each(|s| println!("{}", s.sheet_name));
-        }
-    } else {
-        // Print your sheets
-        if !args.others && !args.all || !args.others {
-            println!("{}", md(t!("jv.success.sheet.list.your_sheet")));
-            let in_use = local_cfg.sheet_in_use();
-            for sheet in latest_info.visible_sheets {
-                let is_ref_sheet = latest_info.reference_sheets.contains(&sheet);
-                let display_name = if is_ref_sheet {
-                    format!(
-                        "{} {}",
-                        sheet,
-                        md(t!("jv.success.sheet.list.reference_sheet_suffix"))
-                            .truecolor(128, 128, 128)
-                    )
-                } else {
-                    sheet.clone()
-                };
+    let target = match output {
+        Some(output) if output.is_dir() => output.join(path.file_name().unwrap_or_default()),
+        Some(output) => output,
+        None => {
+            // Writing in place requires edit rights
+            if latest_file_data.file_holder(vfid) != Some(&account) {
+                eprintln!(
+                    "{}",
+                    md(t!("jv.fail.checkout.not_held", path = path.display()))
+                );
+                return Err(CmdError::Rejected);
+            }
 
-                if let Some(in_use) = in_use
-                    && in_use == &sheet
-                {
-                    println!(
-                        "{}",
-                        md(t!(
-                            "jv.success.sheet.list.your_sheet_item_use",
-                            number = your_sheet_counts + 1,
-                            name = display_name.cyan()
-                        ))
-                    );
-                } else {
+            if full_path.exists() && !args.confirm {
+                let modified = match sha1_hash::calc_sha1(&full_path, 4096usize).await {
+                    Ok(hash_calc) => &hash_calc.hash != mapping.hash_when_updated(),
+                    Err(_) => true,
+                };
+                if modified {
                     println!(
                         "{}",
                         md(t!(
-                            "jv.success.sheet.list.your_sheet_item",
-                            number = your_sheet_counts + 1,
-                            name = display_name
+                            "jv.confirm.checkout",
+                            path = path.display(),
+                            version = &version
                         ))
+                        .yellow()
                     );
+                    confirm_hint_or(t!("common.confirm"), || {
+                        exit(CmdError::Cancelled.exit_code())
+                    })
+                    .await;
                 }
-                your_sheet_counts += 1;
             }
+            full_path.clone()
         }
+    };
 
-        // Print other sheets
-        if args.others || args.all {
-            if args.all {
-                println!();
-            }
-            println!("{}", md(t!("jv.success.sheet.list.other_sheet")));
-            for sheet in latest_info.invisible_sheets {
-                if let Some(holder) = sheet.holder_name {
-                    println!(
-                        "{}",
-                        md(t!(
-                            "jv.success.sheet.list.other_sheet_item",
-                            number = other_sheet_counts + 1,
-                            name = sheet.sheet_name,
-                            holder = holder
-                        ))
-                    );
-                } else {
-                    println!(
-                        "{}",
-                        md(t!(
-                            "jv.success.sheet.list.other_sheet_item_no_holder",
-                            number = other_sheet_counts + 1,
-                            name = sheet.sheet_name
-                        ))
-                    );
-                }
-                other_sheet_counts += 1;
-            }
-        }
-
-        // If not use any sheets, print tips
-        if local_cfg.sheet_in_use().is_none() {
-            println!();
-            if your_sheet_counts > 0 {
-                println!("{}", md(t!("jv.success.sheet.list.tip_has_sheet")));
-            } else {
-                println!("{}", md(t!("jv.success.sheet.list.tip_no_sheet")));
-            }
-        }
+    if let Some(parent) = target.parent() {
+        let _ = fs::create_dir_all(parent).await;
     }
-}
-
-async fn jv_sheet_use(args: SheetUseArgs) {
-    let Some(local_dir) = current_local_path() else {
-        eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+    // The file is already at the version when it is the source
+    let copied = if source == target {
+        Ok(0)
+    } else {
+        fs::copy(&source, &target).await
     };
+    if let Err(e) = copied {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.checkout.write_failed",
+                path = target.display(),
+                error = e
+            ))
+        );
+        return Err(CmdError::Failed);
+    }
 
-    let current_dir = current_dir().unwrap();
+    println!(
+        "{}",
+        md(t!(
+            "jv.success.checkout",
+            path = path.display(),
+            version = &version,
+            target = target.display()
+        ))
+    );
 
-    if local_dir != current_dir {
-        eprintln!("{}", t!("jv.fail.not_root_dir").trim());
+    Ok(())
+}
+
+/// Keep a copy of files whose content is still the version they were synced at,
+/// so that they can be compared or restored later. Only when `base_cache` is enabled
+async fn save_base_snapshots<'a>(
+    local_config: &LocalConfig,
+    files: impl Iterator<Item = &'a PathBuf>,
+) {
+    if !base_cache_enabled() {
         return;
     }
-
-    let Ok(mut local_cfg) = LocalConfig::read().await else {
-        eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
+    let Some(sheet_name) = local_config.sheet_in_use().clone() else {
+        return;
+    };
+    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_config.clone()) else {
+        return;
+    };
+    let account = local_config.current_account();
+    let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
         return;
     };
 
-    match local_cfg.use_sheet(args.sheet_name.clone()).await {
-        Ok(_) => {
-            match LocalConfig::write(&local_cfg).await {
-                Ok(_) => (),
-                Err(e) => {
-                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
-                    return;
-                }
-            };
+    let local_dir = local_workspace.local_path();
+    for file in files {
+        let Ok(mapping) = local_sheet.mapping_data(file) else {
+            continue;
+        };
+        let vfid = mapping.mapping_vfid();
+        let version = mapping.version_when_updated();
+        if base_snapshot_path(local_dir, vfid, version).exists() {
+            continue;
+        }
 
-            // After successfully switching sheets, status should be automatically prompted
-            jv_status(StatusArgs {
-                help: false,
-                json_output: false,
-                pretty: false,
-            })
-            .await;
+        let full_path = local_dir.join(file);
+        match sha1_hash::calc_sha1(&full_path, 4096usize).await {
+            Ok(hash_calc) if &hash_calc.hash == mapping.hash_when_updated() => {}
+            _ => continue,
         }
-        Err(e) => match e.kind() {
-            std::io::ErrorKind::AlreadyExists => {} // Already In Use
-            std::io::ErrorKind::NotFound => {
-                eprintln!(
-                    "{}",
-                    md(t!("jv.fail.use.sheet_not_exists", name = args.sheet_name))
-                );
-                return;
-            }
-            std::io::ErrorKind::DirectoryNotEmpty => {
-                eprintln!(
-                    "{}",
-                    md(t!(
-                        "jv.fail.use.directory_not_empty",
-                        name = args.sheet_name
-                    ))
-                );
-                return;
-            }
-            _ => {
-                handle_err(e.into());
-            }
-        },
+        let _ = save_base_snapshot(local_dir, vfid, version, &full_path).await;
     }
 }
 
-async fn jv_sheet_exit(_args: SheetExitArgs) -> Result<(), ()> {
-    let Some(local_dir) = current_local_path() else {
-        eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return Err(());
-    };
-
-    let current_dir = current_dir().unwrap();
-
-    if local_dir != current_dir {
-        eprintln!("{}", t!("jv.fail.not_root_dir").trim());
-        return Err(());
-    }
+/// Download the latest version of files not held by the current account, as `jv track` syncs them,
+/// replacing their local edits. Returns the synced files, their content is kept as snapshots
+async fn sync_latest_versions(
+    local_config: &LocalConfig,
+    files: HashSet<PathBuf>,
+) -> Result<Vec<PathBuf>, CmdError> {
+    let (pool, ctx, mut output) = build_pool_and_ctx(local_config).await?;
 
-    let Ok(mut local_cfg) = LocalConfig::read().await else {
-        eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        return Err(());
-    };
+    // Nothing is printed, but the messages must still be received
+    let drain = tokio::spawn(async move { while output.recv().await.is_some() {} });
+    let result = proc_track_file_action(
+        &pool,
+        ctx,
+        TrackFileActionArguments {
+            relative_pathes: files,
+            file_update_info: HashMap::new(),
+            print_infos: false,
+            allow_overwrite_modified: true,
+        },
+    )
+    .await;
+    drain.abort();
 
-    match local_cfg.exit_sheet().await {
-        Ok(_) => {
-            match LocalConfig::write(&local_cfg).await {
-                Ok(_) => (),
-                Err(e) => {
-                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
-                    return Err(());
-                }
-            };
-            return Ok(());
+    match result {
+        Ok(TrackFileActionResult::Done { synced, .. }) => {
+            save_base_snapshots(local_config, synced.iter()).await;
+            Ok(synced)
         }
-        Err(e) => {
-            handle_err(e.into());
-            return Err(());
+        Ok(TrackFileActionResult::AuthorizeFailed(e)) => {
+            eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
+            Err(CmdError::AuthorizeFailed)
+        }
+        Ok(TrackFileActionResult::StructureChangesNotSolved) => {
+            eprintln!("{}", md(t!("jv.result.track.structure_changes_not_solved")));
+            Err(CmdError::Rejected)
+        }
+        Ok(_) => {
+            eprintln!("{}", md(t!("jv.fail.sync_latest")));
+            Err(CmdError::Failed)
         }
+        Err(e) => Err(handle_err(e)),
     }
 }
 
-async fn jv_sheet_make(args: SheetMakeArgs) {
-    let sheet_name = snake_case!(args.sheet_name);
-
-    let local_config = match precheck().await {
-        Some(config) => config,
-        None => return,
+async fn jv_info(args: InfoArgs) -> CmdResult {
+    let local_dir = match current_local_path() {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+            return Err(CmdError::NotWorkspace);
+        }
     };
 
-    let (pool, ctx, _output) = match build_pool_and_ctx(&local_config).await {
-        Some(result) => result,
-        None => return,
+    let query_file_paths = if !args.file_patterns.is_empty() || args.from_file.is_some() {
+        let files = collect_files(
+            &args.file_patterns,
+            &args.exclude,
+            args.from_file.as_deref(),
+            args.ignore_case,
+            &local_dir,
+        )
+        .await?;
+        files
+            .iter()
+            .filter_map(|f| PathBuf::from_str(f.0).ok())
+            .collect::<Vec<_>>()
+    } else {
+        println!("{}", md(t!("jv.info")));
+        return Err(CmdError::Usage);
     };
 
-    let Some(local_dir) = current_local_path() else {
-        eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+    // The vault keeps no time for versions, the history can't be filtered by date
+    if args.since.is_some() {
+        eprintln!("{}", md(t!("jv.fail.info.since_unavailable")));
+        return Err(CmdError::Usage);
+    }
+
+    let _ = correct_current_dir();
+
+    let Ok(local_cfg) = LocalConfig::read().await else {
+        eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
+        return Err(CmdError::ConfigNotFound);
     };
 
-    let latest_info = match LatestInfo::read_from(LatestInfo::latest_info_path(
+    let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
         &local_dir,
-        &local_config.current_account(),
+        &local_cfg.current_account(),
     ))
     .await
-    {
-        Ok(info) => info,
-        Err(_) => {
-            eprintln!(
-                "{}",
-                md(t!(
-                    "jv.fail.cfg_not_found.latest_info",
-                    account = &local_config.current_account()
-                ))
-            );
-            return;
-        }
-    };
-
-    if latest_info
-        .invisible_sheets
-        .iter()
-        .any(|sheet| sheet.sheet_name == sheet_name && sheet.holder_name.is_none())
-    {
-        println!(
+    else {
+        eprintln!(
             "{}",
-            md(t!("jv.confirm.sheet.make.restore", sheet_name = sheet_name)).yellow()
+            md(t!(
+                "jv.fail.cfg_not_found.latest_info",
+                account = &local_cfg.current_account()
+            ))
         );
-        if !confirm_hint(t!("common.confirm")).await {
-            return;
-        }
-    }
+        return Err(CmdError::ConfigNotFound);
+    };
 
-    match proc_make_sheet_action(&pool, ctx, sheet_name.clone()).await {
-        Ok(r) => match r {
-            MakeSheetActionResult::Success => {
-                println!(
-                    "{}",
-                    md(t!("jv.result.sheet.make.success", name = sheet_name))
-                )
-            }
-            MakeSheetActionResult::SuccessRestore => {
-                println!(
-                    "{}",
-                    md(t!(
-                        "jv.result.sheet.make.success_restore",
-                        name = sheet_name
-                    ))
-                )
-            }
-            MakeSheetActionResult::AuthorizeFailed(e) => {
-                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)))
-            }
-            MakeSheetActionResult::SheetAlreadyExists => {
-                eprintln!(
-                    "{}",
-                    md(t!(
-                        "jv.result.sheet.make.sheet_already_exists",
-                        name = sheet_name
-                    ))
-                );
-            }
-            MakeSheetActionResult::SheetCreationFailed(e) => {
-                eprintln!(
-                    "{}",
-                    md(t!("jv.result.sheet.make.sheet_creation_failed", err = e))
-                )
-            }
-            MakeSheetActionResult::Unknown => todo!(),
-        },
-        Err(e) => handle_err(e),
-    }
-}
+    let account = local_cfg.current_account();
 
-async fn jv_sheet_drop(args: SheetDropArgs) {
-    let sheet_name = snake_case!(args.sheet_name);
+    let Ok(latest_file_data_path) = LatestFileData::data_path(&account) else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_file_data",
+                account = &account
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
 
-    if !args.confirm {
-        println!(
+    // Get latest file data
+    let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
+        eprintln!(
             "{}",
-            t!("jv.confirm.sheet.drop", sheet_name = sheet_name)
-                .trim()
-                .yellow()
+            md(t!(
+                "jv.fail.cfg_not_found.latest_file_data",
+                account = &account
+            ))
         );
-        confirm_hint_or(t!("common.confirm"), || exit(1)).await;
-    }
+        return Err(CmdError::ConfigNotFound);
+    };
 
-    let local_config = match precheck().await {
-        Some(config) => config,
-        None => return,
+    let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
+        eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
+        return Err(CmdError::NoSheetInUse);
     };
 
-    let (pool, ctx, _output) = match build_pool_and_ctx(&local_config).await {
-        Some(result) => result,
-        None => return,
+    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
+        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
+        return Err(CmdError::NotWorkspace);
     };
 
-    match proc_drop_sheet_action(&pool, ctx, sheet_name.clone()).await {
-        Ok(r) => match r {
-            DropSheetActionResult::Success => {
-                println!(
-                    "{}",
-                    md(t!("jv.result.sheet.drop.success", name = sheet_name))
-                )
-            }
-            DropSheetActionResult::SheetInUse => {
-                eprintln!(
-                    "{}",
-                    md(t!("jv.result.sheet.drop.sheet_in_use", name = sheet_name))
-                )
-            }
-            DropSheetActionResult::AuthorizeFailed(e) => {
-                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)))
-            }
-            DropSheetActionResult::SheetNotExists => {
-                eprintln!(
-                    "{}",
-                    md(t!(
-                        "jv.result.sheet.drop.sheet_not_exists",
-                        name = sheet_name
-                    ))
-                )
-            }
-            DropSheetActionResult::SheetDropFailed(e) => {
-                eprintln!(
-                    "{}",
-                    md(t!("jv.result.sheet.drop.sheet_drop_failed", err = e))
-                )
-            }
-            DropSheetActionResult::NoHolder => {
-                eprintln!(
-                    "{}",
-                    md(t!("jv.result.sheet.drop.no_holder", name = sheet_name))
-                )
-            }
-            DropSheetActionResult::NotOwner => {
-                eprintln!(
-                    "{}",
-                    md(t!("jv.result.sheet.drop.not_owner", name = sheet_name))
-                )
-            }
-            _ => {}
-        },
-        Err(e) => handle_err(e),
-    }
-}
-
-async fn jv_sheet_align(args: SheetAlignArgs) {
-    let Some(local_dir) = current_local_path() else {
-        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
-    };
-
-    let local_cfg = match precheck().await {
-        Some(config) => config,
-        None => {
-            return;
-        }
-    };
-
-    let account = local_cfg.current_account();
-
-    let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
-        eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
-        return;
-    };
-
-    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
-        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
-    };
-
-    let Ok(mut local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
+    let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
         eprintln!(
             "{}",
             md(t!(
@@ -3227,708 +3757,2742 @@ async fn jv_sheet_align(args: SheetAlignArgs) {
                 sheet = &sheet_name
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
-    let Ok(analyzed) = AnalyzeResult::analyze_local_status(&local_workspace).await else {
-        eprintln!("{}", md(t!("jv.fail.status.analyze")).trim());
-        return;
-    };
+    // Files to query
+    let query_file_paths: Vec<PathBuf> = query_file_paths
+        .into_iter()
+        .filter(|p| local_sheet.mapping_data(p).is_ok())
+        .collect();
+    if query_file_paths.len() < 1 {
+        return Err(CmdError::NotFound);
+    }
 
-    let align_tasks = AlignTasks::from_analyze_result(analyzed);
+    // JSON output handling
+    if args.json_output {
+        let mut json_results = Vec::new();
+        for query_file_path in query_file_paths {
+            let Ok(mapping) = local_sheet.mapping_data(&query_file_path) else {
+                continue;
+            };
+            let vfid = mapping.mapping_vfid();
 
-    // No task input, list all tasks needs align
-    let Some(task) = args.task else {
-        // Raw output
-        if args.raw {
-            if args.list_all {
-                align_tasks.created.iter().for_each(|i| println!("{}", i.0));
-                align_tasks.moved.iter().for_each(|i| println!("{}", i.0));
-                align_tasks.lost.iter().for_each(|i| println!("{}", i.0));
-                align_tasks.erased.iter().for_each(|i| println!("{}", i.0));
-                return;
-            }
-            if args.list_created {
-                align_tasks.created.iter().for_each(|i| println!("{}", i.0));
-                return;
-            }
-            if args.list_unsolved {
-                align_tasks.moved.iter().for_each(|i| println!("{}", i.0));
-                align_tasks.lost.iter().for_each(|i| println!("{}", i.0));
-                align_tasks.erased.iter().for_each(|i| println!("{}", i.0));
-                return;
-            }
-            return;
-        }
+            let query_file_path_string =
+                format_path_str(query_file_path.display().to_string()).unwrap_or_default();
 
-        // Json Output
-        if args.json_output {
-            let mut json_result = AlignJsonResult::default();
+            let mut json_result = InfoJsonResult::default();
+            json_result.mapping = query_file_path_string.clone();
 
-            for (name, local_path) in &align_tasks.created {
-                json_result.align_tasks.insert(
-                    name.clone(),
-                    AlignTaskMapping {
-                        local_mapping: local_path.clone(),
-                        remote_mapping: PathBuf::new(),
-                    },
-                );
-            }
-            for (name, (remote_path, local_path)) in &align_tasks.moved {
-                json_result.align_tasks.insert(
-                    name.clone(),
-                    AlignTaskMapping {
-                        local_mapping: local_path.clone(),
-                        remote_mapping: remote_path.clone(),
-                    },
-                );
-            }
-            for (name, local_path) in &align_tasks.lost {
-                json_result.align_tasks.insert(
-                    name.clone(),
-                    AlignTaskMapping {
-                        local_mapping: local_path.clone(),
-                        remote_mapping: PathBuf::new(),
-                    },
-                );
+            // Get reference sheet path
+            json_result.in_ref = if let Some(path) = latest_info.ref_sheet_vfs_mapping.get(vfid) {
+                path.display().to_string()
+            } else {
+                vfid.clone()
+            };
+
+            json_result.vfid = vfid.clone();
+
+            // Get file version in reference sheet
+            let version_in_ref = if let Some(mapping) = latest_info
+                .ref_sheet_content
+                .mapping()
+                .get(&query_file_path)
+            {
+                mapping.version.clone()
+            } else {
+                "".to_string()
+            };
+
+            // Get current file version
+            let version_current = latest_file_data
+                .file_version(vfid)
+                .cloned()
+                .unwrap_or_else(|| "".to_string());
+
+            // Check if file is being edited based on latest version (regardless of hold status)
+            let modified_correctly = if let Ok(mapping) = local_sheet.mapping_data(&query_file_path)
+            {
+                // If base editing version is correct
+                if mapping.version_when_updated() == &version_current {
+                    mapping.last_modifiy_check_result() // Return detection result
+                } else {
+                    false
+                }
+            } else {
+                false
+            };
+
+            // Build history list
+            if let Some(histories) = latest_file_data.file_histories(vfid) {
+                let histories: Vec<_> = histories
+                    .iter()
+                    .filter(|(_, description)| history_visible(&description.creator, &args))
+                    .collect();
+                for (version, description) in limit_histories(histories, args.limit) {
+                    json_result.histories.push(InfoHistory {
+                        version: version.clone(),
+                        version_creator: description.creator.clone(),
+                        version_description: description.description.clone(),
+                        is_current_version: version == &version_current,
+                        is_ref_version: version == &version_in_ref,
+                    });
+                }
             }
-            for (name, local_path) in &align_tasks.erased {
-                json_result.align_tasks.insert(
-                    name.clone(),
-                    AlignTaskMapping {
-                        local_mapping: local_path.clone(),
-                        remote_mapping: PathBuf::new(),
+
+            // Add current modification if exists
+            if modified_correctly && history_visible(&account, &args) {
+                json_result.histories.insert(
+                    0,
+                    InfoHistory {
+                        version: "CURRENT".to_string(),
+                        version_creator: local_workspace
+                            .config()
+                            .lock()
+                            .await
+                            .current_account()
+                            .clone(),
+                        version_description: t!("jv.success.info.oneline.description_current")
+                            .to_string(),
+                        is_current_version: true,
+                        is_ref_version: false,
                     },
                 );
             }
 
-            print_json(json_result, args.pretty);
-            return;
+            json_results.push(json_result);
         }
 
-        let mut table = SimpleTable::new(vec![
-            t!("jv.success.sheet.align.task_name").to_string(),
-            t!("jv.success.sheet.align.local_path").to_string(),
-            if !align_tasks.moved.is_empty() {
-                t!("jv.success.sheet.align.remote_path").to_string()
-            } else {
-                "".to_string()
-            },
-        ]);
+        print_json(json_results, args.pretty);
+        return Ok(());
+    }
 
-        let mut need_align = 0;
+    for (i, query_file_path) in query_file_paths.into_iter().enumerate() {
+        let Ok(mapping) = local_sheet.mapping_data(&query_file_path) else {
+            continue;
+        };
+        let vfid = mapping.mapping_vfid();
 
-        if !align_tasks.created.is_empty() {
-            align_tasks.created.iter().for_each(|(n, p)| {
-                table.push_item(vec![
-                    format!("+ {}", n).green().to_string(),
-                    p.display().to_string().green().to_string(),
-                    "".to_string(),
-                ]);
-            });
-        }
+        let query_file_path_string =
+            format_path_str(query_file_path.display().to_string()).unwrap_or_default();
 
-        if !align_tasks.lost.is_empty() {
-            align_tasks.lost.iter().for_each(|(n, p)| {
-                table.push_item(vec![
-                    format!("- {}", n).red().to_string(),
-                    p.display().to_string().red().to_string(),
-                    "".to_string(),
-                ]);
-            });
-            need_align += 1;
+        // Separate the blocks of multiple files
+        if i > 0 {
+            println!();
         }
 
-        if !align_tasks.erased.is_empty() {
-            align_tasks.erased.iter().for_each(|(n, p)| {
-                table.push_item(vec![
-                    format!("& {}", n).magenta().to_string(),
-                    p.display().to_string().magenta().to_string(),
-                    "".to_string(),
-                ]);
-            });
-            need_align += 1;
+        // Render initial location
+        {
+            println!("{}", query_file_path_string);
         }
 
-        if !align_tasks.moved.is_empty() {
-            align_tasks.moved.iter().for_each(|(n, (rp, lp))| {
-                table.push_item(vec![
-                    format!("> {}", n).yellow().to_string(),
-                    lp.display().to_string().yellow().to_string(),
-                    rp.display().to_string(),
-                ]);
-            });
-            need_align += 1;
-        }
+        // Render reference sheet location, use ID if not found
+        {
+            let path_in_ref = if let Some(path) = latest_info.ref_sheet_vfs_mapping.get(vfid) {
+                path.display().to_string()
+            } else {
+                vfid.clone()
+            };
+
+            // Offset string
+            let offset_string = " ".repeat(display_width(
+                if let Some(last_slash) = query_file_path_string.rfind('/') {
+                    &query_file_path_string[..last_slash]
+                } else {
+                    ""
+                },
+            ));
 
-        if need_align > 0 {
             println!(
-                "{}",
-                md(t!("jv.success.sheet.align.list", tasks = table.to_string()))
+                "{}{}{}",
+                offset_string,
+                "\\_ ".truecolor(128, 128, 128),
+                path_in_ref.cyan()
             );
+        }
 
-            // Suggestion1: Confirm Erased
-            if align_tasks.erased.len() > 0 {
-                println!(
-                    "\n{}",
-                    md(t!(
-                        "jv.success.sheet.align.suggestion_1",
-                        example_erased = align_tasks.erased[0].0
-                    ))
-                )
-            } else
-            // Suggestion2: Confirm Lost
-            if align_tasks.lost.len() > 0 {
-                println!(
-                    "\n{}",
-                    md(t!(
+        // Render complete file history
+        {
+            if let Some(histories) = latest_file_data.file_histories(vfid) {
+                let histories: Vec<_> = histories
+                    .iter()
+                    .filter(|(_, description)| history_visible(&description.creator, &args))
+                    .collect();
+                let histories = limit_histories(histories, args.limit);
+
+                // Get file version in reference sheet
+                let version_in_ref = if let Some(mapping) = latest_info
+                    .ref_sheet_content
+                    .mapping()
+                    .get(&query_file_path)
+                {
+                    mapping.version.clone()
+                } else {
+                    "".to_string()
+                };
+
+                // Get current file version
+                let version_current = latest_file_data
+                    .file_version(vfid)
+                    .cloned()
+                    .unwrap_or_else(|| "".to_string());
+
+                // Check if file is being edited based on latest version (regardless of hold status)
+                let modified_correctly =
+                    if let Ok(mapping) = local_sheet.mapping_data(&query_file_path) {
+                        // If base editing version is correct
+                        if mapping.version_when_updated() == &version_current {
+                            mapping.last_modifiy_check_result() // Return detection result
+                        } else {
+                            false
+                        }
+                    } else {
+                        false
+                    };
+
+                // Text
+                let (prefix_str, version_str, creator_str, description_str) = (
+                    t!("jv.success.info.oneline.table_headers.prefix"),
+                    t!("jv.success.info.oneline.table_headers.version"),
+                    t!("jv.success.info.oneline.table_headers.creator"),
+                    t!("jv.success.info.oneline.table_headers.description"),
+                );
+
+                // Single-line output
+                if !args.full {
+                    // Create table
+                    let mut table = SimpleTable::new(vec![
+                        prefix_str,
+                        version_str,
+                        creator_str,
+                        description_str,
+                    ]);
+
+                    // Append data
+                    for (version, description) in histories {
+                        // If it's reference version, render "@"
+                        // Current version, render "\_"
+                        // Other versions, render "|"
+                        let prefix = if version == &version_in_ref {
+                            "@".cyan().to_string()
+                        } else if version == &version_current {
+                            "|->".yellow().to_string()
+                        } else {
+                            "|".truecolor(128, 128, 128).to_string()
+                        };
+
+                        table.insert_item(
+                            0,
+                            vec![
+                                prefix,
+                                version.to_string(),
+                                format!("@{}: ", &description.creator.cyan()),
+                                truncate_first_line(description.description.to_string()),
+                            ],
+                        );
+                    }
+
+                    // If file has new version, append
+                    if modified_correctly && history_visible(&account, &args) {
+                        table.insert_item(
+                            0,
+                            vec![
+                                "+".green().to_string(),
+                                "CURRENT".green().to_string(),
+                                format!(
+                                    "@{}: ",
+                                    local_workspace
+                                        .config()
+                                        .lock()
+                                        .await
+                                        .current_account()
+                                        .cyan()
+                                ),
+                                format!(
+                                    "{}",
+                                    t!("jv.success.info.oneline.description_current").green()
+                                ),
+                            ],
+                        );
+                    }
+
+                    // Render table
+                    let table_str = table.to_string();
+                    if table_str.lines().count() > 1 {
+                        println!();
+                    }
+                    for line in table_str.lines().skip(1) {
+                        println!("{}", line);
+                    }
+                } else {
+                    // Multi-line output
+                    if histories.len() > 0 {
+                        println!();
+                    }
+                    for (version, description) in histories.iter() {
+                        println!("{}: {}", version_str, version);
+                        println!("{}: {}", creator_str, description.creator.cyan());
+                        println!("{}", description.description);
+                        if version != &histories.last().unwrap().0 {
+                            println!("{}", "-".repeat(45));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn jv_log(args: LogArgs) -> CmdResult {
+    let local_dir = match current_local_path() {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+            return Err(CmdError::NotWorkspace);
+        }
+    };
+
+    let Ok(local_cfg) = LocalConfig::read_from(local_dir.join(CLIENT_FILE_WORKSPACE)).await else {
+        eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let account = local_cfg.current_account();
+
+    let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
+        eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
+        return Err(CmdError::NoSheetInUse);
+    };
+
+    // Files no longer present locally still have a history, so the pattern matches the sheet.
+    // Glob before the current directory changes
+    let filter_paths: Option<HashSet<PathBuf>> = match args.file_pattern.clone() {
+        Some(pattern) => Some(
+            glob_sheet(pattern, &local_dir, &sheet_name)
+                .await
+                .into_keys()
+                .map(PathBuf::from)
+                .collect(),
+        ),
+        None => None,
+    };
+
+    let _ = correct_current_dir();
+
+    let Ok(latest_file_data_path) = LatestFileData::data_path(&account) else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_file_data",
+                account = &account
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_file_data",
+                account = &account
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let Ok(cached_sheet) = CachedSheet::cached_sheet_data(&sheet_name).await else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.cached_sheet",
+                sheet = &sheet_name
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let mappings: Vec<(&PathBuf, &SheetMappingMetadata)> = cached_sheet
+        .mapping()
+        .iter()
+        .filter(|(path, _)| {
+            filter_paths
+                .as_ref()
+                .is_none_or(|filter| filter.contains(*path))
+        })
+        .collect();
+
+    let mut entries = Vec::new();
+    for (path, metadata) in mappings {
+        let Some(histories) = latest_file_data.file_histories(&metadata.id) else {
+            continue;
+        };
+        let version_current = latest_file_data.file_version(&metadata.id);
+        let mapping = format_path_str(path.display().to_string()).unwrap_or_default();
+
+        for (version, description) in histories.iter().rev() {
+            if let Some(member) = &args.member
+                && &description.creator != member
+            {
+                continue;
+            }
+            entries.push(LogEntry {
+                mapping: mapping.clone(),
+                vfid: metadata.id.clone(),
+                version: version.clone(),
+                creator: description.creator.clone(),
+                description: description.description.clone(),
+                is_current_version: version_current == Some(version),
+            });
+        }
+    }
+
+    // The vault keeps no time for versions, so files are listed by path, each from its newest version
+    entries.sort_by(|a, b| a.mapping.cmp(&b.mapping));
+    if let Some(limit) = args.limit {
+        entries.truncate(limit);
+    }
+
+    if args.json_output {
+        print_json(LogJsonResult { entries }, args.pretty);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("{}", md(t!("jv.success.log.empty")));
+        return Ok(());
+    }
+
+    let use_pager = !args.direct && std::io::stdout().is_terminal();
+    // The pager displays plain text, and so do pipes
+    let use_color = !use_pager && std::io::stdout().is_terminal();
+
+    let mut table = SimpleTable::new(vec![
+        t!("jv.success.log.headers.version"),
+        t!("jv.success.log.headers.creator"),
+        t!("jv.success.log.headers.path"),
+        t!("jv.success.log.headers.description"),
+    ]);
+    for entry in entries {
+        let (version, creator) = if !use_color {
+            (entry.version, format!("@{}", entry.creator))
+        } else if entry.is_current_version {
+            (
+                entry.version.yellow().to_string(),
+                format!("@{}", entry.creator.cyan()),
+            )
+        } else {
+            (entry.version, format!("@{}", entry.creator.cyan()))
+        };
+        table.push_item(vec![
+            version,
+            creator,
+            entry.mapping,
+            truncate_first_line(entry.description),
+        ]);
+    }
+
+    if !use_pager {
+        println!("{}", table);
+        return Ok(());
+    }
+
+    let file = local_dir.join(CLIENT_PATH_WORKSPACE_ROOT).join(".LOG.md");
+    if show_in_pager(table.to_string(), file).await.is_err() {
+        println!("{}", table);
+    }
+
+    Ok(())
+}
+
+/// Check whether a history entry passes the `--creator` filter of `jv info`
+fn history_visible(creator: &MemberId, args: &InfoArgs) -> bool {
+    args.creator
+        .as_ref()
+        .is_none_or(|filter_creator| creator == filter_creator)
+}
+
+/// Keep only the latest `limit` entries of a history
+fn limit_histories<T>(mut histories: Vec<T>, limit: Option<usize>) -> Vec<T> {
+    if let Some(limit) = limit {
+        let skip = histories.len().saturating_sub(limit);
+        histories.drain(..skip);
+    }
+    histories
+}
+
+async fn jv_sheet_list(args: SheetListArgs) -> CmdResult {
+    let _ = correct_current_dir();
+
+    let Some(local_dir) = current_local_path() else {
+        if !args.raw {
+            eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+        }
+        return Err(CmdError::NotWorkspace);
+    };
+
+    let Ok(local_cfg) = LocalConfig::read().await else {
+        if !args.raw {
+            eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
+        }
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
+        &local_dir,
+        &local_cfg.current_account(),
+    ))
+    .await
+    else {
+        if !args.raw {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.cfg_not_found.latest_info",
+                    account = &local_cfg.current_account()
+                ))
+            );
+        }
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let mut your_sheet_counts = 0;
+    let mut other_sheet_counts = 0;
+
+    // JSON output handling
+    if args.json_output {
+        let mut json_result = SheetListJsonResult::default();
+
+        // Populate reference sheets
+        for sheet_name in &latest_info.reference_sheets {
+            json_result.reference_sheets.push(SheetItem {
+                name: sheet_name.clone(),
+                holder: local_cfg.current_account().clone(),
+            });
+        }
+
+        // Populate other sheets
+        for sheet in &latest_info.invisible_sheets {
+            json_result.other_sheets.push(SheetItem {
+                name: sheet.sheet_name.clone(),
+                holder: sheet.holder_name.clone().unwrap_or_default(),
+            });
+        }
+
+        // Populate my sheets (excluding reference sheets)
+        for sheet_name in &latest_info.visible_sheets {
+            if !latest_info.reference_sheets.contains(sheet_name) {
+                json_result.my_sheets.push(SheetItem {
+                    name: sheet_name.clone(),
+                    holder: local_cfg.current_account().clone(),
+                });
+            }
+        }
+
+        print_json(json_result, args.pretty);
+        return Ok(());
+    }
+
+    if args.raw {
+        // Print your sheets
+        if !args.others && !args.all || !args.others {
+            latest_info
+                .visible_sheets
+                .iter()
+                .for_each(|s| println!("{}", s));
+        }
+        // Print other sheets
+        if args.others || args.all {
+            latest_info
+                .invisible_sheets
+                .iter()
+                .for_each(|s| println!("{}", s.sheet_name));
+        }
+    } else {
+        // Print your sheets
+        if !args.others && !args.all || !args.others {
+            println!("{}", md(t!("jv.success.sheet.list.your_sheet")));
+            let in_use = local_cfg.sheet_in_use();
+            for sheet in latest_info.visible_sheets {
+                let is_ref_sheet = latest_info.reference_sheets.contains(&sheet);
+                let display_name = if is_ref_sheet {
+                    format!(
+                        "{} {}",
+                        sheet,
+                        md(t!("jv.success.sheet.list.reference_sheet_suffix"))
+                            .truecolor(128, 128, 128)
+                    )
+                } else {
+                    sheet.clone()
+                };
+
+                if let Some(in_use) = in_use
+                    && in_use == &sheet
+                {
+                    println!(
+                        "{}",
+                        md(t!(
+                            "jv.success.sheet.list.your_sheet_item_use",
+                            number = your_sheet_counts + 1,
+                            name = display_name.cyan()
+                        ))
+                    );
+                } else {
+                    println!(
+                        "{}",
+                        md(t!(
+                            "jv.success.sheet.list.your_sheet_item",
+                            number = your_sheet_counts + 1,
+                            name = display_name
+                        ))
+                    );
+                }
+                your_sheet_counts += 1;
+            }
+        }
+
+        // Print other sheets
+        if args.others || args.all {
+            if args.all {
+                println!();
+            }
+            println!("{}", md(t!("jv.success.sheet.list.other_sheet")));
+            for sheet in latest_info.invisible_sheets {
+                if let Some(holder) = sheet.holder_name {
+                    println!(
+                        "{}",
+                        md(t!(
+                            "jv.success.sheet.list.other_sheet_item",
+                            number = other_sheet_counts + 1,
+                            name = sheet.sheet_name,
+                            holder = holder
+                        ))
+                    );
+                } else {
+                    println!(
+                        "{}",
+                        md(t!(
+                            "jv.success.sheet.list.other_sheet_item_no_holder",
+                            number = other_sheet_counts + 1,
+                            name = sheet.sheet_name
+                        ))
+                    );
+                }
+                other_sheet_counts += 1;
+            }
+        }
+
+        // If not use any sheets, print tips
+        if local_cfg.sheet_in_use().is_none() {
+            println!();
+            if your_sheet_counts > 0 {
+                println!("{}", md(t!("jv.success.sheet.list.tip_has_sheet")));
+            } else {
+                println!("{}", md(t!("jv.success.sheet.list.tip_no_sheet")));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn jv_sheet_use(args: SheetUseArgs) -> CmdResult {
+    let Some(local_dir) = current_local_path() else {
+        eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+        return Err(CmdError::NotWorkspace);
+    };
+
+    let current_dir = current_dir().unwrap();
+
+    if local_dir != current_dir {
+        eprintln!("{}", t!("jv.fail.not_root_dir").trim());
+        return Err(CmdError::Usage);
+    }
+
+    let Ok(mut local_cfg) = LocalConfig::read().await else {
+        eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    match local_cfg.use_sheet(args.sheet_name.clone()).await {
+        Ok(_) => {
+            match LocalConfig::write(&local_cfg).await {
+                Ok(_) => (),
+                Err(e) => {
+                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
+                    return Err(CmdError::Failed);
+                }
+            };
+
+            // After successfully switching sheets, status should be automatically prompted
+            jv_status(StatusArgs {
+                help: false,
+                json_output: false,
+                pretty: false,
+            })
+            .await
+        }
+        Err(e) => match e.kind() {
+            std::io::ErrorKind::AlreadyExists => Ok(()), // Already In Use
+            std::io::ErrorKind::NotFound => {
+                eprintln!(
+                    "{}",
+                    md(t!("jv.fail.use.sheet_not_exists", name = args.sheet_name))
+                );
+                let sheets = known_sheet_names(&local_cfg).await;
+                print_did_you_mean(&args.sheet_name, sheets.iter().map(|s| s.as_str()));
+                Err(CmdError::NotFound)
+            }
+            std::io::ErrorKind::DirectoryNotEmpty => {
+                eprintln!(
+                    "{}",
+                    md(t!(
+                        "jv.fail.use.directory_not_empty",
+                        name = args.sheet_name
+                    ))
+                );
+                Err(CmdError::Rejected)
+            }
+            _ => Err(handle_err(e.into())),
+        },
+    }
+}
+
+async fn jv_sheet_exit(_args: SheetExitArgs) -> CmdResult {
+    let Some(local_dir) = current_local_path() else {
+        eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+        return Err(CmdError::NotWorkspace);
+    };
+
+    let current_dir = current_dir().unwrap();
+
+    if local_dir != current_dir {
+        eprintln!("{}", t!("jv.fail.not_root_dir").trim());
+        return Err(CmdError::Usage);
+    }
+
+    let Ok(mut local_cfg) = LocalConfig::read().await else {
+        eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    match local_cfg.exit_sheet().await {
+        Ok(_) => {
+            match LocalConfig::write(&local_cfg).await {
+                Ok(_) => (),
+                Err(e) => {
+                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
+                    return Err(CmdError::Failed);
+                }
+            };
+            Ok(())
+        }
+        Err(e) => Err(handle_err(e.into())),
+    }
+}
+
+async fn jv_sheet_make(args: SheetMakeArgs) -> CmdResult {
+    let sheet_name = snake_case!(args.sheet_name);
+
+    let local_config = precheck().await?;
+
+    let (pool, ctx, _output) = build_pool_and_ctx(&local_config).await?;
+
+    let Some(local_dir) = current_local_path() else {
+        eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+        return Err(CmdError::NotWorkspace);
+    };
+
+    let latest_info = match LatestInfo::read_from(LatestInfo::latest_info_path(
+        &local_dir,
+        &local_config.current_account(),
+    ))
+    .await
+    {
+        Ok(info) => info,
+        Err(_) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.cfg_not_found.latest_info",
+                    account = &local_config.current_account()
+                ))
+            );
+            return Err(CmdError::ConfigNotFound);
+        }
+    };
+
+    if latest_info
+        .invisible_sheets
+        .iter()
+        .any(|sheet| sheet.sheet_name == sheet_name && sheet.holder_name.is_none())
+    {
+        println!(
+            "{}",
+            md(t!("jv.confirm.sheet.make.restore", sheet_name = sheet_name)).yellow()
+        );
+        if !confirm_hint(t!("common.confirm")).await {
+            return Err(CmdError::Cancelled);
+        }
+    }
+
+    match proc_make_sheet_action(&pool, ctx, sheet_name.clone()).await {
+        Ok(r) => match r {
+            MakeSheetActionResult::Success => {
+                println!(
+                    "{}",
+                    md(t!("jv.result.sheet.make.success", name = sheet_name))
+                );
+                Ok(())
+            }
+            MakeSheetActionResult::SuccessRestore => {
+                println!(
+                    "{}",
+                    md(t!(
+                        "jv.result.sheet.make.success_restore",
+                        name = sheet_name
+                    ))
+                );
+                Ok(())
+            }
+            MakeSheetActionResult::AuthorizeFailed(e) => {
+                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
+                Err(CmdError::AuthorizeFailed)
+            }
+            MakeSheetActionResult::SheetAlreadyExists => {
+                eprintln!(
+                    "{}",
+                    md(t!(
+                        "jv.result.sheet.make.sheet_already_exists",
+                        name = sheet_name
+                    ))
+                );
+                Err(CmdError::Rejected)
+            }
+            MakeSheetActionResult::SheetCreationFailed(e) => {
+                eprintln!(
+                    "{}",
+                    md(t!("jv.result.sheet.make.sheet_creation_failed", err = e))
+                );
+                Err(CmdError::Failed)
+            }
+            MakeSheetActionResult::Unknown => todo!(),
+        },
+        Err(e) => Err(handle_err(e)),
+    }
+}
+
+async fn jv_sheet_drop(args: SheetDropArgs) -> CmdResult {
+    let sheet_name = snake_case!(args.sheet_name);
+
+    if !args.confirm {
+        println!(
+            "{}",
+            t!("jv.confirm.sheet.drop", sheet_name = sheet_name)
+                .trim()
+                .yellow()
+        );
+        confirm_hint_or(t!("common.confirm"), || {
+            exit(CmdError::Cancelled.exit_code())
+        })
+        .await;
+    }
+
+    let local_config = precheck().await?;
+
+    let (pool, ctx, _output) = build_pool_and_ctx(&local_config).await?;
+
+    match proc_drop_sheet_action(&pool, ctx, sheet_name.clone()).await {
+        Ok(r) => match r {
+            DropSheetActionResult::Success => {
+                println!(
+                    "{}",
+                    md(t!("jv.result.sheet.drop.success", name = sheet_name))
+                );
+                Ok(())
+            }
+            DropSheetActionResult::SheetInUse => {
+                eprintln!(
+                    "{}",
+                    md(t!("jv.result.sheet.drop.sheet_in_use", name = sheet_name))
+                );
+                Err(CmdError::Rejected)
+            }
+            DropSheetActionResult::AuthorizeFailed(e) => {
+                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
+                Err(CmdError::AuthorizeFailed)
+            }
+            DropSheetActionResult::SheetNotExists => {
+                eprintln!(
+                    "{}",
+                    md(t!(
+                        "jv.result.sheet.drop.sheet_not_exists",
+                        name = sheet_name
+                    ))
+                );
+                let sheets = known_sheet_names(&local_config).await;
+                print_did_you_mean(&sheet_name, sheets.iter().map(|s| s.as_str()));
+                Err(CmdError::NotFound)
+            }
+            DropSheetActionResult::SheetDropFailed(e) => {
+                eprintln!(
+                    "{}",
+                    md(t!("jv.result.sheet.drop.sheet_drop_failed", err = e))
+                );
+                Err(CmdError::Failed)
+            }
+            DropSheetActionResult::NoHolder => {
+                eprintln!(
+                    "{}",
+                    md(t!("jv.result.sheet.drop.no_holder", name = sheet_name))
+                );
+                Err(CmdError::Rejected)
+            }
+            DropSheetActionResult::NotOwner => {
+                eprintln!(
+                    "{}",
+                    md(t!("jv.result.sheet.drop.not_owner", name = sheet_name))
+                );
+                Err(CmdError::Rejected)
+            }
+            _ => Err(CmdError::Failed),
+        },
+        Err(e) => Err(handle_err(e)),
+    }
+}
+
+async fn jv_sheet_align(args: SheetAlignArgs) -> CmdResult {
+    let Some(local_dir) = current_local_path() else {
+        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
+        return Err(CmdError::NotWorkspace);
+    };
+
+    let local_cfg = precheck().await?;
+
+    let account = local_cfg.current_account();
+
+    let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
+        eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
+        return Err(CmdError::NoSheetInUse);
+    };
+
+    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
+        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
+        return Err(CmdError::NotWorkspace);
+    };
+
+    let Ok(mut local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.local_sheet",
+                account = &account,
+                sheet = &sheet_name
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let Ok(mut analyzed) = AnalyzeResult::analyze_local_status(&local_workspace).await else {
+        eprintln!("{}", md(t!("jv.fail.status.analyze")).trim());
+        return Err(CmdError::Failed);
+    };
+
+    // Files matching `.jvignore` are not shown
+    filter_ignored(&local_dir, &mut analyzed, |path| {
+        local_sheet
+            .mapping_data(path)
+            .ok()
+            .map(|mapping| mapping.hash_when_updated().clone())
+    })
+    .await;
+
+    let align_tasks = AlignTasks::from_analyze_result(analyzed);
+
+    // Editor mode: choose the action of every task in the editor, then apply them in one pass
+    // Auto mode: match lost items to created items, then apply them after a confirmation
+    if args.work || args.auto {
+        let matches = if args.auto {
+            let lost = align_tasks
+                .lost
+                .iter()
+                .filter_map(|(_, path)| {
+                    let mapping = local_sheet.mapping_data(path).ok()?;
+                    let snapshot = base_snapshot_path(
+                        &local_dir,
+                        mapping.mapping_vfid(),
+                        mapping.version_when_updated(),
+                    );
+                    let size = std::fs::metadata(snapshot).ok().map(|m| m.len());
+                    Some((path.clone(), mapping.hash_when_updated().clone(), size))
+                })
+                .collect();
+            auto_align_matches(&local_dir, &align_tasks.created, lost).await
+        } else {
+            Vec::new()
+        };
+
+        let decisions = if args.work {
+            // Matches are pre-filled in the editor, which replaces the confirmation
+            let proposals = matches
+                .iter()
+                .filter_map(|m| {
+                    let (name, _) = align_tasks.created.iter().find(|(_, p)| p == &m.created)?;
+                    Some((m.lost.clone(), name.clone()))
+                })
+                .collect();
+            start_align_editor(&local_dir, &align_tasks, &proposals).await?
+        } else {
+            if matches.is_empty() {
+                println!("{}", md(t!("jv.success.sheet.align.auto_nothing")));
+                return Ok(());
+            }
+
+            if !args.dry_run && !args.confirm {
+                let mut table = SimpleTable::new(vec![
+                    t!("jv.success.sheet.align.lost_path").to_string(),
+                    t!("jv.success.sheet.align.created_path").to_string(),
+                    t!("jv.success.sheet.align.match_reason").to_string(),
+                ]);
+                for m in &matches {
+                    table.push_item(vec![
+                        m.lost.display().to_string().red().to_string(),
+                        m.created.display().to_string().green().to_string(),
+                        match m.similarity {
+                            None => t!("jv.success.sheet.align.identical").to_string(),
+                            Some(similarity) => format!("{:.0}%", similarity * 100.0),
+                        },
+                    ]);
+                }
+                println!(
+                    "{}",
+                    md(t!(
+                        "jv.confirm.align_auto",
+                        count = matches.len(),
+                        matches = table.to_string()
+                    ))
+                );
+                confirm_hint_or(t!("common.confirm"), || {
+                    exit(CmdError::Cancelled.exit_code())
+                })
+                .await;
+            }
+
+            matches
+                .into_iter()
+                .map(|m| AlignDecision::MatchLost {
+                    lost: m.lost,
+                    created: m.created,
+                })
+                .collect()
+        };
+        if decisions.is_empty() {
+            println!("{}", md(t!("jv.success.sheet.align.work_nothing")));
+            return Ok(());
+        }
+
+        if args.dry_run {
+            let mut operations = Vec::new();
+            for decision in decisions {
+                match decision {
+                    AlignDecision::MovedToLocal { remote, local } => {
+                        operations.push(DryRunOperation::MoveMapping {
+                            from: remote,
+                            to: local,
+                        })
+                    }
+                    AlignDecision::MovedToRemote { remote, local } => {
+                        operations.push(DryRunOperation::MoveLocal {
+                            from: local,
+                            to: remote,
+                        })
+                    }
+                    AlignDecision::BreakMoved { remote } => {
+                        operations.push(DryRunOperation::BreakMove { mapping: remote })
+                    }
+                    AlignDecision::ConfirmLost { mapping } => {
+                        operations.push(DryRunOperation::RemoveMapping { mapping })
+                    }
+                    AlignDecision::MatchLost { lost, created } => {
+                        operations.push(DryRunOperation::MatchLost { lost, created })
+                    }
+                    AlignDecision::ConfirmErased { mapping } => {
+                        if local_dir.join(&mapping).exists() {
+                            operations.push(DryRunOperation::MoveLocal {
+                                from: mapping.clone(),
+                                to: erased_backup_path(&mapping),
+                            });
+                        }
+                        operations.push(DryRunOperation::RemoveMapping { mapping });
+                    }
+                }
+            }
+            print_dry_run(operations, args.json_output, args.pretty);
+            return Ok(());
+        }
+
+        let mut has_failed = false;
+        let mut mapping_operations: HashMap<FromRelativePathBuf, OperationArgument> =
+            HashMap::new();
+        let mut local_moves = Vec::new();
+
+        // Local sheet changes first, then local moves, and the upstream mappings at last
+        for decision in decisions {
+            match decision {
+                AlignDecision::MovedToLocal { remote, local } => {
+                    mapping_operations.insert(remote, (EditMappingOperations::Move, Some(local)));
+                }
+                AlignDecision::MovedToRemote { remote, local } => {
+                    local_moves.push((remote, local));
+                }
+                AlignDecision::BreakMoved { remote } => {
+                    match local_sheet.mapping_data_mut(&remote) {
+                        Ok(mapping) => mapping
+                            .set_last_modifiy_check_hash(Some(mapping.hash_when_updated().clone())),
+                        Err(_) => {
+                            eprintln!(
+                                "{}",
+                                md(t!(
+                                    "jv.fail.sheet.align.mapping_not_found",
+                                    mapping = remote.display()
+                                ))
+                            );
+                            has_failed = true;
+                        }
+                    }
+                }
+                AlignDecision::ConfirmLost { mapping } => {
+                    if let Err(err) = local_sheet.remove_mapping(&mapping) {
+                        eprintln!(
+                            "{}",
+                            md(t!("jv.fail.sheet.align.remove_mapping_failed", err = err))
+                        );
+                        has_failed = true;
+                    }
+                }
+                AlignDecision::MatchLost { lost, created } => {
+                    // The mapping follows the created file, as if it was moved there
+                    mapping_operations.insert(
+                        lost.clone(),
+                        (EditMappingOperations::Move, Some(created.clone())),
+                    );
+                    let Ok(hash_calc) =
+                        sha1_hash::calc_sha1(local_dir.join(&created), 4096usize).await
+                    else {
+                        eprintln!(
+                            "{}",
+                            md(t!(
+                                "jv.fail.sheet.align.calc_hash_failed",
+                                file = created.display()
+                            ))
+                        );
+                        has_failed = true;
+                        continue;
+                    };
+                    match local_sheet.mapping_data_mut(&lost) {
+                        Ok(mapping) => mapping.set_last_modifiy_check_hash(Some(hash_calc.hash)),
+                        Err(_) => {
+                            eprintln!(
+                                "{}",
+                                md(t!(
+                                    "jv.fail.sheet.align.mapping_not_found",
+                                    mapping = lost.display()
+                                ))
+                            );
+                            has_failed = true;
+                        }
+                    }
+                }
+                AlignDecision::ConfirmErased { mapping } => {
+                    if let Err(err) = local_sheet.remove_mapping(&mapping) {
+                        eprintln!(
+                            "{}",
+                            md(t!("jv.fail.sheet.align.delete_mapping_failed", err = err))
+                        );
+                        has_failed = true;
+                        continue;
+                    }
+                    move_erased_file(&local_dir, &mapping).await;
+                }
+            }
+        }
+
+        // Save sheet
+        if let Err(e) = local_sheet.write().await {
+            eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
+            return Err(CmdError::Failed);
+        }
+
+        let moved_result = align_moved_to_remote(&local_dir, local_moves).await;
+
+        if !mapping_operations.is_empty() {
+            let (pool, ctx, _output) = build_pool_and_ctx(&local_cfg).await?;
+            proc_mapping_edit(
+                &pool,
+                ctx,
+                EditMappingActionArguments {
+                    operations: mapping_operations,
+                },
+            )
+            .await?;
+        }
+
+        moved_result?;
+        return if has_failed {
+            Err(CmdError::PartialFailure)
+        } else {
+            Ok(())
+        };
+    }
+
+    // No task input, list all tasks needs align
+    let Some(task) = args.task else {
+        // Raw output
+        if args.raw {
+            if args.list_all {
+                align_tasks.created.iter().for_each(|i| println!("{}", i.0));
+                align_tasks.moved.iter().for_each(|i| println!("{}", i.0));
+                align_tasks.lost.iter().for_each(|i| println!("{}", i.0));
+                align_tasks.erased.iter().for_each(|i| println!("{}", i.0));
+                return Ok(());
+            }
+            if args.list_created {
+                align_tasks.created.iter().for_each(|i| println!("{}", i.0));
+                return Ok(());
+            }
+            if args.list_unsolved {
+                align_tasks.moved.iter().for_each(|i| println!("{}", i.0));
+                align_tasks.lost.iter().for_each(|i| println!("{}", i.0));
+                align_tasks.erased.iter().for_each(|i| println!("{}", i.0));
+                return Ok(());
+            }
+            return Ok(());
+        }
+
+        // Json Output
+        if args.json_output {
+            let mut json_result = AlignJsonResult::default();
+
+            for (name, local_path) in &align_tasks.created {
+                json_result.align_tasks.insert(
+                    name.clone(),
+                    AlignTaskMapping {
+                        local_mapping: local_path.clone(),
+                        remote_mapping: PathBuf::new(),
+                    },
+                );
+            }
+            for (name, (remote_path, local_path)) in &align_tasks.moved {
+                json_result.align_tasks.insert(
+                    name.clone(),
+                    AlignTaskMapping {
+                        local_mapping: local_path.clone(),
+                        remote_mapping: remote_path.clone(),
+                    },
+                );
+            }
+            for (name, local_path) in &align_tasks.lost {
+                json_result.align_tasks.insert(
+                    name.clone(),
+                    AlignTaskMapping {
+                        local_mapping: local_path.clone(),
+                        remote_mapping: PathBuf::new(),
+                    },
+                );
+            }
+            for (name, local_path) in &align_tasks.erased {
+                json_result.align_tasks.insert(
+                    name.clone(),
+                    AlignTaskMapping {
+                        local_mapping: local_path.clone(),
+                        remote_mapping: PathBuf::new(),
+                    },
+                );
+            }
+
+            print_json(json_result, args.pretty);
+            return Ok(());
+        }
+
+        let mut table = SimpleTable::new(vec![
+            t!("jv.success.sheet.align.task_name").to_string(),
+            t!("jv.success.sheet.align.local_path").to_string(),
+            if !align_tasks.moved.is_empty() {
+                t!("jv.success.sheet.align.remote_path").to_string()
+            } else {
+                "".to_string()
+            },
+        ]);
+
+        let mut need_align = 0;
+
+        if !align_tasks.created.is_empty() {
+            align_tasks.created.iter().for_each(|(n, p)| {
+                table.push_item(vec![
+                    format!("+ {}", n).green().to_string(),
+                    p.display().to_string().green().to_string(),
+                    "".to_string(),
+                ]);
+            });
+        }
+
+        if !align_tasks.lost.is_empty() {
+            align_tasks.lost.iter().for_each(|(n, p)| {
+                table.push_item(vec![
+                    format!("- {}", n).red().to_string(),
+                    p.display().to_string().red().to_string(),
+                    "".to_string(),
+                ]);
+            });
+            need_align += 1;
+        }
+
+        if !align_tasks.erased.is_empty() {
+            align_tasks.erased.iter().for_each(|(n, p)| {
+                table.push_item(vec![
+                    format!("& {}", n).magenta().to_string(),
+                    p.display().to_string().magenta().to_string(),
+                    "".to_string(),
+                ]);
+            });
+            need_align += 1;
+        }
+
+        if !align_tasks.moved.is_empty() {
+            align_tasks.moved.iter().for_each(|(n, (rp, lp))| {
+                table.push_item(vec![
+                    format!("> {}", n).yellow().to_string(),
+                    lp.display().to_string().yellow().to_string(),
+                    rp.display().to_string(),
+                ]);
+            });
+            need_align += 1;
+        }
+
+        if need_align > 0 {
+            println!(
+                "{}",
+                md(t!("jv.success.sheet.align.list", tasks = table.to_string()))
+            );
+
+            // Suggestion1: Confirm Erased
+            if align_tasks.erased.len() > 0 {
+                println!(
+                    "\n{}",
+                    md(t!(
+                        "jv.success.sheet.align.suggestion_1",
+                        example_erased = align_tasks.erased[0].0
+                    ))
+                )
+            } else
+            // Suggestion2: Confirm Lost
+            if align_tasks.lost.len() > 0 {
+                println!(
+                    "\n{}",
+                    md(t!(
                         "jv.success.sheet.align.suggestion_2",
                         example_lost = align_tasks.lost[0].0
                     ))
-                )
-            } else
-            // Suggestion3: Confirm Moved
-            if align_tasks.moved.len() > 0 {
-                println!(
-                    "\n{}",
+                )
+            } else
+            // Suggestion3: Confirm Moved
+            if align_tasks.moved.len() > 0 {
+                println!(
+                    "\n{}",
+                    md(t!(
+                        "jv.success.sheet.align.suggestion_3",
+                        example_moved = align_tasks.moved[0].0
+                    ))
+                )
+            }
+        } else {
+            println!("{}", md(t!("jv.success.sheet.align.no_changes").trim()));
+        }
+
+        return Ok(());
+    };
+
+    let Some(to) = args.to else {
+        eprintln!("{}", md(t!("jv.fail.sheet.align.no_direction")));
+        return Err(CmdError::Usage);
+    };
+
+    // Move: alignment mode
+    if task.starts_with("moved") {
+        let align_to = match to.trim().to_lowercase().as_str() {
+            "remote" => "remote",
+            "local" => "local",
+            "break" => "break",
+            _ => {
+                eprintln!("{}", md(t!("jv.fail.sheet.align.unknown_moved_direction")));
+                return Err(CmdError::Usage);
+            }
+        };
+
+        // Build remote move operations
+        let operations: HashMap<FromRelativePathBuf, OperationArgument> = if task == "moved" {
+            // Align all moved items
+            align_tasks
+                .moved
+                .iter()
+                .map(|(_, (remote_path, local_path))| {
+                    (
+                        remote_path.clone(),
+                        (EditMappingOperations::Move, Some(local_path.clone())),
+                    )
+                })
+                .collect()
+        } else {
+            // Align specific moved item
+            align_tasks
+                .moved
+                .iter()
+                .filter(|(key, _)| key == &task)
+                .map(|(_, (remote_path, local_path))| {
+                    (
+                        remote_path.clone(),
+                        (EditMappingOperations::Move, Some(local_path.clone())),
+                    )
+                })
+                .collect()
+        };
+
+        if args.dry_run {
+            let mut sorted_operations = operations.iter().collect::<Vec<_>>();
+            sorted_operations.sort_by(|a, b| a.0.cmp(b.0));
+
+            let operations = sorted_operations
+                .into_iter()
+                .map(|(remote_path, (_, local_path))| {
+                    let local_path = local_path.clone().unwrap_or_default();
+                    match align_to {
+                        "local" => DryRunOperation::MoveMapping {
+                            from: remote_path.clone(),
+                            to: local_path,
+                        },
+                        "remote" => DryRunOperation::MoveLocal {
+                            from: local_path,
+                            to: remote_path.clone(),
+                        },
+                        _ => DryRunOperation::BreakMove {
+                            mapping: remote_path.clone(),
+                        },
+                    }
+                })
+                .collect();
+            print_dry_run(operations, args.json_output, args.pretty);
+            return Ok(());
+        }
+
+        if align_to == "local" {
+            // Align to local
+            // Network move mapping
+            let (pool, ctx, _output) = build_pool_and_ctx(&local_cfg).await?;
+
+            // Process mapping edit, errors are handled internally
+            proc_mapping_edit(&pool, ctx, EditMappingActionArguments { operations }).await?;
+        } else if align_to == "remote" {
+            // Align to remote
+            // Offline move files
+            align_moved_to_remote(
+                &local_dir,
+                operations
+                    .into_iter()
+                    .map(|(remote_path, (_, local_path))| (remote_path, local_path.unwrap()))
+                    .collect(),
+            )
+            .await?;
+        } else if align_to == "break" {
+            for (remote_path, (_, _)) in operations {
+                let Ok(mapping) = local_sheet.mapping_data_mut(&remote_path) else {
+                    eprintln!(
+                        "{}",
+                        md(t!(
+                            "jv.fail.sheet.align.mapping_not_found",
+                            mapping = remote_path.display()
+                        ))
+                    );
+                    return Err(CmdError::NotFound);
+                };
+
+                // Restore the latest detected hash to the original hash,
+                // making the analyzer unable to correctly match
+                //
+                // That is to say,
+                // if the file's hash has remained completely unchanged from the beginning to the end,
+                // then break is also ineffective.
+                mapping.set_last_modifiy_check_hash(Some(mapping.hash_when_updated().clone()));
+            }
+
+            // Save sheet
+            match local_sheet.write().await {
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
+                    return Err(CmdError::Failed);
+                }
+            };
+        }
+    }
+    // Lost: match or confirm mode
+    else if task.starts_with("lost") {
+        let selected_lost_mapping: Vec<(AlignTaskName, PathBuf)> = align_tasks
+            .lost
+            .iter()
+            .filter(|(name, _)| name.starts_with(&task))
+            .cloned()
+            .collect();
+
+        if to == "confirm" {
+            if args.dry_run {
+                let operations = selected_lost_mapping
+                    .into_iter()
+                    .map(|(_, mapping)| DryRunOperation::RemoveMapping { mapping })
+                    .collect();
+                print_dry_run(operations, args.json_output, args.pretty);
+                return Ok(());
+            }
+
+            // Confirm mode
+            for (_, path) in selected_lost_mapping {
+                if let Err(err) = local_sheet.remove_mapping(&path) {
+                    eprintln!(
+                        "{}",
+                        md(t!("jv.fail.sheet.align.remove_mapping_failed", err = err))
+                    );
+                };
+            }
+            // Save sheet
+            match local_sheet.write().await {
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
+                    return Err(CmdError::Failed);
+                }
+            };
+            return Ok(());
+        }
+
+        if to.starts_with("created") {
+            // Match mode
+            let created_file: Vec<(AlignTaskName, PathBuf)> = align_tasks
+                .created
+                .iter()
+                .find(|p| p.0.starts_with(&to))
+                .map(|found| found.clone())
+                .into_iter()
+                .collect();
+
+            if selected_lost_mapping.len() < 1 {
+                eprintln!("{}", md(t!("jv.fail.sheet.align.no_lost_matched")));
+                return Err(CmdError::NotFound);
+            }
+
+            if created_file.len() < 1 {
+                eprintln!("{}", md(t!("jv.fail.sheet.align.no_created_matched")));
+                return Err(CmdError::NotFound);
+            }
+
+            if selected_lost_mapping.len() > 1 {
+                eprintln!("{}", md(t!("jv.fail.sheet.align.too_many_lost")));
+                return Err(CmdError::Usage);
+            }
+
+            if created_file.len() > 1 {
+                eprintln!("{}", md(t!("jv.fail.sheet.align.too_many_created")));
+                return Err(CmdError::Usage);
+            }
+
+            // Check completed, match lost and created items
+            let lost_mapping = &selected_lost_mapping.first().unwrap().1;
+
+            if args.dry_run {
+                let operations = vec![DryRunOperation::MatchLost {
+                    lost: lost_mapping.clone(),
+                    created: created_file.first().unwrap().1.clone(),
+                }];
+                print_dry_run(operations, args.json_output, args.pretty);
+                return Ok(());
+            }
+
+            let created_file = local_dir.join(&created_file.first().unwrap().1);
+
+            let Ok(hash_calc) = sha1_hash::calc_sha1(&created_file, 4096usize).await else {
+                eprintln!("{}", md(t!("jv.fail.sheet.align.calc_hash_failed")));
+                return Err(CmdError::Failed);
+            };
+            let Ok(mapping) = local_sheet.mapping_data_mut(lost_mapping) else {
+                eprintln!(
+                    "{}",
+                    md(t!(
+                        "jv.fail.sheet.align.mapping_not_found",
+                        mapping = lost_mapping.display()
+                    ))
+                );
+                return Err(CmdError::NotFound);
+            };
+
+            mapping.set_last_modifiy_check_hash(Some(hash_calc.hash));
+
+            // Save sheet
+            match local_sheet.write().await {
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
+                    return Err(CmdError::Failed);
+                }
+            };
+        }
+    }
+    // Erased: confirm mode
+    else if task.starts_with("erased") {
+        let selected_erased_mapping: Vec<(AlignTaskName, PathBuf)> = align_tasks
+            .erased
+            .iter()
+            .filter(|(name, _)| name.starts_with(&task))
+            .cloned()
+            .collect();
+
+        if to == "confirm" {
+            if args.dry_run {
+                let mut operations = Vec::new();
+                for (_, mapping) in selected_erased_mapping {
+                    if local_dir.join(&mapping).exists() {
+                        operations.push(DryRunOperation::MoveLocal {
+                            from: mapping.clone(),
+                            to: erased_backup_path(&mapping),
+                        });
+                    }
+                    operations.push(DryRunOperation::RemoveMapping { mapping });
+                }
+                print_dry_run(operations, args.json_output, args.pretty);
+                return Ok(());
+            }
+
+            // Confirm mode
+            for (_, path) in selected_erased_mapping {
+                if let Err(err) = local_sheet.remove_mapping(&path) {
+                    eprintln!(
+                        "{}",
+                        md(t!("jv.fail.sheet.align.delete_mapping_failed", err = err))
+                    );
+                };
+
+                move_erased_file(&local_dir, &path).await;
+            }
+
+            // Save sheet
+            match local_sheet.write().await {
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
+                    return Err(CmdError::Failed);
+                }
+            };
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Decision on an align task, made in the align editor
+enum AlignDecision {
+    /// Move the upstream mapping to the local path
+    MovedToLocal { remote: PathBuf, local: PathBuf },
+
+    /// Move the local file back to the upstream mapping
+    MovedToRemote { remote: PathBuf, local: PathBuf },
+
+    /// Break the move relationship
+    BreakMoved { remote: PathBuf },
+
+    /// Confirm the lost file is deleted
+    ConfirmLost { mapping: PathBuf },
+
+    /// The lost file is moved to the created file
+    MatchLost { lost: PathBuf, created: PathBuf },
+
+    /// Confirm the mapping is erased
+    ConfirmErased { mapping: PathBuf },
+}
+
+/// Open the align tasks in the editor, and parse the action chosen for each task.
+/// Lost items found in `proposals` are pre-filled with the created item matched to them
+async fn start_align_editor(
+    local_dir: &Path,
+    align_tasks: &AlignTasks,
+    proposals: &HashMap<PathBuf, AlignTaskName>,
+) -> Result<Vec<AlignDecision>, CmdError> {
+    let skip = "skip".to_string();
+
+    // Generate editor text
+    let mut table = SimpleTable::new_with_padding(
+        vec![
+            t!("editor.align_line.header.action").trim(),
+            t!("editor.align_line.header.task").trim(),
+            t!("editor.align_line.header.local_path").trim(),
+            t!("editor.align_line.header.remote_path").trim(),
+        ],
+        2,
+    );
+    for (name, (remote_path, local_path)) in &align_tasks.moved {
+        table.push_item(vec![
+            skip.clone(),
+            name.clone(),
+            local_path.display().to_string(),
+            remote_path.display().to_string(),
+        ]);
+    }
+    for (name, path) in &align_tasks.lost {
+        table.push_item(vec![
+            proposals.get(path).unwrap_or(&skip).clone(),
+            name.clone(),
+            path.display().to_string(),
+            "".to_string(),
+        ]);
+    }
+    for (name, path) in &align_tasks.erased {
+        table.push_item(vec![
+            skip.clone(),
+            name.clone(),
+            path.display().to_string(),
+            "".to_string(),
+        ]);
+    }
+    let created = align_tasks
+        .created
+        .iter()
+        .map(|(name, path)| format!("#   {}  {}", name, path.display()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let str = t!(
+        "editor.align_editor",
+        tasks = table.to_string(),
+        created = created
+    );
+
+    let path = local_dir.join(CLIENT_PATH_WORKSPACE_ROOT).join(".ALIGN.md");
+    let result = input_with_editor(str, path, "#").await.unwrap_or_default();
+
+    // Parse format: action task_name paths...
+    let mut decisions = Vec::new();
+    let mut matched_created = HashSet::new();
+    for line in result.lines() {
+        let mut words = line.split_whitespace();
+        let (Some(action), Some(task)) = (words.next(), words.next()) else {
+            continue;
+        };
+        let action_lower = action.to_lowercase();
+        if action_lower == skip {
+            continue;
+        }
+
+        let invalid_action = || {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.sheet.align.work.invalid_action",
+                    action = action,
+                    task = task
+                ))
+            );
+            Err(CmdError::Usage)
+        };
+
+        if let Some((_, (remote, local))) = align_tasks.moved.iter().find(|(n, _)| n == task) {
+            let (remote, local) = (remote.clone(), local.clone());
+            decisions.push(match action_lower.as_str() {
+                "local" => AlignDecision::MovedToLocal { remote, local },
+                "remote" => AlignDecision::MovedToRemote { remote, local },
+                "break" => AlignDecision::BreakMoved { remote },
+                _ => return invalid_action(),
+            });
+        } else if let Some((_, lost)) = align_tasks.lost.iter().find(|(n, _)| n == task) {
+            if action_lower == "confirm" {
+                decisions.push(AlignDecision::ConfirmLost {
+                    mapping: lost.clone(),
+                });
+                continue;
+            }
+
+            // Otherwise the action is the created item matched to the lost item
+            let Some((_, created)) = align_tasks.created.iter().find(|(n, _)| n == action) else {
+                return invalid_action();
+            };
+            if !matched_created.insert(action.to_string()) {
+                eprintln!(
+                    "{}",
                     md(t!(
-                        "jv.success.sheet.align.suggestion_3",
-                        example_moved = align_tasks.moved[0].0
+                        "jv.fail.sheet.align.work.created_matched_twice",
+                        created = action
                     ))
-                )
+                );
+                return Err(CmdError::Usage);
+            }
+            decisions.push(AlignDecision::MatchLost {
+                lost: lost.clone(),
+                created: created.clone(),
+            });
+        } else if let Some((_, erased)) = align_tasks.erased.iter().find(|(n, _)| n == task) {
+            if action_lower != "confirm" {
+                return invalid_action();
+            }
+            decisions.push(AlignDecision::ConfirmErased {
+                mapping: erased.clone(),
+            });
+        } else {
+            eprintln!(
+                "{}",
+                md(t!("jv.fail.sheet.align.work.unknown_task", task = task))
+            );
+            return Err(CmdError::Usage);
+        }
+    }
+
+    Ok(decisions)
+}
+
+/// Lowest name similarity for `jv align --auto` to match a lost item to a created item
+const AUTO_ALIGN_MIN_SIMILARITY: f64 = 0.6;
+
+/// A lost item matched to a created item by `jv align --auto`
+struct AutoAlignMatch {
+    lost: PathBuf,
+    created: PathBuf,
+
+    /// Similarity of the paths, `None` if the content is identical
+    similarity: Option<f64>,
+}
+
+/// Match lost items, given with the hash they were updated at and their size if a snapshot is kept, to created files.
+/// Files with identical content are matched first, then the rest by the similarity of their paths.
+/// Only created files as large as a lost item are hashed
+async fn auto_align_matches(
+    local_dir: &Path,
+    created: &[(AlignTaskName, PathBuf)],
+    lost: Vec<(PathBuf, String, Option<u64>)>,
+) -> Vec<AutoAlignMatch> {
+    let lost_sizes: HashSet<u64> = lost.iter().filter_map(|(_, _, size)| *size).collect();
+
+    let mut created_hashes: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for (_, path) in created {
+        let full_path = local_dir.join(path);
+        let size_matched =
+            std::fs::metadata(&full_path).is_ok_and(|m| lost_sizes.contains(&m.len()));
+        if !size_matched {
+            continue;
+        }
+        match sha1_hash::calc_sha1(&full_path, 4096usize).await {
+            Ok(hash_calc) => created_hashes
+                .entry(hash_calc.hash)
+                .or_default()
+                .push(path.clone()),
+            Err(_) => eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.sheet.align.calc_hash_failed",
+                    file = path.display()
+                ))
+            ),
+        }
+    }
+
+    let mut matches = Vec::new();
+    let mut matched_created = HashSet::new();
+    let mut unmatched_lost = Vec::new();
+
+    // Identical content, the most similar path wins if several created files have the same content
+    for (lost_path, hash, _) in lost {
+        let best = created_hashes.get(&hash).and_then(|candidates| {
+            candidates
+                .iter()
+                .filter(|path| !matched_created.contains(*path))
+                .max_by(|a, b| {
+                    path_similarity(&lost_path, a).total_cmp(&path_similarity(&lost_path, b))
+                })
+                .cloned()
+        });
+        match best {
+            Some(created_path) => {
+                matched_created.insert(created_path.clone());
+                matches.push(AutoAlignMatch {
+                    lost: lost_path,
+                    created: created_path,
+                    similarity: None,
+                });
+            }
+            None => unmatched_lost.push(lost_path),
+        }
+    }
+
+    // Similar paths, the most similar pairs first
+    let mut candidates = Vec::new();
+    for lost_path in &unmatched_lost {
+        for (_, created_path) in created {
+            if matched_created.contains(created_path) {
+                continue;
+            }
+            let similarity = path_similarity(lost_path, created_path);
+            if similarity >= AUTO_ALIGN_MIN_SIMILARITY {
+                candidates.push((similarity, lost_path, created_path));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut matched_lost = HashSet::new();
+    for (similarity, lost_path, created_path) in candidates {
+        if matched_lost.contains(lost_path) || matched_created.contains(created_path) {
+            continue;
+        }
+        matched_lost.insert(lost_path);
+        matched_created.insert(created_path.clone());
+        matches.push(AutoAlignMatch {
+            lost: lost_path.clone(),
+            created: created_path.clone(),
+            similarity: Some(similarity),
+        });
+    }
+
+    matches
+}
+
+/// Move local files back to their upstream mappings, given as (remote, local) pairs
+async fn align_moved_to_remote(local_dir: &Path, moves: Vec<(PathBuf, PathBuf)>) -> CmdResult {
+    let mut has_failed = false;
+    for (remote_path, local_path) in moves {
+        let from = local_dir.join(&local_path);
+        let to = local_dir.join(&remote_path);
+
+        if to.exists() {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.sheet.align.target_exists",
+                    local = local_path.display(),
+                    remote = remote_path.display()
+                ))
+            );
+            return Err(CmdError::Rejected);
+        }
+
+        if let Some(parent) = to.parent() {
+            if let Err(err) = fs::create_dir_all(parent).await {
+                eprintln!("{}", md(t!("jv.fail.sheet.align.move_failed", err = err)));
+                has_failed = true;
+                continue;
+            }
+        } else {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.sheet.align.move_failed",
+                    err = "no parent directory"
+                ))
+            );
+            has_failed = true;
+            continue;
+        }
+        if let Err(err) = fs::rename(from, to).await {
+            eprintln!("{}", md(t!("jv.fail.sheet.align.move_failed", err = err)));
+            has_failed = true;
+        }
+    }
+    if has_failed {
+        return Err(CmdError::PartialFailure);
+    }
+    Ok(())
+}
+
+/// Where the local file of an erased mapping is moved to, relative to the workspace
+fn erased_backup_path(mapping: &Path) -> PathBuf {
+    PathBuf::from(CLIENT_FOLDER_WORKSPACE_ROOT_NAME)
+        .join(".temp")
+        .join("erased")
+        .join(mapping)
+}
+
+/// Move the local file of an erased mapping away, if it exists
+async fn move_erased_file(local_dir: &Path, mapping: &Path) {
+    let from = local_dir.join(mapping);
+
+    if !from.exists() {
+        return;
+    }
+
+    let to = local_dir.join(erased_backup_path(mapping));
+    let to_path = to
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| to.clone());
+
+    let _ = fs::create_dir_all(&to_path).await;
+    if let Some(e) = fs::rename(&from, &to).await.err() {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.move.rename_failed",
+                from = from.display(),
+                to = to.display(),
+                error = e
+            ))
+            .yellow()
+        );
+    }
+}
+
+async fn jv_track(mut args: TrackFileArgs) -> CmdResult {
+    // Read the description file before precheck, as its path is relative to the current directory
+    if let Some(desc_file) = &args.desc_file {
+        let list_from_stdin = args.from_file.as_deref() == Some("-")
+            || args
+                .track_file_patterns
+                .iter()
+                .any(|pattern| pattern == "-");
+        if desc_file == "-" && list_from_stdin {
+            eprintln!("{}", md(t!("jv.fail.track.desc_file_stdin")));
+            return Err(CmdError::Usage);
+        }
+        match read_text(desc_file).await {
+            Ok(desc) => args.desc = Some(desc.trim().to_string()),
+            Err(err) => {
+                eprintln!(
+                    "{}",
+                    md(t!("jv.fail.track.desc_file", path = desc_file, error = err))
+                );
+                return Err(CmdError::Failed);
             }
-        } else {
-            println!("{}", md(t!("jv.success.sheet.align.no_changes").trim()));
         }
+    }
 
-        return;
+    // Perform glob operation before precheck, as precheck will call set_current_dir
+    let track_files = if !args.track_file_patterns.is_empty() || args.from_file.is_some() {
+        let local_dir = match current_local_path() {
+            Some(dir) => dir,
+            None => {
+                eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+                return Err(CmdError::NotWorkspace);
+            }
+        };
+        let files = collect_files(
+            &args.track_file_patterns,
+            &args.exclude,
+            args.from_file.as_deref(),
+            args.ignore_case,
+            &local_dir,
+        )
+        .await?;
+        files
+            .iter()
+            .filter_map(|f| PathBuf::from_str(f.0).ok())
+            .collect::<Vec<_>>()
+    } else {
+        println!("{}", md(t!("jv.track")));
+        return Err(CmdError::Usage);
     };
 
-    let Some(to) = args.to else {
-        eprintln!("{}", md(t!("jv.fail.sheet.align.no_direction")));
-        return;
+    // set_current_dir called here
+    let local_config = precheck().await?;
+
+    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_config.clone()) else {
+        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
+        return Err(CmdError::NotWorkspace);
     };
 
-    // Move: alignment mode
-    if task.starts_with("moved") {
-        let align_to = match to.trim().to_lowercase().as_str() {
-            "remote" => "remote",
-            "local" => "local",
-            "break" => "break",
-            _ => {
-                eprintln!("{}", md(t!("jv.fail.sheet.align.unknown_moved_direction")));
-                return;
-            }
-        };
+    if track_files.iter().len() < 1 {
+        eprintln!("{}", md(t!("jv.fail.track.no_selection")));
+        return Err(CmdError::NotFound);
+    };
 
-        // Build remote move operations
-        let operations: HashMap<FromRelativePathBuf, OperationArgument> = if task == "moved" {
-            // Align all moved items
-            align_tasks
-                .moved
-                .iter()
-                .map(|(_, (remote_path, local_path))| {
-                    (
-                        remote_path.clone(),
-                        (EditMappingOperations::Move, Some(local_path.clone())),
-                    )
-                })
-                .collect()
-        } else {
-            // Align specific moved item
-            align_tasks
-                .moved
-                .iter()
-                .filter(|(key, _)| key == &task)
-                .map(|(_, (remote_path, local_path))| {
-                    (
-                        remote_path.clone(),
-                        (EditMappingOperations::Move, Some(local_path.clone())),
-                    )
-                })
-                .collect()
-        };
+    if args.dry_run {
+        let files = track_files.iter().cloned().collect();
+        let overwrite = args.allow_overwrite;
+        let json_output = args.json_output;
+        let pretty = args.pretty;
+        let update_info = get_update_info(local_workspace, &files, args).await?;
 
-        if align_to == "local" {
-            // Align to local
-            // Network move mapping
-            let (pool, ctx, _output) = match build_pool_and_ctx(&local_cfg).await {
-                Some(result) => result,
-                None => return,
-            };
+        let operations =
+            track_dry_run_operations(&local_config, &files, &update_info, overwrite).await?;
+        print_dry_run(operations, json_output, pretty);
+        return Ok(());
+    }
 
-            // Process mapping edit, errors are handled internally
-            let _ = proc_mapping_edit(&pool, ctx, EditMappingActionArguments { operations }).await;
-        } else if align_to == "remote" {
-            // Align to remote
-            // Offline move files
-            for (remote_path, (_, local_path)) in operations {
-                let local_path = local_path.unwrap();
-                let from = local_dir.join(&local_path);
-                let to = local_dir.join(&remote_path);
+    // A failing pre hook aborts before connecting or opening the editor
+    let mut hook_paths: Vec<PathBuf> = track_files.clone();
+    hook_paths.sort();
+    run_pre_hook("track", &hook_paths).await?;
 
-                if to.exists() {
-                    eprintln!(
-                        "{}",
-                        md(t!(
-                            "jv.fail.sheet.align.target_exists",
-                            local = local_path.display(),
-                            remote = remote_path.display()
-                        ))
-                    );
-                    return;
-                }
+    let (pool, ctx, mut output) = build_pool_and_ctx(&local_config).await?;
 
-                if let Some(parent) = to.parent() {
-                    if let Err(err) = fs::create_dir_all(parent).await {
-                        eprintln!("{}", md(t!("jv.fail.sheet.align.move_failed", err = err)));
-                        continue;
+    let files = track_files.iter().cloned().collect();
+    let overwrite = args.allow_overwrite;
+    let json_output = args.json_output;
+    let pretty = args.pretty;
+    let update_info = get_update_info(local_workspace, &files, args).await?;
+
+    // Files to transfer and the sizes of the uploads, to report the progress
+    let mut progress = TransferProgress::new(
+        track_transfer_files(&local_config, &files, &update_info, overwrite).await,
+    );
+
+    // Draw a progress bar on a terminal, otherwise print the progress periodically
+    let draw_bar = !json_output && std::io::stdout().is_terminal();
+    let progress_started = Instant::now();
+
+    // Report a failed track, as a json result or a message
+    let fail = |result: TrackResult, err: CmdError, msg: String| -> CmdResult {
+        if json_output {
+            print_json(
+                TrackJsonResult {
+                    result,
+                    ..Default::default()
+                },
+                pretty,
+            );
+        } else {
+            eprintln!("{}", msg);
+        }
+        Err(err)
+    };
+
+    let track_action = proc_track_file_action(
+        &pool,
+        ctx,
+        TrackFileActionArguments {
+            relative_pathes: files,
+            file_update_info: update_info,
+            // The messages report the progress, they are not printed with `--json`
+            print_infos: true,
+            allow_overwrite_modified: overwrite,
+        },
+    );
+
+    // Drive the progress alongside the track, the result is always the one of the track
+    let progress_loop = async {
+        let mut ticker = tokio::time::interval(if draw_bar {
+            Duration::from_millis(200)
+        } else {
+            Duration::from_secs(5)
+        });
+        // The first tick completes immediately
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                msg = output.recv() => {
+                    let Some(msg) = msg else {
+                        break;
+                    };
+                    progress.apply_message(&msg);
+                    if !json_output {
+                        if draw_bar {
+                            clear_progress_line();
+                        }
+                        println!("{}", msg);
+                    }
+                }
+                _ = ticker.tick() => {
+                    // On a terminal the bar is redrawn below
+                    if !draw_bar {
+                        let line = track_progress_line(&progress, progress_started.elapsed());
+                        if json_output {
+                            // Keep the json output clean
+                            eprintln!("{}", line);
+                        } else {
+                            println!("{}", line);
+                        }
                     }
+                }
+            }
+
+            if draw_bar {
+                draw_progress_line(format!(
+                    "{} {}",
+                    progress_bar(progress.fraction(), 30),
+                    track_progress_line(&progress, progress_started.elapsed())
+                ));
+            }
+        }
+    };
+
+    tokio::pin!(track_action);
+    let track_result = tokio::select! {
+        result = &mut track_action => result,
+        _ = progress_loop => track_action.await,
+    };
+    if draw_bar {
+        clear_progress_line();
+    }
+
+    let result = match track_result {
+        Ok(result) => match result {
+            TrackFileActionResult::Done {
+                created,
+                updated,
+                synced,
+                skipped,
+            } => {
+                save_base_snapshots(
+                    &local_config,
+                    created.iter().chain(updated.iter()).chain(synced.iter()),
+                )
+                .await;
+
+                let has_skipped = skipped.len() > 0;
+                if json_output {
+                    print_json(
+                        TrackJsonResult {
+                            result: TrackResult::Done,
+                            created,
+                            updated,
+                            synced,
+                            skipped,
+                        },
+                        pretty,
+                    );
                 } else {
-                    eprintln!(
+                    println!(
                         "{}",
                         md(t!(
-                            "jv.fail.sheet.align.move_failed",
-                            err = "no parent directory"
+                            "jv.result.track.done",
+                            count = created.len() + updated.len() + synced.len(),
+                            created = created.len(),
+                            updated = updated.len(),
+                            synced = synced.len()
                         ))
                     );
-                    continue;
+
+                    if has_skipped {
+                        println!(
+                            "\n{}",
+                            md(t!(
+                                "jv.result.track.tip_has_skipped",
+                                skipped_num = skipped.len(),
+                                skipped = skipped
+                                    .iter()
+                                    .map(|f| f.display().to_string())
+                                    .collect::<Vec<String>>()
+                                    .join("\n")
+                                    .trim()
+                            ))
+                            .yellow()
+                        );
+                    }
                 }
-                if let Err(err) = fs::rename(from, to).await {
-                    eprintln!("{}", md(t!("jv.fail.sheet.align.move_failed", err = err)));
+
+                if has_skipped {
+                    return Err(CmdError::PartialFailure);
                 }
+                Ok(())
             }
-        } else if align_to == "break" {
-            for (remote_path, (_, _)) in operations {
-                let Ok(mapping) = local_sheet.mapping_data_mut(&remote_path) else {
-                    eprintln!(
-                        "{}",
+            TrackFileActionResult::AuthorizeFailed(e) => fail(
+                TrackResult::AuthorizeFailed(e.to_string()),
+                CmdError::AuthorizeFailed,
+                md(t!("jv.result.common.authroize_failed", err = e)),
+            ),
+            TrackFileActionResult::StructureChangesNotSolved => fail(
+                TrackResult::StructureChangesNotSolved,
+                CmdError::Rejected,
+                md(t!("jv.result.track.structure_changes_not_solved")),
+            ),
+            TrackFileActionResult::CreateTaskFailed(create_task_result) => match create_task_result
+            {
+                CreateTaskResult::Success(_) => Ok(()), // Success is not handled here
+                CreateTaskResult::CreateFileOnExistPath(path) => fail(
+                    TrackResult::CreateFileOnExistPath(path.clone()),
+                    CmdError::Rejected,
+                    md(t!(
+                        "jv.result.track.create_failed.create_file_on_exist_path",
+                        path = path.display()
+                    )),
+                ),
+                CreateTaskResult::SheetNotFound(sheet) => fail(
+                    TrackResult::SheetNotFound(sheet.to_string()),
+                    CmdError::NotFound,
+                    md(t!(
+                        "jv.result.track.create_failed.sheet_not_found",
+                        name = sheet
+                    )),
+                ),
+            },
+            TrackFileActionResult::UpdateTaskFailed(update_task_result) => match update_task_result
+            {
+                UpdateTaskResult::Success(_) => Ok(()), // Success is not handled here
+                UpdateTaskResult::VerifyFailed { path, reason } => match reason {
+                    VerifyFailReason::SheetNotFound(sheet_name) => fail(
+                        TrackResult::SheetNotFound(sheet_name.to_string()),
+                        CmdError::NotFound,
                         md(t!(
-                            "jv.fail.sheet.align.mapping_not_found",
-                            mapping = remote_path.display()
-                        ))
-                    );
-                    return;
-                };
+                            "jv.result.track.update_failed.verify.sheet_not_found",
+                            sheet_name = sheet_name
+                        )),
+                    ),
+                    VerifyFailReason::MappingNotFound => fail(
+                        TrackResult::MappingNotFound(path.clone()),
+                        CmdError::NotFound,
+                        md(t!(
+                            "jv.result.track.update_failed.verify.mapping_not_found",
+                            path = path.display()
+                        )),
+                    ),
+                    VerifyFailReason::VirtualFileNotFound(vfid) => fail(
+                        TrackResult::VirtualFileNotFound(vfid.clone()),
+                        CmdError::NotFound,
+                        md(t!(
+                            "jv.result.track.update_failed.verify.virtual_file_not_found",
+                            vfid = vfid
+                        )),
+                    ),
+                    VerifyFailReason::VirtualFileReadFailed(vfid) => fail(
+                        TrackResult::VirtualFileReadFailed(vfid.clone()),
+                        CmdError::Failed,
+                        md(t!(
+                            "jv.result.track.update_failed.verify.virtual_file_read_failed",
+                            vfid = vfid
+                        )),
+                    ),
+                    VerifyFailReason::NotHeld => fail(
+                        TrackResult::NotHeld(path.clone()),
+                        CmdError::Rejected,
+                        md(t!(
+                            "jv.result.track.update_failed.verify.not_held",
+                            path = path.display()
+                        )),
+                    ),
+                    VerifyFailReason::VersionDismatch(current_version, latest_version) => fail(
+                        TrackResult::VersionDismatch {
+                            path: path.clone(),
+                            current: current_version.clone(),
+                            latest: latest_version.clone(),
+                        },
+                        CmdError::Rejected,
+                        md(t!(
+                            "jv.result.track.update_failed.verify.version_dismatch",
+                            version_current = current_version,
+                            version_latest = latest_version
+                        )),
+                    ),
+                    VerifyFailReason::UpdateButNoDescription => fail(
+                        TrackResult::UpdateButNoDescription(path.clone()),
+                        CmdError::Usage,
+                        md(t!(
+                            "jv.result.track.update_failed.verify.update_but_no_description"
+                        )),
+                    ),
+                    VerifyFailReason::VersionAlreadyExist(latest_version) => fail(
+                        TrackResult::VersionAlreadyExist {
+                            path: path.clone(),
+                            version: latest_version.clone(),
+                        },
+                        CmdError::Rejected,
+                        md(t!(
+                            "jv.result.track.update_failed.verify.version_already_exist",
+                            path = path.display(),
+                            version = latest_version
+                        )),
+                    ),
+                },
+            },
+            TrackFileActionResult::SyncTaskFailed(sync_task_result) => match sync_task_result {
+                SyncTaskResult::Success(_) => Ok(()), // Success is not handled here
+            },
+        },
+        Err(e) => Err(handle_err(e)),
+    };
 
-                // Restore the latest detected hash to the original hash,
-                // making the analyzer unable to correctly match
-                //
-                // That is to say,
-                // if the file's hash has remained completely unchanged from the beginning to the end,
-                // then break is also ineffective.
-                mapping.set_last_modifiy_check_hash(Some(mapping.hash_when_updated().clone()));
-            }
+    run_post_hook("track", &hook_paths, &result).await;
+    result
+}
 
-            // Save sheet
-            match local_sheet.write().await {
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
-                    return;
-                }
-            };
-        }
-    }
-    // Lost: match or confirm mode
-    else if task.starts_with("lost") {
-        let selected_lost_mapping: Vec<(AlignTaskName, PathBuf)> = align_tasks
-            .lost
-            .iter()
-            .filter(|(name, _)| name.starts_with(&task))
-            .cloned()
-            .collect();
+/// One line of the progress of `jv track`
+fn track_progress_line(progress: &TransferProgress, elapsed: Duration) -> String {
+    t!(
+        "jv.progress.track",
+        percent = (progress.fraction() * 100.0) as u32,
+        done = progress.files_done(),
+        count = progress.files_total(),
+        sent = size_str(progress.sent_bytes() as usize),
+        total = size_str(progress.total_bytes() as usize),
+        elapsed = duration_str(elapsed),
+        eta = progress
+            .eta(elapsed)
+            .map(duration_str)
+            .unwrap_or("--".to_string()),
+        current = progress
+            .last_file()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default()
+    )
+    .trim()
+    .to_string()
+}
 
-        if to == "confirm" {
-            // Confirm mode
-            for (_, path) in selected_lost_mapping {
-                if let Err(err) = local_sheet.remove_mapping(&path) {
-                    eprintln!(
-                        "{}",
-                        md(t!("jv.fail.sheet.align.remove_mapping_failed", err = err))
-                    );
-                };
-            }
-            // Save sheet
-            match local_sheet.write().await {
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
-                    return;
-                }
-            };
-            return;
-        }
+/// Redraw the last line of the terminal, cut to the width of the terminal
+fn draw_progress_line(line: String) {
+    let width = crossterm::terminal::size()
+        .map(|(width, _)| width as usize)
+        .unwrap_or(80);
+
+    let mut used = 0;
+    let cut: String = line
+        .chars()
+        .take_while(|c| {
+            used += display_width(&c.to_string());
+            used < width
+        })
+        .collect();
 
-        if to.starts_with("created") {
-            // Match mode
-            let created_file: Vec<(AlignTaskName, PathBuf)> = align_tasks
-                .created
-                .iter()
-                .find(|p| p.0.starts_with(&to))
-                .map(|found| found.clone())
-                .into_iter()
-                .collect();
+    let _ = crossterm::execute!(
+        std::io::stdout(),
+        crossterm::cursor::MoveToColumn(0),
+        crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine),
+        crossterm::style::Print(cut)
+    );
+}
 
-            if selected_lost_mapping.len() < 1 {
-                eprintln!("{}", md(t!("jv.fail.sheet.align.no_lost_matched")));
-                return;
-            }
+fn clear_progress_line() {
+    draw_progress_line(String::new());
+}
+
+/// Files `jv track` is expected to transfer, with the sizes of the uploads (0 for downloads), to report the progress.
+/// Estimated from the last modification check instead of analyzing the workspace again
+async fn track_transfer_files(
+    local_config: &LocalConfig,
+    files: &HashSet<PathBuf>,
+    update_info: &HashMap<PathBuf, (NextVersion, UpdateDescription)>,
+    allow_overwrite: bool,
+) -> Vec<(PathBuf, u64)> {
+    let account = local_config.current_account();
+    let Some(sheet_name) = local_config.sheet_in_use().clone() else {
+        return Vec::new();
+    };
+    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_config.clone()) else {
+        return Vec::new();
+    };
+    let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
+        return Vec::new();
+    };
+    let Ok(cached_sheet) = CachedSheet::cached_sheet_data(&sheet_name).await else {
+        return Vec::new();
+    };
+    let Ok(latest_file_data_path) = LatestFileData::data_path(&account) else {
+        return Vec::new();
+    };
+    let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
+        return Vec::new();
+    };
+    let size = |file: &PathBuf| std::fs::metadata(file).map(|m| m.len()).unwrap_or_default();
 
-            if created_file.len() < 1 {
-                eprintln!("{}", md(t!("jv.fail.sheet.align.no_created_matched")));
-                return;
+    let mut transfers = Vec::new();
+    for file in files {
+        // Not in the sheet, created
+        let Some(cached_mapping) = cached_sheet.mapping().get(file) else {
+            if file.exists() {
+                transfers.push((file.clone(), size(file)));
             }
+            continue;
+        };
 
-            if selected_lost_mapping.len() > 1 {
-                eprintln!("{}", md(t!("jv.fail.sheet.align.too_many_lost")));
-                return;
+        let held = latest_file_data
+            .file_holder(&cached_mapping.id)
+            .is_some_and(|holder| holder == &account);
+        if held {
+            // Uploaded as a new version
+            if update_info.contains_key(file) {
+                transfers.push((file.clone(), size(file)));
             }
+            continue;
+        }
 
-            if created_file.len() > 1 {
-                eprintln!("{}", md(t!("jv.fail.sheet.align.too_many_created")));
-                return;
-            }
+        let Ok(mapping) = local_sheet.mapping_data(file) else {
+            continue;
+        };
+        let outdated = latest_file_data.file_version(&cached_mapping.id)
+            != Some(mapping.version_when_updated());
+        let overwritten = allow_overwrite && mapping.last_modifiy_check_result();
+        if !file.exists() || outdated || overwritten {
+            transfers.push((file.clone(), 0));
+        }
+    }
+    transfers
+}
 
-            // Check completed, match lost and created items
-            let lost_mapping = &selected_lost_mapping.first().unwrap().1;
-            let created_file = local_dir.join(&created_file.first().unwrap().1);
+/// Compute the operations `jv track` would perform, from the local status of the files
+async fn track_dry_run_operations(
+    local_config: &LocalConfig,
+    files: &HashSet<PathBuf>,
+    update_info: &HashMap<PathBuf, (NextVersion, UpdateDescription)>,
+    allow_overwrite: bool,
+) -> Result<Vec<DryRunOperation>, CmdError> {
+    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_config.clone()) else {
+        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
+        return Err(CmdError::NotWorkspace);
+    };
 
-            let Ok(hash_calc) = sha1_hash::calc_sha1(&created_file, 4096usize).await else {
-                eprintln!("{}", md(t!("jv.fail.sheet.align.calc_hash_failed")));
-                return;
-            };
-            let Ok(mapping) = local_sheet.mapping_data_mut(lost_mapping) else {
-                eprintln!(
-                    "{}",
-                    md(t!(
-                        "jv.fail.sheet.align.mapping_not_found",
-                        mapping = lost_mapping.display()
-                    ))
-                );
-                return;
-            };
+    let account = local_config.current_account();
 
-            mapping.set_last_modifiy_check_hash(Some(hash_calc.hash));
+    let Some(sheet_name) = local_config.sheet_in_use().clone() else {
+        eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
+        return Err(CmdError::NoSheetInUse);
+    };
 
-            // Save sheet
-            match local_sheet.write().await {
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
-                    return;
-                }
-            };
-        }
-    }
-    // Erased: confirm mode
-    else if task.starts_with("erased") {
-        let selected_erased_mapping: Vec<(AlignTaskName, PathBuf)> = align_tasks
-            .erased
-            .iter()
-            .filter(|(name, _)| name.starts_with(&task))
-            .cloned()
-            .collect();
+    let Ok(latest_file_data_path) = LatestFileData::data_path(&account) else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_file_data",
+                account = &account
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
 
-        if to == "confirm" {
-            // Confirm mode
-            for (_, path) in selected_erased_mapping {
-                if let Err(err) = local_sheet.remove_mapping(&path) {
-                    eprintln!(
-                        "{}",
-                        md(t!("jv.fail.sheet.align.delete_mapping_failed", err = err))
-                    );
-                };
+    let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_file_data",
+                account = &account
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
 
-                let from = local_dir.join(&path);
+    let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.local_sheet",
+                account = &account,
+                sheet = &sheet_name
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
 
-                if !from.exists() {
-                    continue;
-                }
+    let Ok(cached_sheet) = CachedSheet::cached_sheet_data(&sheet_name).await else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.cached_sheet",
+                sheet = &sheet_name
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
 
-                let to = local_dir
-                    .join(CLIENT_FOLDER_WORKSPACE_ROOT_NAME)
-                    .join(".temp")
-                    .join("erased")
-                    .join(path);
-                let to_path = to
-                    .parent()
-                    .map(|p| p.to_path_buf())
-                    .unwrap_or_else(|| to.clone());
-
-                let _ = fs::create_dir_all(&to_path).await;
-                if let Some(e) = fs::rename(&from, &to).await.err() {
-                    eprintln!(
-                        "{}",
-                        md(t!(
-                            "jv.fail.move.rename_failed",
-                            from = from.display(),
-                            to = to.display(),
-                            error = e
-                        ))
-                        .yellow()
-                    );
-                }
-            }
+    let Ok(analyzed) = AnalyzeResult::analyze_local_status(&local_workspace).await else {
+        eprintln!("{}", md(t!("jv.fail.status.analyze")).trim());
+        return Err(CmdError::Failed);
+    };
 
-            // Save sheet
-            match local_sheet.write().await {
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
-                    return;
-                }
-            };
-            return;
-        }
+    // Track refuses to run until the lost and moved items are aligned
+    if !analyzed.lost.is_empty() || !analyzed.moved.is_empty() {
+        eprintln!("{}", md(t!("jv.result.track.structure_changes_not_solved")));
+        return Err(CmdError::Rejected);
     }
-}
 
-async fn jv_track(args: TrackFileArgs) {
-    // Perform glob operation before precheck, as precheck will call set_current_dir
-    let track_files = if let Some(pattern) = args.track_file_pattern.clone() {
-        let local_dir = match current_local_path() {
-            Some(dir) => dir,
-            None => {
-                eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-                return;
+    let mut sorted_files = files.iter().collect::<Vec<_>>();
+    sorted_files.sort();
+
+    let mut operations = Vec::new();
+    for file in sorted_files {
+        // Not in the sheet, create it
+        let Some(cached_mapping) = cached_sheet.mapping().get(file) else {
+            if file.exists() {
+                operations.push(DryRunOperation::CreateFile { path: file.clone() });
             }
+            continue;
         };
-        let files = glob(pattern, &local_dir).await;
-        files
-            .iter()
-            .filter_map(|f| PathBuf::from_str(f.0).ok())
-            .collect::<Vec<_>>()
-    } else {
-        println!("{}", md(t!("jv.track")));
-        return;
-    };
 
-    // set_current_dir called here
-    let local_config = match precheck().await {
-        Some(config) => config,
-        None => {
-            return;
+        let latest_version = latest_file_data.file_version(&cached_mapping.id).cloned();
+        let local_version = local_sheet
+            .mapping_data(file)
+            .ok()
+            .map(|mapping| mapping.version_when_updated().clone());
+        let held = latest_file_data
+            .file_holder(&cached_mapping.id)
+            .is_some_and(|holder| holder == &account);
+
+        if analyzed.modified.contains(file) {
+            if held {
+                // Held and modified, upload a new version
+                let info = update_info.get(file);
+                operations.push(DryRunOperation::UpdateFile {
+                    path: file.clone(),
+                    current_version: local_version.unwrap_or_default(),
+                    next_version: info.map(|(version, _)| version.clone()),
+                    description: info.map(|(_, description)| description.clone()),
+                });
+            } else if allow_overwrite {
+                operations.push(DryRunOperation::SyncFile {
+                    path: file.clone(),
+                    version: latest_version,
+                });
+            } else {
+                operations.push(DryRunOperation::SkipSync { path: file.clone() });
+            }
+            continue;
         }
-    };
 
-    let Some(local_workspace) = LocalWorkspace::init_current_dir(local_config.clone()) else {
-        eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
-    };
+        // Outdated or missing locally, download the latest version
+        if !file.exists() || local_version != latest_version {
+            operations.push(DryRunOperation::SyncFile {
+                path: file.clone(),
+                version: latest_version,
+            });
+        }
+    }
 
-    if track_files.iter().len() < 1 {
-        eprintln!("{}", md(t!("jv.fail.track.no_selection")));
-        return;
-    };
+    Ok(operations)
+}
 
-    let (pool, ctx, mut output) = match build_pool_and_ctx(&local_config).await {
-        Some(result) => result,
-        None => return,
+async fn get_update_info(
+    workspace: LocalWorkspace,
+    files: &HashSet<PathBuf>,
+    args: TrackFileArgs,
+) -> Result<HashMap<PathBuf, (NextVersion, UpdateDescription)>, CmdError> {
+    let bump = match &args.bump {
+        Some(bump) => match VersionBump::parse(bump) {
+            Some(bump) => bump,
+            None => {
+                eprintln!("{}", md(t!("jv.fail.track.invalid_bump", bump = bump)));
+                return Err(CmdError::Usage);
+            }
+        },
+        None => VersionBump::default(),
     };
 
-    let files = track_files.iter().cloned().collect();
-    let overwrite = args.allow_overwrite;
-    let update_info = get_update_info(local_workspace, &files, args).await;
+    // Bumping needs versions to fill, and doesn't apply to a version given explicitly
+    if args.bump.is_some() && ((!args.work && args.desc.is_none()) || args.next_version.is_some()) {
+        eprintln!("{}", md(t!("jv.fail.track.bump_no_effect")));
+        return Err(CmdError::Usage);
+    }
 
-    let track_action = proc_track_file_action(
-        &pool,
-        ctx,
-        TrackFileActionArguments {
-            relative_pathes: files,
-            file_update_info: update_info,
-            print_infos: true,
-            allow_overwrite_modified: overwrite,
-        },
-    );
+    let mut result = HashMap::new();
+    if !args.work && args.desc.is_none() {
+        return Ok(result);
+    }
 
-    tokio::select! {
-        result = track_action => {
-            match result {
-                Ok(result) => match result {
-                    TrackFileActionResult::Done {
-                        created,
-                        updated,
-                        synced,
-                        skipped,
-                    } => {
-                        println!(
-                            "{}",
-                            md(t!(
-                                "jv.result.track.done",
-                                count = created.len() + updated.len() + synced.len(),
-                                created = created.len(),
-                                updated = updated.len(),
-                                synced = synced.len()
-                            ))
-                        );
+    let updatable = updatable_files(&workspace, files).await;
+    let schemes = VersionSchemeRules::parse(&config_list(ConfigKey::VersionSchemes).join("\n"));
+    let today = chrono::Local::now().date_naive();
+    let next_version = |path: &PathBuf, latest: &VirtualFileVersion| {
+        schemes
+            .scheme_of(&path.display().to_string())
+            .next_version(latest, bump, today)
+    };
 
-                        if skipped.len() > 0 {
-                            println!(
-                                "\n{}",
-                                md(t!(
-                                    "jv.result.track.tip_has_skipped",
-                                    skipped_num = skipped.len(),
-                                    skipped = skipped
-                                        .iter()
-                                        .map(|f| f.display().to_string())
-                                        .collect::<Vec<String>>()
-                                        .join("\n")
-                                        .trim()
-                                ))
-                                .yellow()
-                            );
-                        }
-                    }
-                    TrackFileActionResult::AuthorizeFailed(e) => {
-                        eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)))
-                    }
-                    TrackFileActionResult::StructureChangesNotSolved => {
-                        eprintln!("{}", md(t!("jv.result.track.structure_changes_not_solved")))
-                    }
-                    TrackFileActionResult::CreateTaskFailed(create_task_result) => match create_task_result
-                    {
-                        CreateTaskResult::Success(_) => {} // Success is not handled here
-                        CreateTaskResult::CreateFileOnExistPath(path) => {
-                            eprintln!(
-                                "{}",
-                                md(t!(
-                                    "jv.result.track.create_failed.create_file_on_exist_path",
-                                    path = path.display()
-                                ))
-                            )
-                        }
-                        CreateTaskResult::SheetNotFound(sheet) => {
-                            eprintln!(
-                                "{}",
-                                md(t!(
-                                    "jv.result.track.create_failed.sheet_not_found",
-                                    name = sheet
-                                ))
-                            )
-                        }
-                    },
-                    TrackFileActionResult::UpdateTaskFailed(update_task_result) => match update_task_result
-                    {
-                        UpdateTaskResult::Success(_) => {} // Success is not handled here
-                        UpdateTaskResult::VerifyFailed { path, reason } => match reason {
-                            VerifyFailReason::SheetNotFound(sheet_name) => {
-                                eprintln!(
-                                    "{}",
-                                    md(t!(
-                                        "jv.result.track.update_failed.verify.sheet_not_found",
-                                        sheet_name = sheet_name
-                                    ))
-                                )
-                            }
-                            VerifyFailReason::MappingNotFound => {
-                                eprintln!(
-                                    "{}",
-                                    md(t!(
-                                        "jv.result.track.update_failed.verify.mapping_not_found",
-                                        path = path.display()
-                                    ))
-                                )
-                            }
-                            VerifyFailReason::VirtualFileNotFound(vfid) => {
-                                eprintln!(
-                                    "{}",
-                                    md(t!(
-                                        "jv.result.track.update_failed.verify.virtual_file_not_found",
-                                        vfid = vfid
-                                    ))
-                                )
-                            }
-                            VerifyFailReason::VirtualFileReadFailed(vfid) => {
-                                eprintln!(
-                                    "{}",
-                                    md(t!(
-                                        "jv.result.track.update_failed.verify.virtual_file_read_failed",
-                                        vfid = vfid
-                                    ))
-                                )
-                            }
-                            VerifyFailReason::NotHeld => {
-                                eprintln!(
-                                    "{}",
-                                    md(t!(
-                                        "jv.result.track.update_failed.verify.not_held",
-                                        path = path.display()
-                                    ))
-                                )
-                            }
-                            VerifyFailReason::VersionDismatch(current_version, latest_version) => {
-                                eprintln!(
-                                    "{}",
-                                    md(t!(
-                                        "jv.result.track.update_failed.verify.version_dismatch",
-                                        version_current = current_version,
-                                        version_latest = latest_version
-                                    ))
-                                )
-                            }
-                            VerifyFailReason::UpdateButNoDescription => {
-                                eprintln!(
-                                    "{}",
-                                    md(t!(
-                                        "jv.result.track.update_failed.verify.update_but_no_description"
-                                    ))
-                                )
-                            }
-                            VerifyFailReason::VersionAlreadyExist(latest_version) => {
-                                eprintln!(
-                                    "{}",
-                                    md(t!(
-                                        "jv.result.track.update_failed.verify.version_already_exist",
-                                        path = path.display(),
-                                        version = latest_version
-                                    ))
-                                )
-                            }
-                        },
-                    },
-                    TrackFileActionResult::SyncTaskFailed(sync_task_result) => match sync_task_result {
-                        SyncTaskResult::Success(_) => {} // Success is not handled here
-                    },
-                },
-                Err(e) => handle_err(e),
+    let single = files.iter().next().filter(|_| files.len() == 1);
+    if let (Some(file), Some(desc), Some(ver)) = (single, &args.desc, &args.next_version) {
+        result.insert(file.clone(), (ver.clone(), desc.clone()));
+    } else if args.work {
+        let updatable = updatable
+            .iter()
+            .map(|(path, latest)| (path.clone(), latest.clone(), next_version(path, latest)))
+            .collect();
+        result = start_update_editor(&workspace, updatable, &args).await?;
+    } else if let (Some(desc), Some(_)) = (&args.desc, &args.bump) {
+        // Bump every modified file, by the scheme of its path
+        let mut not_bumpable = Vec::new();
+        for (path, latest) in &updatable {
+            match next_version(path, latest) {
+                Some(ver) => {
+                    result.insert(path.clone(), (ver, desc.clone()));
+                }
+                None => not_bumpable.push(format!("- {}: {}", path.display(), latest)),
             }
         }
-        _ = async {
-            while let Some(msg) = output.recv().await {
-                println!("{}", msg);
-            }
-        } => {}
+        if !not_bumpable.is_empty() {
+            not_bumpable.sort();
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.track.version_not_bumpable",
+                    files = not_bumpable.join("\n")
+                ))
+            );
+            return Err(CmdError::Usage);
+        }
     }
-}
 
-async fn get_update_info(
-    workspace: LocalWorkspace,
-    files: &HashSet<PathBuf>,
-    args: TrackFileArgs,
-) -> HashMap<PathBuf, (NextVersion, UpdateDescription)> {
-    let mut result = HashMap::new();
+    // The new versions must be greater than the latest ones
+    let mut not_greater: Vec<String> = updatable
+        .iter()
+        .filter_map(|(path, latest)| {
+            let (ver, _) = result.get(path)?;
+            (compare_versions(ver, latest) != std::cmp::Ordering::Greater)
+                .then(|| format!("- {}: {} <= {}", path.display(), ver, latest))
+        })
+        .collect();
+    if !not_greater.is_empty() {
+        not_greater.sort();
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.track.version_not_greater",
+                files = not_greater.join("\n")
+            ))
+        );
+        return Err(CmdError::Usage);
+    }
 
-    if files.len() == 1 {
-        if let (Some(desc), Some(ver)) = (&args.desc, &args.next_version) {
-            if let Some(file) = files.iter().next() {
-                result.insert(file.clone(), (ver.clone(), desc.clone()));
-                return result;
-            }
+    // Descriptions must follow the rules of the workspace
+    check_desc_rules(&result)?;
+
+    Ok(result)
+}
+
+/// Check the update descriptions against the description rules of the configuration
+fn check_desc_rules(update_info: &HashMap<PathBuf, (NextVersion, UpdateDescription)>) -> CmdResult {
+    let (rules, layer) = current_config().list(ConfigKey::DescRules);
+    let rules = match DescriptionRules::parse(&rules.join("\n")) {
+        Ok(rules) => rules,
+        Err((line, err)) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.track.invalid_desc_rule",
+                    layer = layer.name(),
+                    line = line,
+                    error = err
+                ))
+            );
+            return Err(CmdError::ConfigNotFound);
         }
-    }
-    if args.work {
-        return start_update_editor(workspace, files, &args).await;
+    };
+
+    let mut violations: Vec<String> = update_info
+        .iter()
+        .filter_map(|(path, (_, desc))| {
+            let rule = rules.first_violation(desc)?;
+            Some(format!("- {}: `{}`", path.display(), rule))
+        })
+        .collect();
+    if !violations.is_empty() {
+        violations.sort();
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.track.desc_rule_violated",
+                files = violations.join("\n")
+            ))
+        );
+        return Err(CmdError::Usage);
     }
 
-    result
+    Ok(())
 }
 
-async fn start_update_editor(
-    workspace: LocalWorkspace,
+/// Modified files that can be updated, with their latest versions
+async fn updatable_files(
+    workspace: &LocalWorkspace,
     files: &HashSet<PathBuf>,
-    args: &TrackFileArgs,
-) -> HashMap<PathBuf, (NextVersion, UpdateDescription)> {
+) -> Vec<(PathBuf, VirtualFileVersion)> {
     let account = workspace.config().lock().await.current_account();
 
     let Ok(latest_file_data_path) = LatestFileData::data_path(&account) else {
@@ -3939,7 +6503,7 @@ async fn start_update_editor(
                 account = &account
             ))
         );
-        return HashMap::new();
+        return Vec::new();
     };
 
     // Get latest file data
@@ -3951,30 +6515,30 @@ async fn start_update_editor(
                 account = &account
             ))
         );
-        return HashMap::new();
+        return Vec::new();
     };
 
     // Get files
-    let Ok(analyzed) = AnalyzeResult::analyze_local_status(&workspace).await else {
-        return HashMap::new();
+    let Ok(analyzed) = AnalyzeResult::analyze_local_status(workspace).await else {
+        return Vec::new();
     };
     // Has unsolved moves, skip
     if analyzed.lost.len() > 0 || analyzed.moved.len() > 0 {
-        return HashMap::new();
+        return Vec::new();
     }
     // No modified, skip
     if analyzed.modified.len() < 1 {
-        return HashMap::new();
+        return Vec::new();
     }
     // No sheet, skip
     let Some(sheet) = workspace.config().lock().await.sheet_in_use().clone() else {
-        return HashMap::new();
+        return Vec::new();
     };
     // No cached sheet, skip
     let Ok(cached_sheet) = CachedSheet::cached_sheet_data(&sheet).await else {
-        return HashMap::new();
+        return Vec::new();
     };
-    let files: Vec<(PathBuf, VirtualFileVersion)> = files
+    let mut files: Vec<(PathBuf, VirtualFileVersion)> = files
         .iter()
         .filter_map(|file| {
             if analyzed.modified.contains(file) {
@@ -3989,6 +6553,20 @@ async fn start_update_editor(
             }
         })
         .collect();
+    files.sort();
+    files
+}
+
+/// Let the user fill the versions and descriptions of the files in the editor,
+/// the versions are pre-filled with the suggested next versions
+async fn start_update_editor(
+    workspace: &LocalWorkspace,
+    files: Vec<(PathBuf, VirtualFileVersion, Option<NextVersion>)>,
+    args: &TrackFileArgs,
+) -> Result<HashMap<PathBuf, (NextVersion, UpdateDescription)>, CmdError> {
+    if files.is_empty() {
+        return Ok(HashMap::new());
+    }
 
     // Generate editor text
     let mut table = SimpleTable::new_with_padding(
@@ -4000,10 +6578,9 @@ async fn start_update_editor(
         ],
         2,
     );
-    for item in files {
-        let path = item.0.display().to_string();
-        let base_ver = item.1.to_string();
-        let next_ver = push_version(&base_ver).unwrap_or(" ".to_string());
+    for (path, base_ver, next_ver) in files {
+        let path = path.display().to_string();
+        let next_ver = next_ver.unwrap_or(" ".to_string());
         table.push_item(vec![
             path,
             base_ver,
@@ -4013,10 +6590,16 @@ async fn start_update_editor(
     }
     let lines = table.to_string();
 
+    // Pre-fill the description with the template of the configuration
+    let description = match &args.desc {
+        Some(desc) => desc.clone(),
+        None => config_value(ConfigKey::DescTemplate).trim_end().to_string(),
+    };
+
     let str = t!(
         "editor.update_editor",
         modified_lines = lines,
-        description = args.desc.clone().unwrap_or_default()
+        description = description
     );
 
     let path = workspace
@@ -4029,110 +6612,168 @@ async fn start_update_editor(
 
     // Parse the result returned from the editor
     let lines: Vec<&str> = result.lines().collect();
-    let mut i = 0;
 
     // Find the separator line
-    let mut separator_index = None;
-    while i < lines.len() {
-        let line = lines[i].trim();
-        if line.chars().all(|c| c == '-') && line.len() >= 5 {
-            separator_index = Some(i);
-            break;
-        }
-        i += 1;
-    }
-
-    if let Some(sep_idx) = separator_index {
-        // Parse path and version information before the separator
-        for line in &lines[..sep_idx] {
-            let trimmed_line = line.trim();
-            if trimmed_line.is_empty() {
-                continue;
-            }
-
-            // Parse format: /directory/file.extension version -> new_version
-            if let Some(arrow_pos) = trimmed_line.find("->") {
-                let before_arrow = &trimmed_line[..arrow_pos].trim();
-                let after_arrow = &trimmed_line[arrow_pos + 2..].trim();
+    let Some(sep_idx) = lines.iter().position(|line| {
+        let line = line.trim();
+        line.chars().all(|c| c == '-') && line.len() >= 5
+    }) else {
+        return Ok(update_info);
+    };
+    let descriptions = UpdateDescriptions::parse(&lines[sep_idx + 1..].join("\n"));
 
-                // Separate path and old version
-                if let Some(last_space) = before_arrow.rfind(' ') {
-                    let path_str = &before_arrow[..last_space].trim();
-                    let _old_version = &before_arrow[last_space + 1..].trim(); // Old version, needs parsing but not used
-                    let new_version = after_arrow.trim();
+    // Parse path and version information before the separator
+    let mut missing = Vec::new();
+    for line in &lines[..sep_idx] {
+        // Parse format: /directory/file.extension version -> new_version
+        let Some((before_arrow, new_version)) = line.trim().split_once("->") else {
+            continue;
+        };
 
-                    if !path_str.is_empty() && !new_version.is_empty() {
-                        let path = PathBuf::from(path_str);
-                        // Get description (all content after the separator)
-                        let description = lines[sep_idx + 1..].join("\n").trim().to_string();
+        // Separate path and old version
+        let Some((path_str, _old_version)) = before_arrow.trim().rsplit_once(' ') else {
+            continue;
+        };
+        let (path_str, new_version) = (path_str.trim(), new_version.trim());
+        if path_str.is_empty() || new_version.is_empty() {
+            continue;
+        }
 
-                        // Only add to update_info if description is not empty after trimming
-                        if !description.is_empty() {
-                            update_info.insert(path, (new_version.to_string(), description));
-                        }
-                    }
-                }
+        // Use the description of the file's section, or the shared one
+        match descriptions.description_of(path_str) {
+            Some(description) => {
+                update_info.insert(
+                    PathBuf::from(path_str),
+                    (new_version.to_string(), description.to_string()),
+                );
             }
+            None => missing.push(path_str.to_string()),
         }
     }
 
-    update_info
+    let paths: Vec<String> = update_info
+        .keys()
+        .map(|path| path.display().to_string())
+        .chain(missing.iter().cloned())
+        .collect();
+    let paths: Vec<&str> = paths.iter().map(|path| path.as_str()).collect();
+    let unused = descriptions.unused_patterns(&paths);
+    if !unused.is_empty() {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.track.unknown_description_section",
+                patterns = unused
+                    .iter()
+                    .map(|pattern| format!("[{}]", pattern))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        );
+        return Err(CmdError::Usage);
+    }
+
+    // Every updated file needs a description, instead of being dropped silently
+    if !missing.is_empty() {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.track.missing_description",
+                files = missing
+                    .iter()
+                    .map(|file| format!("- {}", file))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ))
+        );
+        return Err(CmdError::Usage);
+    }
+
+    Ok(update_info)
 }
 
-async fn jv_hold(args: HoldFileArgs) {
+async fn jv_hold(args: HoldFileArgs) -> CmdResult {
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
-    let Some(hold_file_pattern) = args.hold_file_pattern else {
+    if args.hold_file_patterns.is_empty() && args.from_file.is_none() {
         println!("{}", md(t!("jv.hold")));
-        return;
-    };
+        return Err(CmdError::Usage);
+    }
 
-    let files = glob(hold_file_pattern, &local_dir).await;
+    let files = collect_files(
+        &args.hold_file_patterns,
+        &args.exclude,
+        args.from_file.as_deref(),
+        args.ignore_case,
+        &local_dir,
+    )
+    .await?;
 
     let _ = correct_current_dir();
 
-    jv_change_edit_right(
-        files
-            .iter()
-            .filter_map(|f| PathBuf::from_str(f.0).ok())
-            .collect(),
+    let files: Vec<PathBuf> = files
+        .iter()
+        .filter_map(|f| PathBuf::from_str(f.0).ok())
+        .collect();
+
+    run_pre_hook("hold", &files).await?;
+    let result = jv_change_edit_right(
+        files.clone(),
         EditRightChangeBehaviour::Hold,
         args.show_fail_details,
         args.skip_failed,
         args.force,
+        args.json_output,
+        args.pretty,
     )
     .await;
+    run_post_hook("hold", &files, &result).await;
+    result
 }
 
-async fn jv_throw(args: ThrowFileArgs) {
+async fn jv_throw(args: ThrowFileArgs) -> CmdResult {
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
-    let Some(throw_file_pattern) = args.throw_file_pattern else {
+    if args.throw_file_patterns.is_empty() && args.from_file.is_none() {
         println!("{}", md(t!("jv.throw")));
-        return;
-    };
+        return Err(CmdError::Usage);
+    }
 
-    let files = glob(throw_file_pattern, &local_dir).await;
+    let files = collect_files(
+        &args.throw_file_patterns,
+        &args.exclude,
+        args.from_file.as_deref(),
+        args.ignore_case,
+        &local_dir,
+    )
+    .await?;
 
     let _ = correct_current_dir();
 
-    jv_change_edit_right(
-        files
-            .iter()
-            .filter_map(|f| PathBuf::from_str(f.0).ok())
-            .collect(),
+    let files: Vec<PathBuf> = files
+        .iter()
+        .filter_map(|f| PathBuf::from_str(f.0).ok())
+        .collect();
+
+    run_pre_hook("throw", &files).await?;
+    let result = jv_change_edit_right(
+        files.clone(),
         EditRightChangeBehaviour::Throw,
         args.show_fail_details,
         args.skip_failed,
         args.force,
+        args.json_output,
+        args.pretty,
     )
     .await;
+    run_post_hook("throw", &files, &result).await;
+    result
 }
 
 async fn jv_change_edit_right(
@@ -4141,7 +6782,9 @@ async fn jv_change_edit_right(
     show_fail_details: bool,
     mut skip_failed: bool,
     force: bool,
-) {
+    json_output: bool,
+    pretty: bool,
+) -> CmdResult {
     // If both `--details` and `--skip-failed` are set, only enable `--details`
     if show_fail_details && skip_failed {
         skip_failed = false;
@@ -4149,23 +6792,23 @@ async fn jv_change_edit_right(
 
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(local_cfg) = LocalConfig::read_from(local_dir.join(CLIENT_FILE_WORKSPACE)).await else {
         eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Some(local_workspace) = LocalWorkspace::init_current_dir(local_cfg.clone()) else {
         eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     // Get files
     let Ok(analyzed) = AnalyzeResult::analyze_local_status(&local_workspace).await else {
         eprintln!("{}", md(t!("jv.fail.status.analyze")).trim());
-        return;
+        return Err(CmdError::Failed);
     };
 
     let account = local_cfg.current_account();
@@ -4178,7 +6821,7 @@ async fn jv_change_edit_right(
                 account = &account
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Ok(latest_file_data) = LatestFileData::read_from(&latest_file_data_path).await else {
@@ -4189,12 +6832,12 @@ async fn jv_change_edit_right(
                 account = &account
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
         eprintln!("{}", md(t!("jv.fail.status.no_sheet_in_use")).trim());
-        return;
+        return Err(CmdError::NoSheetInUse);
     };
 
     let Ok(local_sheet) = local_workspace.local_sheet(&account, &sheet_name).await else {
@@ -4206,7 +6849,7 @@ async fn jv_change_edit_right(
                 sheet = &sheet_name
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let Ok(cached_sheet) = CachedSheet::cached_sheet_data(&sheet_name).await else {
@@ -4217,87 +6860,26 @@ async fn jv_change_edit_right(
                 sheet = &sheet_name
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let num = files.iter().len();
     if num < 1 {
         eprintln!("{}", md(t!("jv.fail.change_edit_right.no_selection")));
-        return;
-    }
-
-    let mut passed_files = Vec::new();
-    let mut details = Vec::new();
-    let mut failed = 0;
-
-    // Helper function to handle validation failures
-    fn handle_validation_failure(
-        show_fail_details: bool,
-        details: &mut Vec<String>,
-        failed: &mut usize,
-        num: usize,
-        reason: String,
-    ) -> bool {
-        if show_fail_details {
-            details.push(reason);
-            *failed += 1;
-            true // Continue to next file
-        } else {
-            eprintln!(
-                "{}",
-                md(t!("jv.fail.change_edit_right.check_failed", num = num))
-            );
-            false // Break processing
-        }
+        return Err(CmdError::NotFound);
     }
 
-    for file in files {
-        let exists = file.exists();
-
-        // If force is enabled, add to the list regardless
-        if force {
-            passed_files.push(file);
-            continue;
-        }
-
+    // Check whether the edit right of the file can be changed
+    let check = |file: &PathBuf| -> Result<(), EditRightFailReason> {
         // Mapping exists
-        let Some(cached_mapping) = cached_sheet.mapping().get(&file) else {
-            let reason = t!(
-                "jv.fail.change_edit_right.check_fail_item",
-                path = file.display(),
-                reason = t!("jv.fail.change_edit_right.check_fail_reason.not_found_in_sheet")
-            )
-            .trim()
-            .to_string();
-
-            if !handle_validation_failure(show_fail_details, &mut details, &mut failed, num, reason)
-            {
-                return;
-            }
-            continue;
+        let Some(cached_mapping) = cached_sheet.mapping().get(file) else {
+            return Err(EditRightFailReason::NotFoundInSheet);
         };
 
-        let vfid: VirtualFileId = if exists {
+        let vfid: VirtualFileId = if file.exists() {
             // Not tracked
-            let Ok(local_mapping) = local_sheet.mapping_data(&file) else {
-                let reason = t!(
-                    "jv.fail.change_edit_right.check_fail_item",
-                    path = file.display(),
-                    reason = t!("jv.fail.change_edit_right.check_fail_reason.not_a_tracked_file")
-                )
-                .trim()
-                .to_string();
-
-                if !handle_validation_failure(
-                    show_fail_details,
-                    &mut details,
-                    &mut failed,
-                    num,
-                    reason,
-                ) {
-                    return;
-                }
-                continue;
+            let Ok(local_mapping) = local_sheet.mapping_data(file) else {
+                return Err(EditRightFailReason::NotATrackedFile);
             };
 
             let vfid = local_mapping.mapping_vfid();
@@ -4309,24 +6891,7 @@ async fn jv_change_edit_right(
                     .file_version(vfid)
                     .unwrap_or(&String::default())
             {
-                let reason = t!(
-                    "jv.fail.change_edit_right.check_fail_item",
-                    path = file.display(),
-                    reason = t!("jv.fail.change_edit_right.check_fail_reason.base_version_unmatch")
-                )
-                .trim()
-                .to_string();
-
-                if !handle_validation_failure(
-                    show_fail_details,
-                    &mut details,
-                    &mut failed,
-                    num,
-                    reason,
-                ) {
-                    return;
-                }
-                continue;
+                return Err(EditRightFailReason::BaseVersionUnmatch);
             }
 
             vfid.clone()
@@ -4336,129 +6901,95 @@ async fn jv_change_edit_right(
 
         // Hold validation
         let holder = latest_file_data.file_holder(&vfid);
-        let validation_passed = match behaviour {
-            EditRightChangeBehaviour::Hold => {
-                if holder.is_some_and(|h| h != &account) {
-                    // Has holder but not current account
-                    let holder_name = holder.unwrap();
-                    let reason = t!(
-                        "jv.fail.change_edit_right.check_fail_item",
-                        path = file.display(),
-                        reason = t!(
-                            "jv.fail.change_edit_right.check_fail_reason.has_holder",
-                            holder = holder_name
-                        )
-                    )
-                    .trim()
-                    .to_string();
-
-                    if !handle_validation_failure(
-                        show_fail_details,
-                        &mut details,
-                        &mut failed,
-                        num,
-                        reason,
-                    ) {
-                        return;
-                    }
-                    false
-                } else if holder.is_some_and(|h| h == &account) {
-                    // Already held by current account
-                    let reason = t!(
-                        "jv.fail.change_edit_right.check_fail_item",
-                        path = file.display(),
-                        reason = t!("jv.fail.change_edit_right.check_fail_reason.already_held")
-                    )
-                    .trim()
-                    .to_string();
-
-                    if !handle_validation_failure(
-                        show_fail_details,
-                        &mut details,
-                        &mut failed,
-                        num,
-                        reason,
-                    ) {
-                        return;
-                    }
-                    false
-                } else {
-                    true
-                }
-            }
+        match behaviour {
+            EditRightChangeBehaviour::Hold => match holder {
+                // Has holder but not current account
+                Some(h) if h != &account => Err(EditRightFailReason::HasHolder(h.clone())),
+                // Already held by current account
+                Some(_) => Err(EditRightFailReason::AlreadyHeld),
+                None => Ok(()),
+            },
             EditRightChangeBehaviour::Throw => {
                 if holder.is_some_and(|h| h != &account) {
                     // Not the holder
-                    let reason = t!(
-                        "jv.fail.change_edit_right.check_fail_item",
-                        path = file.display(),
-                        reason = t!("jv.fail.change_edit_right.check_fail_reason.not_holder")
-                    )
-                    .trim()
-                    .to_string();
-
-                    if !handle_validation_failure(
-                        show_fail_details,
-                        &mut details,
-                        &mut failed,
-                        num,
-                        reason,
-                    ) {
-                        return;
-                    }
-                    false
-                } else if analyzed.modified.contains(&file) {
+                    Err(EditRightFailReason::NotHolder)
+                } else if analyzed.modified.contains(file) {
                     // Already modified
-                    let reason = t!(
-                        "jv.fail.change_edit_right.check_fail_item",
-                        path = file.display(),
-                        reason = t!("jv.fail.change_edit_right.check_fail_reason.already_modified")
-                    )
-                    .trim()
-                    .to_string();
-
-                    if !handle_validation_failure(
-                        show_fail_details,
-                        &mut details,
-                        &mut failed,
-                        num,
-                        reason,
-                    ) {
-                        return;
-                    }
-                    false
+                    Err(EditRightFailReason::AlreadyModified)
                 } else {
-                    true
+                    Ok(())
                 }
             }
-        };
+        }
+    };
+
+    // Failed items are collected when they need to be reported or skipped,
+    // otherwise the first failure stops the operation
+    let collect_failed = show_fail_details || skip_failed || json_output;
+
+    let mut passed_files = Vec::new();
+    let mut failed_items = Vec::new();
 
-        if validation_passed {
+    for file in files {
+        // If force is enabled, add to the list regardless
+        if force {
             passed_files.push(file);
+            continue;
         }
-    }
 
-    if failed > 0 && show_fail_details {
-        eprintln!(
-            "{}",
-            md(t!(
-                "jv.fail.change_edit_right.check_failed_details",
-                num = num,
-                failed = failed,
-                items = details.join("\n").trim().yellow()
-            ))
-        );
-        return;
+        match check(&file) {
+            Ok(_) => passed_files.push(file),
+            Err(reason) => {
+                if !collect_failed {
+                    eprintln!(
+                        "{}",
+                        md(t!("jv.fail.change_edit_right.check_failed", num = num))
+                    );
+                    return Err(CmdError::Rejected);
+                }
+                failed_items.push(EditRightFailedItem { path: file, reason });
+            }
+        }
     }
 
-    if !(failed > 0 && skip_failed) && failed != 0 {
-        return;
+    let failed = failed_items.len();
+    if failed > 0 && !skip_failed {
+        if json_output {
+            print_json(
+                EditRightJsonResult {
+                    result: EditRightResult::CheckFailed,
+                    failed: failed_items,
+                    ..Default::default()
+                },
+                pretty,
+            );
+        } else if show_fail_details {
+            let details = failed_items
+                .iter()
+                .map(|item| {
+                    t!(
+                        "jv.fail.change_edit_right.check_fail_item",
+                        path = item.path.display(),
+                        reason = edit_right_fail_reason_str(&item.reason)
+                    )
+                    .trim()
+                    .to_string()
+                })
+                .collect::<Vec<String>>();
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.change_edit_right.check_failed_details",
+                    num = num,
+                    failed = failed,
+                    items = details.join("\n").trim().yellow()
+                ))
+            );
+        }
+        return Err(CmdError::Rejected);
     }
 
-    let (pool, ctx, _output) = match build_pool_and_ctx(&local_cfg).await {
-        Some(result) => result,
-        None => return,
-    };
+    let (pool, ctx, _output) = build_pool_and_ctx(&local_cfg).await?;
 
     let passed = passed_files
         .iter()
@@ -4471,7 +7002,23 @@ async fn jv_change_edit_right(
                 success_hold,
                 success_throw,
             } => {
-                if success_hold.len() > 0 && success_throw.len() == 0 {
+                let do_nothing = success_hold.is_empty() && success_throw.is_empty();
+
+                if json_output {
+                    print_json(
+                        EditRightJsonResult {
+                            result: if do_nothing {
+                                EditRightResult::DoNothing
+                            } else {
+                                EditRightResult::Success
+                            },
+                            held: success_hold,
+                            thrown: success_throw,
+                            failed: failed_items,
+                        },
+                        pretty,
+                    );
+                } else if success_hold.len() > 0 && success_throw.len() == 0 {
                     println!(
                         "{}",
                         md(t!(
@@ -4498,37 +7045,106 @@ async fn jv_change_edit_right(
                         ))
                     )
                 } else {
-                    eprintln!("{}", md(t!("jv.result.change_edit_right.failed.none")))
+                    eprintln!("{}", md(t!("jv.result.change_edit_right.failed.none")));
+                }
+
+                if do_nothing {
+                    return Err(CmdError::Rejected);
                 }
+
+                // Some files failed the check but were skipped
+                if failed > 0 {
+                    return Err(CmdError::PartialFailure);
+                }
+                Ok(())
             }
             ChangeVirtualFileEditRightResult::AuthorizeFailed(e) => {
-                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)))
+                if json_output {
+                    print_json(
+                        EditRightJsonResult {
+                            result: EditRightResult::AuthorizeFailed(e.to_string()),
+                            failed: failed_items,
+                            ..Default::default()
+                        },
+                        pretty,
+                    );
+                } else {
+                    eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
+                }
+                Err(CmdError::AuthorizeFailed)
             }
             ChangeVirtualFileEditRightResult::DoNothing => {
-                eprintln!("{}", md(t!("jv.result.change_edit_right.failed.none")))
+                if json_output {
+                    print_json(
+                        EditRightJsonResult {
+                            result: EditRightResult::DoNothing,
+                            failed: failed_items,
+                            ..Default::default()
+                        },
+                        pretty,
+                    );
+                } else {
+                    eprintln!("{}", md(t!("jv.result.change_edit_right.failed.none")));
+                }
+                Err(CmdError::Rejected)
             }
         },
-        Err(e) => handle_err(e),
+        Err(e) => Err(handle_err(e)),
     }
 }
 
-async fn jv_move(args: MoveMappingArgs) {
+fn edit_right_fail_reason_str(reason: &EditRightFailReason) -> String {
+    match reason {
+        EditRightFailReason::NotFoundInSheet => {
+            t!("jv.fail.change_edit_right.check_fail_reason.not_found_in_sheet")
+        }
+        EditRightFailReason::NotATrackedFile => {
+            t!("jv.fail.change_edit_right.check_fail_reason.not_a_tracked_file")
+        }
+        EditRightFailReason::BaseVersionUnmatch => {
+            t!("jv.fail.change_edit_right.check_fail_reason.base_version_unmatch")
+        }
+        EditRightFailReason::HasHolder(holder) => t!(
+            "jv.fail.change_edit_right.check_fail_reason.has_holder",
+            holder = holder
+        ),
+        EditRightFailReason::AlreadyHeld => {
+            t!("jv.fail.change_edit_right.check_fail_reason.already_held")
+        }
+        EditRightFailReason::NotHolder => {
+            t!("jv.fail.change_edit_right.check_fail_reason.not_holder")
+        }
+        EditRightFailReason::AlreadyModified => {
+            t!("jv.fail.change_edit_right.check_fail_reason.already_modified")
+        }
+    }
+    .to_string()
+}
+
+async fn jv_move(args: MoveMappingArgs) -> CmdResult {
     let local_dir = match current_local_path() {
         Some(dir) => dir,
         None => {
             eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-            return;
+            return Err(CmdError::NotWorkspace);
         }
     };
 
     let move_files = if let Some(from_pattern) = args.move_mapping_pattern.clone() {
-        let from = glob(from_pattern, &local_dir).await;
+        let from = collect_files(
+            &[from_pattern],
+            &args.exclude,
+            None,
+            args.ignore_case,
+            &local_dir,
+        )
+        .await?;
         from.iter()
             .filter_map(|f| PathBuf::from_str(f.0).ok())
             .collect::<Vec<_>>()
     } else {
         println!("{}", md(t!("jv.move")));
-        return;
+        return Err(CmdError::Usage);
     };
 
     let to_pattern = if args.to_mapping_pattern.is_some() {
@@ -4538,7 +7154,7 @@ async fn jv_move(args: MoveMappingArgs) {
             "".to_string()
         } else {
             eprintln!("{}", md(t!("jv.fail.move.no_target_dir")));
-            return;
+            return Err(CmdError::Usage);
         }
     };
 
@@ -4611,7 +7227,7 @@ async fn jv_move(args: MoveMappingArgs) {
         }
         if from_mappings.len() > 1 && !is_to_pattern_a_dir {
             eprintln!("{}", md(t!("jv.fail.move.count_doesnt_match")));
-            return;
+            return Err(CmdError::Usage);
         }
 
         // NOTE
@@ -4620,44 +7236,97 @@ async fn jv_move(args: MoveMappingArgs) {
         // }
     }
 
-    let local_cfg = match precheck().await {
-        Some(config) => config,
-        None => return,
-    };
+    let local_cfg = precheck().await?;
+
+    if args.dry_run {
+        let erase_dir = PathBuf::from(CLIENT_FOLDER_WORKSPACE_ROOT_NAME)
+            .join(".temp")
+            .join("erased");
+
+        let mut sorted_operations = edit_mapping_args.operations.iter().collect::<Vec<_>>();
+        sorted_operations.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut operations = Vec::new();
+        let mut local_operations = Vec::new();
+        for (from, (operation, to)) in sorted_operations {
+            let to = match operation {
+                EditMappingOperations::Move => {
+                    let to = to.clone().unwrap_or_default();
+                    operations.push(DryRunOperation::MoveMapping {
+                        from: from.clone(),
+                        to: to.clone(),
+                    });
+                    to
+                }
+                EditMappingOperations::Erase => {
+                    operations.push(DryRunOperation::EraseMapping {
+                        mapping: from.clone(),
+                    });
+                    erase_dir.join(from)
+                }
+            };
+            if !args.only_remote && local_dir.join(from).exists() {
+                local_operations.push(DryRunOperation::MoveLocal {
+                    from: from.clone(),
+                    to,
+                });
+            }
+        }
+        operations.extend(local_operations);
+
+        print_dry_run(operations, args.json_output, args.pretty);
+        return Ok(());
+    }
+
+    let (pool, ctx, _output) = build_pool_and_ctx(&local_cfg).await?;
 
-    let (pool, ctx, _output) = match build_pool_and_ctx(&local_cfg).await {
-        Some(result) => result,
-        None => return,
+    let mut json_result = MappingEditJsonResult {
+        operations: edit_mapping_args
+            .operations
+            .iter()
+            .map(|(from, (operation, to))| MappingEditItem {
+                from: from.clone(),
+                to: to.clone(),
+                erase: matches!(operation, EditMappingOperations::Erase),
+            })
+            .collect(),
+        ..Default::default()
     };
 
-    if proc_mapping_edit(&pool, ctx, edit_mapping_args.clone())
-        .await
-        .is_ok()
-    {
-        // If the operation succeeds and only_remote is not enabled,
-        // synchronize local moves
-        if !args.only_remote {
-            let erase_dir = local_dir
-                .join(CLIENT_FOLDER_WORKSPACE_ROOT_NAME)
-                .join(".temp")
-                .join("erased");
-
-            let mut skipped = 0;
-            for (from_relative, (operation, to_relative)) in edit_mapping_args.operations {
-                let from = local_dir.join(&from_relative);
-
-                if !from.exists() {
-                    continue;
-                }
+    if args.json_output {
+        json_result.result = request_mapping_edit(&pool, ctx, edit_mapping_args.clone()).await?;
+        if let Err(err) = mapping_edit_cmd_result(&json_result.result) {
+            print_json(json_result, args.pretty);
+            return Err(err);
+        }
+    } else {
+        proc_mapping_edit(&pool, ctx, edit_mapping_args.clone()).await?;
+    }
 
-                let to = match operation {
-                    EditMappingOperations::Move => local_dir.join(to_relative.unwrap()),
-                    EditMappingOperations::Erase => erase_dir.join(&from_relative),
-                };
-                if let Some(to_dir) = to.parent() {
-                    let _ = fs::create_dir_all(to_dir).await;
-                }
-                if let Some(e) = fs::rename(&from, &to).await.err() {
+    // If the operation succeeds and only_remote is not enabled,
+    // synchronize local moves
+    if !args.only_remote {
+        let erase_dir = local_dir
+            .join(CLIENT_FOLDER_WORKSPACE_ROOT_NAME)
+            .join(".temp")
+            .join("erased");
+
+        for (from_relative, (operation, to_relative)) in edit_mapping_args.operations {
+            let from = local_dir.join(&from_relative);
+
+            if !from.exists() {
+                continue;
+            }
+
+            let to = match operation {
+                EditMappingOperations::Move => local_dir.join(to_relative.unwrap()),
+                EditMappingOperations::Erase => erase_dir.join(&from_relative),
+            };
+            if let Some(to_dir) = to.parent() {
+                let _ = fs::create_dir_all(to_dir).await;
+            }
+            if let Some(e) = fs::rename(&from, &to).await.err() {
+                if !args.json_output {
                     eprintln!(
                         "{}",
                         md(t!(
@@ -4668,21 +7337,84 @@ async fn jv_move(args: MoveMappingArgs) {
                         ))
                         .yellow()
                     );
-                    skipped += 1;
                 }
-            }
-            if skipped > 0 {
-                eprintln!("{}", md(t!("jv.fail.move.has_rename_failed")));
+                json_result.local_rename_failed.push(from_relative);
             }
         }
     }
+
+    let has_rename_failed = !json_result.local_rename_failed.is_empty();
+    if args.json_output {
+        print_json(json_result, args.pretty);
+    } else if has_rename_failed {
+        eprintln!("{}", md(t!("jv.fail.move.has_rename_failed")));
+    }
+
+    if has_rename_failed {
+        return Err(CmdError::PartialFailure);
+    }
+    Ok(())
 }
 
 async fn proc_mapping_edit(
     pool: &ActionPool,
     ctx: ActionContext,
     edit_mapping_args: EditMappingActionArguments,
-) -> Result<(), ()> {
+) -> CmdResult {
+    let result = request_mapping_edit(pool, ctx, edit_mapping_args).await?;
+    match &result {
+        MappingEditResult::Success => {
+            println!("{}", md(t!("jv.result.move.success")));
+        }
+        MappingEditResult::AuthorizeFailed(e) => {
+            eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
+        }
+        MappingEditResult::MappingNotFound(path_buf) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.result.move.mapping_not_found",
+                    path = path_buf.display()
+                ))
+            );
+        }
+        MappingEditResult::MoveOperationButNoTarget(path_buf) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.result.move.invalid_move.no_target",
+                    path = path_buf.display()
+                ))
+            );
+        }
+        MappingEditResult::ContainsDuplicateMapping(path_buf) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.result.move.invalid_move.duplicate_mapping",
+                    path = path_buf.display()
+                ))
+            );
+        }
+        MappingEditResult::Unknown => {
+            eprintln!("{}", md(t!("jv.result.move.unknown")));
+        }
+        MappingEditResult::EditNotAllowed => {
+            eprintln!(
+                "{}",
+                md(t!("jv.result.common.not_allowed_in_reference_sheet"))
+            );
+        }
+    }
+    mapping_edit_cmd_result(&result)
+}
+
+/// Send the mapping edit request to the upstream vault, without printing the result
+async fn request_mapping_edit(
+    pool: &ActionPool,
+    ctx: ActionContext,
+    edit_mapping_args: EditMappingActionArguments,
+) -> Result<MappingEditResult, CmdError> {
     match proc_edit_mapping_action(
         pool,
         ctx,
@@ -4692,130 +7424,126 @@ async fn proc_mapping_edit(
     )
     .await
     {
-        Ok(r) => match r {
-            EditMappingActionResult::Success => {
-                println!("{}", md(t!("jv.result.move.success")));
-                Ok(())
-            }
+        Ok(r) => Ok(match r {
+            EditMappingActionResult::Success => MappingEditResult::Success,
             EditMappingActionResult::AuthorizeFailed(e) => {
-                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
-                Err(())
+                MappingEditResult::AuthorizeFailed(e.to_string())
             }
             EditMappingActionResult::MappingNotFound(path_buf) => {
-                eprintln!(
-                    "{}",
-                    md(t!(
-                        "jv.result.move.mapping_not_found",
-                        path = path_buf.display()
-                    ))
-                );
-                Err(())
+                MappingEditResult::MappingNotFound(path_buf)
             }
             EditMappingActionResult::InvalidMove(invalid_move_reason) => {
                 match invalid_move_reason {
                     InvalidMoveReason::MoveOperationButNoTarget(path_buf) => {
-                        eprintln!(
-                            "{}",
-                            md(t!(
-                                "jv.result.move.invalid_move.no_target",
-                                path = path_buf.display()
-                            ))
-                        );
+                        MappingEditResult::MoveOperationButNoTarget(path_buf)
                     }
                     InvalidMoveReason::ContainsDuplicateMapping(path_buf) => {
-                        eprintln!(
-                            "{}",
-                            md(t!(
-                                "jv.result.move.invalid_move.duplicate_mapping",
-                                path = path_buf.display()
-                            ))
-                        );
+                        MappingEditResult::ContainsDuplicateMapping(path_buf)
                     }
                 }
-                Err(())
             }
-            EditMappingActionResult::Unknown => {
-                eprintln!("{}", md(t!("jv.result.move.unknown")));
-                Err(())
+            EditMappingActionResult::Unknown => MappingEditResult::Unknown,
+            EditMappingActionResult::EditNotAllowed => MappingEditResult::EditNotAllowed,
+        }),
+        Err(e) => Err(handle_err(e)),
+    }
+}
+
+fn mapping_edit_cmd_result(result: &MappingEditResult) -> CmdResult {
+    match result {
+        MappingEditResult::Success => Ok(()),
+        MappingEditResult::AuthorizeFailed(_) => Err(CmdError::AuthorizeFailed),
+        MappingEditResult::MappingNotFound(_) => Err(CmdError::NotFound),
+        MappingEditResult::MoveOperationButNoTarget(_)
+        | MappingEditResult::ContainsDuplicateMapping(_) => Err(CmdError::Usage),
+        MappingEditResult::Unknown => Err(CmdError::Failed),
+        MappingEditResult::EditNotAllowed => Err(CmdError::Rejected),
+    }
+}
+
+async fn jv_share(args: ShareMappingArgs) -> CmdResult {
+    // Share the listed files: jv share --from-file <PATH> <SHEET> <DESCRIPTION>
+    if args.from_file.is_some() {
+        return match (&args.args1, &args.args2, &args.args3) {
+            (Some(to_sheet), Some(description), None) => {
+                share_out(
+                    String::default(),
+                    to_sheet.to_string(),
+                    description.to_string(),
+                    args,
+                )
+                .await
+            }
+            (Some(to_sheet), None, None) if args.work => {
+                share_out(
+                    String::default(),
+                    to_sheet.to_string(),
+                    String::default(),
+                    args,
+                )
+                .await
             }
-            EditMappingActionResult::EditNotAllowed => {
-                eprintln!(
-                    "{}",
-                    md(t!("jv.result.common.not_allowed_in_reference_sheet"))
-                );
-                Err(())
+            _ => {
+                println!("{}", md(t!("jv.share")));
+                Err(CmdError::Usage)
             }
-        },
-        Err(e) => {
-            handle_err(e);
-            Err(())
-        }
+        };
     }
-}
 
-async fn jv_share(args: ShareMappingArgs) {
     if let (Some(args1), None, None) = (&args.args1, &args.args2, &args.args3) {
         // List mode
         if args1.trim() == "list" || args1.trim() == "ls" {
-            share_list(args).await;
-            return;
+            return share_list(args).await;
         }
 
-        share_accept(args1.to_string(), args).await;
-        return;
+        return share_accept(args1.to_string(), args).await;
     }
 
     if let (Some(args1), Some(args2), None) = (&args.args1, &args.args2, &args.args3) {
         // 如果是 work 模式，那么就是分享
         if args.work {
-            share_out(
+            return share_out(
                 args1.to_string(),
                 args2.to_string(),
                 String::default(),
                 args,
             )
             .await;
-            return;
         }
 
         // See mode
         if args1.trim() == "see" {
-            share_see(args2.to_string(), args).await;
-            return;
+            return share_see(args2.to_string(), args).await;
         }
 
-        share_in(args1.to_string(), args2.to_string(), args).await;
-        return;
+        return share_in(args1.to_string(), args2.to_string(), args).await;
     }
 
     if let (Some(share_pattern), Some(to_sheet), Some(description)) =
         (&args.args1, &args.args2, &args.args3)
     {
-        share_out(
+        return share_out(
             share_pattern.to_string(),
             to_sheet.to_string(),
             description.to_string(),
             args,
         )
         .await;
-        return;
     }
 
     println!("{}", md(t!("jv.share")));
+    Err(CmdError::Usage)
 }
 
-async fn share_list(args: ShareMappingArgs) {
+async fn share_list(args: ShareMappingArgs) -> CmdResult {
     let _ = correct_current_dir();
 
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
-    let local_config = match precheck().await {
-        Some(config) => config,
-        None => return,
-    };
+    let local_config = precheck().await?;
 
     let sheet_name = local_config.sheet_in_use().clone().unwrap_or_default();
 
@@ -4832,7 +7560,7 @@ async fn share_list(args: ShareMappingArgs) {
                 account = &local_config.current_account()
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     if let Some(shares) = latest_info.shares_in_my_sheets.get(&sheet_name) {
@@ -4854,7 +7582,7 @@ async fn share_list(args: ShareMappingArgs) {
                 .collect();
             let result = ShareListResult { share_list };
             print_json(result, args.pretty);
-            return;
+            return Ok(());
         }
 
         if !args.raw {
@@ -4886,20 +7614,19 @@ async fn share_list(args: ShareMappingArgs) {
                 .for_each(|share| println!("{}", share.0));
         }
     }
+
+    Ok(())
 }
 
-async fn share_see(share_id: String, args: ShareMappingArgs) {
+async fn share_see(share_id: String, args: ShareMappingArgs) -> CmdResult {
     let _ = correct_current_dir();
 
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
-    let local_config = match precheck().await {
-        Some(config) => config,
-        None => return,
-    };
+    let local_config = precheck().await?;
 
     let sheet_name = local_config.sheet_in_use().clone().unwrap_or_default();
 
@@ -4916,7 +7643,7 @@ async fn share_see(share_id: String, args: ShareMappingArgs) {
                 account = &local_config.current_account()
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     if let Some(shares) = latest_info.shares_in_my_sheets.get(&sheet_name) {
@@ -4929,7 +7656,7 @@ async fn share_see(share_id: String, args: ShareMappingArgs) {
                     mappings: share.mappings.clone(),
                 };
                 print_json(result, args.pretty);
-                return;
+                return Ok(());
             }
 
             println!(
@@ -4942,22 +7669,22 @@ async fn share_see(share_id: String, args: ShareMappingArgs) {
                     mappings = render_share_path_tree(&share.mappings)
                 ))
             );
+            return Ok(());
         }
     }
+
+    Err(CmdError::NotFound)
 }
 
-async fn share_accept(import_id: String, args: ShareMappingArgs) {
+async fn share_accept(import_id: String, args: ShareMappingArgs) -> CmdResult {
     let _ = correct_current_dir();
 
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
-    let local_config = match precheck().await {
-        Some(config) => config,
-        None => return,
-    };
+    let local_config = precheck().await?;
 
     let sheet_name = local_config.sheet_in_use().clone().unwrap_or_default();
 
@@ -4974,7 +7701,7 @@ async fn share_accept(import_id: String, args: ShareMappingArgs) {
                 account = &local_config.current_account()
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let contains_share = if let Some(share_ids) = latest_info.shares_in_my_sheets.get(&sheet_name) {
@@ -4988,33 +7715,74 @@ async fn share_accept(import_id: String, args: ShareMappingArgs) {
             "{}",
             md(t!("jv.fail.share.share_id_not_exist", id = &import_id))
         );
-        return;
+        return Err(CmdError::NotFound);
     }
 
-    let (pool, ctx, _output) = match build_pool_and_ctx(&local_config).await {
-        Some(result) => result,
-        None => return,
-    };
-
-    let share_merge_mode = {
-        if args.safe {
-            ShareMergeMode::Safe
-        } else if args.skip {
-            ShareMergeMode::Skip
-        } else if args.overwrite {
-            ShareMergeMode::Overwrite
-        } else if args.reject {
-            ShareMergeMode::RejectAll
+    if args.dry_run {
+        let operations = if args.reject {
+            vec![DryRunOperation::RejectShare {
+                share_id: import_id,
+            }]
         } else {
-            ShareMergeMode::Safe
-        }
-    };
+            let mut mappings = latest_info
+                .shares_in_my_sheets
+                .get(&sheet_name)
+                .and_then(|shares| shares.get(&import_id))
+                .map(|share| share.mappings.keys().cloned().collect::<Vec<_>>())
+                .unwrap_or_default();
+            mappings.sort();
+            mappings
+                .into_iter()
+                .map(|mapping| DryRunOperation::ImportMapping {
+                    mapping,
+                    from: import_id.clone(),
+                })
+                .collect()
+        };
+        print_dry_run(operations, args.json_output, args.pretty);
+        return Ok(());
+    }
+
+    merge_share(
+        &local_config,
+        import_id,
+        &sheet_name,
+        share_merge_mode(&args),
+    )
+    .await
+}
+
+/// Select the merge mode from the `--safe/--skip/--overwrite/--reject` flags
+fn share_merge_mode(args: &ShareMappingArgs) -> ShareMergeMode {
+    if args.safe {
+        ShareMergeMode::Safe
+    } else if args.skip {
+        ShareMergeMode::Skip
+    } else if args.overwrite {
+        ShareMergeMode::Overwrite
+    } else if args.reject {
+        ShareMergeMode::RejectAll
+    } else {
+        ShareMergeMode::Safe
+    }
+}
+
+/// Merge a share into the current sheet and report the result
+async fn merge_share(
+    local_config: &LocalConfig,
+    share_id: String,
+    sheet_name: &String,
+    share_merge_mode: ShareMergeMode,
+) -> CmdResult {
+    let reject = matches!(share_merge_mode, ShareMergeMode::RejectAll);
+
+    let (pool, ctx, _output) = build_pool_and_ctx(local_config).await?;
 
     match proc_merge_share_mapping_action(
         &pool,
         ctx,
         MergeShareMappingArguments {
-            share_id: import_id.clone(),
+            share_id: share_id.clone(),
             share_merge_mode,
         },
     )
@@ -5022,12 +7790,12 @@ async fn share_accept(import_id: String, args: ShareMappingArgs) {
     {
         Ok(r) => match r {
             MergeShareMappingActionResult::Success => {
-                if args.reject {
+                if reject {
                     println!(
                         "{}",
                         md(t!(
                             "jv.result.share.merge_shares.success_reject",
-                            share_id = &import_id
+                            share_id = &share_id
                         ))
                     );
                 } else {
@@ -5035,29 +7803,33 @@ async fn share_accept(import_id: String, args: ShareMappingArgs) {
                         "{}",
                         md(t!(
                             "jv.result.share.merge_shares.success",
-                            share_id = &import_id,
-                            sheet = &sheet_name
+                            share_id = &share_id,
+                            sheet = sheet_name
                         ))
                     );
                 }
+                Ok(())
             }
             MergeShareMappingActionResult::HasConflicts => {
                 eprintln!(
                     "{}",
                     md(t!(
                         "jv.result.share.merge_shares.has_conflicts",
-                        share_id = &import_id
+                        share_id = &share_id
                     ))
                 );
+                Err(CmdError::Rejected)
             }
             MergeShareMappingActionResult::AuthorizeFailed(e) => {
                 eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
+                Err(CmdError::AuthorizeFailed)
             }
             MergeShareMappingActionResult::EditNotAllowed => {
                 eprintln!(
                     "{}",
                     md(t!("jv.result.share.merge_shares.edit_not_allowed"))
                 );
+                Err(CmdError::Rejected)
             }
             MergeShareMappingActionResult::ShareIdNotFound(share_id) => {
                 eprintln!(
@@ -5067,6 +7839,7 @@ async fn share_accept(import_id: String, args: ShareMappingArgs) {
                         share_id = share_id
                     ))
                 );
+                Err(CmdError::NotFound)
             }
             MergeShareMappingActionResult::MergeFails(error) => {
                 eprintln!(
@@ -5076,17 +7849,194 @@ async fn share_accept(import_id: String, args: ShareMappingArgs) {
                         error = error
                     ))
                 );
+                Err(CmdError::Failed)
             }
             MergeShareMappingActionResult::Unknown => {
                 eprintln!("{}", md(t!("jv.result.share.merge_shares.unknown")));
+                Err(CmdError::Failed)
             }
         },
-        Err(e) => handle_err(e),
+        Err(e) => Err(handle_err(e)),
     }
 }
 
-async fn share_in(_from_sheet: String, _import_pattern: String, _args: ShareMappingArgs) {
-    // TODO: Implement pull mode logic
+async fn share_in(from_sheet: String, import_pattern: String, args: ShareMappingArgs) -> CmdResult {
+    let local_dir = match current_local_path() {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+            return Err(CmdError::NotWorkspace);
+        }
+    };
+
+    // Pulled mappings are matched against the source sheet, not the local files
+    let pulled_files = glob_sheet(&import_pattern, &local_dir, &from_sheet)
+        .await
+        .iter()
+        .filter_map(|f| PathBuf::from_str(f.0).ok())
+        .collect::<Vec<_>>();
+
+    let _ = correct_current_dir();
+
+    if args.reject {
+        eprintln!("{}", md(t!("jv.fail.share.reject_in_pull_mode")));
+        return Err(CmdError::Usage);
+    }
+
+    let local_config = precheck().await?;
+
+    let sheet_name = local_config.sheet_in_use().clone().unwrap_or_default();
+
+    let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
+        &local_dir,
+        &local_config.current_account(),
+    ))
+    .await
+    else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_info",
+                account = &local_config.current_account()
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    // Pre-check if the source sheet is visible
+    if from_sheet == sheet_name {
+        eprintln!("{}", md(t!("jv.fail.share.source_is_self")));
+        return Err(CmdError::Usage);
+    }
+    if !latest_info.visible_sheets.contains(&from_sheet)
+        && !latest_info.reference_sheets.contains(&from_sheet)
+    {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.share.invalid_source_sheet",
+                sheet = &from_sheet
+            ))
+        );
+        return Err(CmdError::NotFound);
+    }
+
+    if pulled_files.is_empty() {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.share.no_matching_mappings",
+                pattern = &import_pattern,
+                sheet = &from_sheet
+            ))
+        );
+        return Err(CmdError::NotFound);
+    }
+
+    if args.dry_run {
+        let mut mappings = pulled_files.clone();
+        mappings.sort();
+        let operations = mappings
+            .into_iter()
+            .map(|mapping| DryRunOperation::ImportMapping {
+                mapping,
+                from: from_sheet.clone(),
+            })
+            .collect();
+        print_dry_run(operations, args.json_output, args.pretty);
+        return Ok(());
+    }
+
+    // The share action doesn't return the id of the new share, so the description carries
+    // a tag unique to this pull, to find the share after updating
+    let pull_tag = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_nanos())
+        .unwrap_or_default()
+        ^ std::process::id() as u128;
+    let description = t!(
+        "jv.success.share.pull_description",
+        from_sheet = &from_sheet,
+        tag = format!("{:x}", pull_tag)
+    )
+    .trim()
+    .to_string();
+
+    let (pool, ctx, _output) = build_pool_and_ctx(&local_config).await?;
+
+    match proc_share_mapping_action(
+        &pool,
+        ctx,
+        ShareMappingArguments {
+            mappings: pulled_files.clone(),
+            description: description.clone(),
+            from_sheet: Some(from_sheet.clone()),
+            to_sheet: sheet_name.clone(),
+        },
+    )
+    .await
+    {
+        Ok(ShareMappingActionResult::Success) => {}
+        Ok(r) => print_share_mapping_fail(r)?,
+        Err(e) => return Err(handle_err(e)),
+    }
+
+    // Update
+    // This will change the current current_dir
+    jv_update(UpdateArgs {
+        help: false,
+        silent: true,
+        json_output: false,
+        pretty: false,
+    })
+    .await?;
+
+    let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
+        &local_dir,
+        &local_config.current_account(),
+    ))
+    .await
+    else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.cfg_not_found.latest_info",
+                account = &local_config.current_account()
+            ))
+        );
+        return Err(CmdError::ConfigNotFound);
+    };
+
+    let account = local_config.current_account();
+    let share_ids: Vec<&String> = latest_info
+        .shares_in_my_sheets
+        .get(&sheet_name)
+        .map(|shares| {
+            shares
+                .iter()
+                .filter(|(_, share)| share.sharer == account && share.description == description)
+                .map(|(id, _)| id)
+                .collect()
+        })
+        .unwrap_or_default();
+    let [share_id] = share_ids.as_slice() else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.share.pulled_share_not_found",
+                sheet = &from_sheet
+            ))
+        );
+        return Err(CmdError::NotFound);
+    };
+
+    merge_share(
+        &local_config,
+        share_id.to_string(),
+        &sheet_name,
+        share_merge_mode(&args),
+    )
+    .await
 }
 
 async fn share_out(
@@ -5094,16 +8044,22 @@ async fn share_out(
     to_sheet: String,
     description: String,
     args: ShareMappingArgs,
-) {
+) -> CmdResult {
     let mut shared_files = {
         let local_dir = match current_local_path() {
             Some(dir) => dir,
             None => {
                 eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-                return;
+                return Err(CmdError::NotWorkspace);
             }
         };
-        let files = glob(share_pattern, &local_dir).await;
+        // With `--from-file`, there is no share pattern
+        let patterns: Vec<String> = Some(share_pattern)
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect();
+        let files =
+            collect_files(&patterns, &[], args.from_file.as_deref(), false, &local_dir).await?;
         files
             .iter()
             .filter_map(|f| PathBuf::from_str(f.0).ok())
@@ -5114,17 +8070,14 @@ async fn share_out(
 
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
-    let local_config = match precheck().await {
-        Some(config) => config,
-        None => return,
-    };
+    let local_config = precheck().await?;
 
     let Some(local_workspace) = LocalWorkspace::init_current_dir(local_config.clone()) else {
         eprintln!("{}", md(t!("jv.fail.workspace_not_found")).trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
@@ -5140,7 +8093,7 @@ async fn share_out(
                 account = &local_config.current_account()
             ))
         );
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     // Pre-check if the sheet exists
@@ -5155,12 +8108,14 @@ async fn share_out(
             "{}",
             md(t!("jv.fail.share.invalid_target_sheet", sheet = &to_sheet))
         );
-        return;
+        return Err(CmdError::NotFound);
     }
 
-    let (pool, ctx, _output) = match build_pool_and_ctx(&local_config).await {
-        Some(result) => result,
-        None => return,
+    // Dry run does not connect to the upstream vault
+    let connection = if args.dry_run {
+        None
+    } else {
+        Some(build_pool_and_ctx(&local_config).await?)
     };
 
     let to_sheet_holder = {
@@ -5186,7 +8141,21 @@ async fn share_out(
         Some(description.to_string())
     }) else {
         eprintln!("{}", md(t!("jv.fail.share.no_description")));
-        return;
+        return Err(CmdError::Usage);
+    };
+
+    let Some((pool, ctx, _output)) = connection else {
+        let mut mappings = shared_files.clone();
+        mappings.sort();
+        let operations = mappings
+            .into_iter()
+            .map(|mapping| DryRunOperation::ShareMapping {
+                mapping,
+                to_sheet: to_sheet.clone(),
+            })
+            .collect();
+        print_dry_run(operations, args.json_output, args.pretty);
+        return Ok(());
     };
 
     match proc_share_mapping_action(
@@ -5216,36 +8185,50 @@ async fn share_out(
                         to_sheet_holder = to_sheet_holder
                     ))
                 );
+                Ok(())
             }
-            ShareMappingActionResult::AuthorizeFailed(e) => {
-                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
-            }
-            ShareMappingActionResult::TargetSheetNotFound(sheet) => {
-                eprintln!(
-                    "{}",
-                    md(t!(
-                        "jv.result.share.share_mapping.target_sheet_not_found",
-                        to_sheet = sheet
-                    ))
-                );
-            }
-            ShareMappingActionResult::TargetIsSelf => {
-                eprintln!("{}", md(t!("jv.result.share.share_mapping.target_is_self")));
-            }
-            ShareMappingActionResult::MappingNotFound(path_buf) => {
-                eprintln!(
-                    "{}",
-                    md(t!(
-                        "jv.result.share.share_mapping.mapping_not_found",
-                        mapping = path_buf.display()
-                    ))
-                );
-            }
-            ShareMappingActionResult::Unknown => {
-                eprintln!("{}", md(t!("jv.result.share.share_mapping.unknown")));
-            }
+            r => print_share_mapping_fail(r),
         },
-        Err(e) => handle_err(e),
+        Err(e) => Err(handle_err(e)),
+    }
+}
+
+/// Report a failed `ShareMappingActionResult`, returns the reason of the failure
+fn print_share_mapping_fail(result: ShareMappingActionResult) -> CmdResult {
+    match result {
+        ShareMappingActionResult::AuthorizeFailed(e) => {
+            eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
+            Err(CmdError::AuthorizeFailed)
+        }
+        ShareMappingActionResult::TargetSheetNotFound(sheet) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.result.share.share_mapping.target_sheet_not_found",
+                    to_sheet = sheet
+                ))
+            );
+            Err(CmdError::NotFound)
+        }
+        ShareMappingActionResult::TargetIsSelf => {
+            eprintln!("{}", md(t!("jv.result.share.share_mapping.target_is_self")));
+            Err(CmdError::Usage)
+        }
+        ShareMappingActionResult::MappingNotFound(path_buf) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.result.share.share_mapping.mapping_not_found",
+                    mapping = path_buf.display()
+                ))
+            );
+            Err(CmdError::NotFound)
+        }
+        ShareMappingActionResult::Unknown => {
+            eprintln!("{}", md(t!("jv.result.share.share_mapping.unknown")));
+            Err(CmdError::Failed)
+        }
+        ShareMappingActionResult::Success => Ok(()),
     }
 }
 
@@ -5341,7 +8324,7 @@ async fn start_share_editor(
     }
 }
 
-async fn jv_account_add(user_dir: UserDirectory, args: AccountAddArgs) {
+async fn jv_account_add(user_dir: UserDirectory, args: AccountAddArgs) -> CmdResult {
     let member = Member::new(args.account_name.clone());
 
     match user_dir.register_account(member).await {
@@ -5353,7 +8336,7 @@ async fn jv_account_add(user_dir: UserDirectory, args: AccountAddArgs) {
         }
         Err(_) => {
             eprintln!("{}", t!("jv.fail.account.add", account = args.account_name));
-            return;
+            return Err(CmdError::Failed);
         }
     }
     if args.keygen {
@@ -5379,41 +8362,47 @@ async fn jv_account_add(user_dir: UserDirectory, args: AccountAddArgs) {
                         key_path: output_path,
                     },
                 )
-                .await
+                .await?;
             }
             Ok(_) => {
                 eprintln!("{}", t!("jv.fail.account.keygen"));
+                return Err(CmdError::Failed);
             }
             Err(_) => {
                 eprintln!("{}", t!("jv.fail.account.keygen_exec"));
+                return Err(CmdError::Failed);
             }
         }
     }
+
+    Ok(())
 }
 
-async fn jv_account_remove(user_dir: UserDirectory, args: AccountRemoveArgs) {
+async fn jv_account_remove(user_dir: UserDirectory, args: AccountRemoveArgs) -> CmdResult {
     match user_dir.remove_account(&args.account_name) {
         Ok(_) => {
             println!(
                 "{}",
                 t!("jv.success.account.remove", account = args.account_name)
             );
+            Ok(())
         }
         Err(_) => {
             eprintln!(
                 "{}",
                 t!("jv.fail.account.remove", account = args.account_name)
             );
+            Err(CmdError::Failed)
         }
     }
 }
 
-async fn jv_account_list(user_dir: UserDirectory, args: AccountListArgs) {
+async fn jv_account_list(user_dir: UserDirectory, args: AccountListArgs) -> CmdResult {
     let _ = correct_current_dir();
 
     if args.json_output {
         let Ok(account_ids) = user_dir.account_ids() else {
-            return;
+            return Err(CmdError::Failed);
         };
         let mut result = HashMap::new();
         for account_id in account_ids {
@@ -5422,16 +8411,16 @@ async fn jv_account_list(user_dir: UserDirectory, args: AccountListArgs) {
         }
         let json_result = AccountListJsonResult { result };
         print_json(json_result, args.pretty);
-        return;
+        return Ok(());
     }
 
     if args.raw {
         let Ok(account_ids) = user_dir.account_ids() else {
-            return;
+            return Err(CmdError::Failed);
         };
         account_ids.iter().for_each(|a| println!("host/{}", a));
         account_ids.iter().for_each(|a| println!("{}", a));
-        return;
+        return Ok(());
     }
 
     match user_dir.account_ids() {
@@ -5455,35 +8444,39 @@ async fn jv_account_list(user_dir: UserDirectory, args: AccountListArgs) {
                 });
                 i += 1;
             }
+            Ok(())
         }
         Err(_) => {
             eprintln!("{}", t!("jv.fail.account.list"));
+            Err(CmdError::Failed)
         }
     }
 }
 
-async fn jv_account_as(user_dir: UserDirectory, args: SetLocalWorkspaceAccountArgs) {
+async fn jv_account_as(user_dir: UserDirectory, args: SetLocalWorkspaceAccountArgs) -> CmdResult {
     let (account, is_host_mode) = process_account_parameter(args.account_name);
 
     // Account exist
     let Ok(member) = user_dir.account(&account).await else {
         eprintln!("{}", t!("jv.fail.account.not_found", account = &account));
-        return;
+        let accounts = user_dir.account_ids().unwrap_or_default();
+        print_did_you_mean(&account, accounts.iter().map(|a| a.as_str()));
+        return Err(CmdError::NotFound);
     };
 
     let Some(_local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(mut local_cfg) = LocalConfig::read().await else {
         eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     if let Err(_) = local_cfg.set_current_account(member.id()) {
         eprintln!("{}", md(t!("jv.fail.account.as")));
-        return;
+        return Err(CmdError::Failed);
     };
 
     local_cfg.set_host_mode(is_host_mode);
@@ -5492,7 +8485,7 @@ async fn jv_account_as(user_dir: UserDirectory, args: SetLocalWorkspaceAccountAr
         Ok(_) => {}
         Err(e) => {
             eprintln!("{}", md(t!("jv.fail.write_cfg", error = e)));
-            return;
+            return Err(CmdError::Failed);
         }
     };
 
@@ -5507,6 +8500,8 @@ async fn jv_account_as(user_dir: UserDirectory, args: SetLocalWorkspaceAccountAr
             t!("jv.success.account.as", account = member.id()).trim()
         );
     }
+
+    Ok(())
 }
 
 /// Input account, get MemberId and whether it's a host
@@ -5522,14 +8517,14 @@ fn process_account_parameter(account_input: String) -> (MemberId, bool) {
     (snake_case!(account_id), is_host)
 }
 
-async fn jv_account_move_key(user_dir: UserDirectory, args: MoveKeyToAccountArgs) {
+async fn jv_account_move_key(user_dir: UserDirectory, args: MoveKeyToAccountArgs) -> CmdResult {
     // Key file exist
     if !args.key_path.exists() {
         eprintln!(
             "{}",
             t!("jv.fail.path_not_found", path = args.key_path.display())
         );
-        return;
+        return Err(CmdError::NotFound);
     }
 
     // Account exist
@@ -5538,7 +8533,9 @@ async fn jv_account_move_key(user_dir: UserDirectory, args: MoveKeyToAccountArgs
             "{}",
             t!("jv.fail.account.not_found", account = args.account_name)
         );
-        return;
+        let accounts = user_dir.account_ids().unwrap_or_default();
+        print_did_you_mean(&args.account_name, accounts.iter().map(|a| a.as_str()));
+        return Err(CmdError::NotFound);
     };
 
     // Rename key file
@@ -5548,12 +8545,21 @@ async fn jv_account_move_key(user_dir: UserDirectory, args: MoveKeyToAccountArgs
     )
     .await
     {
-        Ok(_) => println!("{}", t!("jv.success.account.move_key")),
-        Err(_) => eprintln!("{}", t!("jv.fail.account.move_key")),
+        Ok(_) => {
+            println!("{}", t!("jv.success.account.move_key"));
+            Ok(())
+        }
+        Err(_) => {
+            eprintln!("{}", t!("jv.fail.account.move_key"));
+            Err(CmdError::Failed)
+        }
     }
 }
 
-async fn jv_account_generate_pub_key(user_dir: UserDirectory, args: GeneratePublicKeyArgs) {
+async fn jv_account_generate_pub_key(
+    user_dir: UserDirectory,
+    args: GeneratePublicKeyArgs,
+) -> CmdResult {
     let private_key_path = user_dir.account_private_key_path(&args.account_name);
     let target_path = args
         .output_dir
@@ -5580,61 +8586,78 @@ async fn jv_account_generate_pub_key(user_dir: UserDirectory, args: GeneratePubl
                     export = target_path.display()
                 )
             );
+            Ok(())
         }
         Ok(_) => {
             eprintln!("{}", t!("jv.fail.account.keygen"));
+            Err(CmdError::Failed)
         }
         Err(_) => {
             eprintln!("{}", t!("jv.fail.account.keygen_exec"));
+            Err(CmdError::Failed)
         }
     }
 }
 
-async fn jv_update(update_file_args: UpdateArgs) {
-    let local_config = match precheck().await {
-        Some(config) => config,
-        None => return,
-    };
+async fn jv_update(update_file_args: UpdateArgs) -> CmdResult {
+    let local_config = precheck().await?;
 
-    let (pool, ctx, _output) = match build_pool_and_ctx(&local_config).await {
-        Some(result) => result,
-        None => return,
-    };
+    let (pool, ctx, _output) = build_pool_and_ctx(&local_config).await?;
 
-    match proc_update_to_latest_info_action(&pool, ctx, ()).await {
-        Err(e) => handle_err(e),
-        Ok(result) => {
-            if !update_file_args.silent {
-                match result {
-                    UpdateToLatestInfoResult::Success => {
-                        println!("{}", md(t!("jv.result.update.success")));
-                    }
-                    UpdateToLatestInfoResult::AuthorizeFailed(e) => {
-                        eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)))
+    let result = match proc_update_to_latest_info_action(&pool, ctx, ()).await {
+        Err(e) => return Err(handle_err(e)),
+        Ok(result) => match result {
+            UpdateToLatestInfoResult::Success => UpdateResult::Success,
+            UpdateToLatestInfoResult::AuthorizeFailed(e) => {
+                UpdateResult::AuthorizeFailed(e.to_string())
+            }
+            UpdateToLatestInfoResult::SyncCachedSheetFail(sync_cached_sheet_fail_reason) => {
+                match sync_cached_sheet_fail_reason {
+                    SyncCachedSheetFailReason::PathAlreadyExist(path_buf) => {
+                        UpdateResult::PathAlreadyExist(path_buf)
                     }
-                    UpdateToLatestInfoResult::SyncCachedSheetFail(
-                        sync_cached_sheet_fail_reason,
-                    ) => match sync_cached_sheet_fail_reason {
-                        SyncCachedSheetFailReason::PathAlreadyExist(path_buf) => {
-                            eprintln!(
-                                "{}",
-                                md(t!(
-                                    "jv.result.update.fail.sync_cached_sheet_fail.path_already_exist",
-                                    path = path_buf.display()
-                                ))
-                            );
-                        }
-                    },
                 }
             }
+        },
+    };
+
+    let cmd_result = match &result {
+        UpdateResult::Success => Ok(()),
+        UpdateResult::AuthorizeFailed(_) => Err(CmdError::AuthorizeFailed),
+        UpdateResult::PathAlreadyExist(_) => Err(CmdError::Rejected),
+    };
+
+    if update_file_args.json_output {
+        print_json(UpdateJsonResult { result }, update_file_args.pretty);
+        return cmd_result;
+    }
+
+    if !update_file_args.silent {
+        match result {
+            UpdateResult::Success => {
+                println!("{}", md(t!("jv.result.update.success")));
+            }
+            UpdateResult::AuthorizeFailed(e) => {
+                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)))
+            }
+            UpdateResult::PathAlreadyExist(path_buf) => {
+                eprintln!(
+                    "{}",
+                    md(t!(
+                        "jv.result.update.fail.sync_cached_sheet_fail.path_already_exist",
+                        path = path_buf.display()
+                    ))
+                );
+            }
         }
     }
+    cmd_result
 }
 
-async fn jv_direct(args: DirectArgs) {
+async fn jv_direct(args: DirectArgs) -> CmdResult {
     let Some(upstream) = args.upstream else {
         println!("{}", md(t!("jv.direct")));
-        return;
+        return Err(CmdError::Usage);
     };
 
     if !args.confirm {
@@ -5642,7 +8665,10 @@ async fn jv_direct(args: DirectArgs) {
             "{}",
             t!("jv.confirm.direct", upstream = upstream).trim().yellow()
         );
-        confirm_hint_or(t!("common.confirm"), || exit(1)).await;
+        confirm_hint_or(t!("common.confirm"), || {
+            exit(CmdError::Cancelled.exit_code())
+        })
+        .await;
     }
 
     let pool = client_registry::client_action_pool();
@@ -5657,68 +8683,100 @@ async fn jv_direct(args: DirectArgs) {
                     err = e
                 ))
             );
-            return;
+            return Err(CmdError::Usage);
         }
     };
 
-    let Some(instance) = connect(upstream).await else {
-        // Since connect() function already printed error messages, we only handle the return here
-        return;
-    };
+    // Since connect() function already printed error messages, we only handle the return here
+    let instance = connect(upstream).await?;
 
     let ctx = ActionContext::local().insert_instance(instance);
 
-    match proc_set_upstream_vault_action(&pool, ctx, upstream).await {
-        Err(e) => handle_err(e),
+    let result = match proc_set_upstream_vault_action(&pool, ctx, upstream).await {
+        Err(e) => return Err(handle_err(e)),
         Ok(result) => match result {
-            SetUpstreamVaultActionResult::DirectedAndStained => {
-                println!(
-                    "{}",
-                    md(t!(
-                        "jv.result.direct.directed_and_stained",
-                        upstream = upstream
-                    ))
-                );
-                insert_recent_ip_address(upstream.to_string().trim()).await;
-            }
-            SetUpstreamVaultActionResult::Redirected => {
-                println!(
-                    "{}",
-                    md(t!("jv.result.direct.redirected", upstream = upstream))
-                );
-                insert_recent_ip_address(upstream.to_string().trim()).await;
-            }
-            SetUpstreamVaultActionResult::AlreadyStained => {
-                eprintln!("{}", md(t!("jv.result.direct.already_stained")))
-            }
+            SetUpstreamVaultActionResult::DirectedAndStained => DirectResult::DirectedAndStained,
+            SetUpstreamVaultActionResult::Redirected => DirectResult::Redirected,
+            SetUpstreamVaultActionResult::AlreadyStained => DirectResult::AlreadyStained,
             SetUpstreamVaultActionResult::AuthorizeFailed(e) => {
-                eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)))
+                DirectResult::AuthorizeFailed(e.to_string())
             }
             SetUpstreamVaultActionResult::RedirectFailed(e) => {
-                eprintln!("{}", md(t!("jv.result.direct.redirect_failed", err = e)))
-            }
-            SetUpstreamVaultActionResult::SameUpstream => {
-                eprintln!("{}", md(t!("jv.result.direct.same_upstream")))
+                DirectResult::RedirectFailed(e.to_string())
             }
-            _ => {}
+            SetUpstreamVaultActionResult::SameUpstream => DirectResult::SameUpstream,
+            _ => DirectResult::Unknown,
         },
     };
+
+    let cmd_result = match &result {
+        DirectResult::DirectedAndStained | DirectResult::Redirected => {
+            insert_recent_ip_address(upstream.to_string().trim()).await;
+            Ok(())
+        }
+        DirectResult::AlreadyStained | DirectResult::SameUpstream => Err(CmdError::Rejected),
+        DirectResult::AuthorizeFailed(_) => Err(CmdError::AuthorizeFailed),
+        DirectResult::RedirectFailed(_) | DirectResult::Unknown => Err(CmdError::Failed),
+    };
+
+    if args.json_output {
+        print_json(
+            DirectJsonResult {
+                upstream: upstream.to_string(),
+                result,
+            },
+            args.pretty,
+        );
+        return cmd_result;
+    }
+
+    match result {
+        DirectResult::DirectedAndStained => {
+            println!(
+                "{}",
+                md(t!(
+                    "jv.result.direct.directed_and_stained",
+                    upstream = upstream
+                ))
+            );
+        }
+        DirectResult::Redirected => {
+            println!(
+                "{}",
+                md(t!("jv.result.direct.redirected", upstream = upstream))
+            );
+        }
+        DirectResult::AlreadyStained => {
+            eprintln!("{}", md(t!("jv.result.direct.already_stained")));
+        }
+        DirectResult::AuthorizeFailed(e) => {
+            eprintln!("{}", md(t!("jv.result.common.authroize_failed", err = e)));
+        }
+        DirectResult::RedirectFailed(e) => {
+            eprintln!("{}", md(t!("jv.result.direct.redirect_failed", err = e)));
+        }
+        DirectResult::SameUpstream => {
+            eprintln!("{}", md(t!("jv.result.direct.same_upstream")));
+        }
+        DirectResult::Unknown => {}
+    }
+    cmd_result
 }
 
-async fn jv_unstain(args: UnstainArgs) {
+async fn jv_unstain(args: UnstainArgs) -> CmdResult {
     let Some(_local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(mut local_cfg) = LocalConfig::read().await else {
         eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     if !local_cfg.stained() {
         eprintln!("{}", md(t!("jv.fail.unstain")));
-        return;
+        return Err(CmdError::NotStained);
     }
 
     if !args.confirm {
@@ -5726,7 +8784,10 @@ async fn jv_unstain(args: UnstainArgs) {
             "{}",
             md(t!("jv.confirm.unstain", upstream = local_cfg.vault_addr())).yellow()
         );
-        confirm_hint_or(t!("common.confirm"), || exit(1)).await;
+        confirm_hint_or(t!("common.confirm"), || {
+            exit(CmdError::Cancelled.exit_code())
+        })
+        .await;
     }
 
     local_cfg.unstain();
@@ -5734,19 +8795,21 @@ async fn jv_unstain(args: UnstainArgs) {
         Ok(_) => {}
         Err(e) => {
             eprintln!("{}", md(t!("jv.fail.write_cfg", error = e.to_string())));
-            return;
+            return Err(CmdError::Failed);
         }
     };
 
     println!("{}", md(t!("jv.success.unstain")));
+
+    Ok(())
 }
 
-async fn jv_docs(args: DocsArgs) {
+async fn jv_docs(args: DocsArgs) -> CmdResult {
     let Some(docs_name) = args.docs_name else {
         if !args.raw {
             println!("{}", md(t!("jv.docs")));
         }
-        return;
+        return Err(CmdError::Usage);
     };
 
     if docs_name.trim() == "ls" || docs_name.trim() == "list" {
@@ -5758,7 +8821,7 @@ async fn jv_docs(args: DocsArgs) {
                     println!("{}", d.trim_start_matches("docs_"))
                 }
             });
-            return;
+            return Ok(());
         }
 
         println!("{}", md(t!("jv.success.docs.list.header")));
@@ -5778,67 +8841,275 @@ async fn jv_docs(args: DocsArgs) {
         }
         println!("{}", md(t!("jv.success.docs.list.footer")));
 
-        return;
+        return Ok(());
+    }
+
+    let name = format!("docs_{}", snake_case!(docs_name.clone()));
+    let Some(document) = document(name) else {
+        eprintln!(
+            "{}",
+            md(t!("jv.fail.docs.not_found", docs_name = docs_name))
+        );
+        return Err(CmdError::NotFound);
+    };
+
+    if args.direct {
+        println!("{}", document.trim());
+    } else {
+        let Some(doc_dir) = current_cfg_dir() else {
+            eprintln!(
+                "{}",
+                md(t!("jv.fail.docs.no_doc_dir", docs_name = docs_name))
+            );
+            return Err(CmdError::Failed);
+        };
+        let file = doc_dir.join("DOCS.MD");
+        if let Err(e) = show_in_pager(document, file).await {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.docs.open_editor",
+                    err = e,
+                    docs_name = docs_name
+                ))
+            );
+            return Err(CmdError::Failed);
+        }
+    }
+
+    Ok(())
+}
+
+async fn jv_config_list(args: ConfigListArgs) -> CmdResult {
+    let config = read_current_config()?;
+    let items: Vec<ConfigItem> = config
+        .entries()
+        .into_iter()
+        .map(|entry| {
+            let (value, layer) = config.resolve_entry(&entry, |var| std::env::var(var).ok());
+            ConfigItem {
+                key: entry.name(),
+                value,
+                layer: layer.name().to_string(),
+            }
+        })
+        .collect();
+
+    if args.json_output {
+        print_json(ConfigListJsonResult { result: items }, args.pretty);
+        return Ok(());
+    }
+
+    let mut table = SimpleTable::new(vec![
+        t!("jv.success.config.list.headers.key"),
+        t!("jv.success.config.list.headers.value"),
+        t!("jv.success.config.list.headers.layer"),
+    ]);
+    for item in items {
+        let layer = if item.layer == ConfigLayer::Default.name() {
+            item.layer.bright_black().to_string()
+        } else {
+            item.layer.cyan().to_string()
+        };
+        table.push_item(vec![item.key, item.value, layer]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+async fn jv_config_get(args: ConfigGetArgs) -> CmdResult {
+    let entry = parse_config_entry(&args.key)?;
+    let (value, layer) =
+        read_current_config()?.resolve_entry(&entry, |var| std::env::var(var).ok());
+    if args.origin {
+        println!("{} {}", value, format!("({})", layer.name()).cyan());
+    } else {
+        println!("{}", value);
+    }
+    Ok(())
+}
+
+async fn jv_config_set(args: ConfigSetArgs) -> CmdResult {
+    let entry = parse_config_entry(&args.key)?;
+    let Some(value) = entry.parse_value(&args.value) else {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.config.invalid_value",
+                key = entry.name(),
+                value = args.value
+            ))
+        );
+        return Err(CmdError::Usage);
+    };
+
+    let (path, layer) = config_file_path(args.workspace)?;
+    let mut file = read_checked_config_file(&path)?;
+    file.set(&entry.name(), value);
+    write_checked_config_file(&path, &file)?;
+
+    println!(
+        "{}",
+        md(t!(
+            "jv.success.config.set",
+            key = entry.name(),
+            value = file.get(&entry.name()).unwrap_or_default(),
+            layer = layer.name()
+        ))
+    );
+    print_config_overridden(&entry, layer)
+}
+
+async fn jv_config_unset(args: ConfigUnsetArgs) -> CmdResult {
+    let entry = parse_config_entry(&args.key)?;
+    let (path, layer) = config_file_path(args.workspace)?;
+    let mut file = read_checked_config_file(&path)?;
+    if !file.unset(&entry.name()) {
+        println!(
+            "{}",
+            md(t!(
+                "jv.success.config.not_set",
+                key = entry.name(),
+                layer = layer.name()
+            ))
+        );
+        return Ok(());
+    }
+    write_checked_config_file(&path, &file)?;
+
+    println!(
+        "{}",
+        md(t!(
+            "jv.success.config.unset",
+            key = entry.name(),
+            layer = layer.name()
+        ))
+    );
+    print_config_overridden(&entry, layer)
+}
+
+/// Parse a configuration key or alias, suggesting the closest known key if it is unknown
+fn parse_config_entry(key: &str) -> Result<ConfigEntry, CmdError> {
+    ConfigEntry::parse(key).ok_or_else(|| {
+        eprintln!("{}", md(t!("jv.fail.config.unknown_key", key = key)));
+        print_did_you_mean(key, ConfigKey::ALL.iter().map(|key| key.name()));
+        CmdError::Usage
+    })
+}
+
+/// Path of the configuration file to edit, of the current workspace or of the user
+fn config_file_path(workspace: bool) -> Result<(PathBuf, ConfigLayer), CmdError> {
+    if workspace {
+        let Some(local_dir) = current_local_path() else {
+            eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
+            return Err(CmdError::NotWorkspace);
+        };
+        return Ok((workspace_config_path(&local_dir), ConfigLayer::Workspace));
+    }
+
+    let Some(path) = user_config_path() else {
+        eprintln!("{}", t!("jv.fail.account.no_user_dir"));
+        return Err(CmdError::ConfigNotFound);
+    };
+    Ok((path, ConfigLayer::User))
+}
+
+/// Read the configuration files of the user and of the current workspace
+fn read_current_config() -> Result<Config, CmdError> {
+    let mut config = Config::default();
+    if let Some(path) = user_config_path() {
+        config.user = read_checked_config_file(&path)?;
+    }
+    if let Some(local_dir) = current_local_path() {
+        config.workspace = read_checked_config_file(&workspace_config_path(&local_dir))?;
     }
+    Ok(config)
+}
+
+fn read_checked_config_file(path: &Path) -> Result<ConfigTable, CmdError> {
+    read_config_file(path).map_err(|err| {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.config.invalid_file",
+                path = path.display(),
+                error = err.message()
+            ))
+        );
+        CmdError::ConfigNotFound
+    })
+}
 
-    let name = format!("docs_{}", snake_case!(docs_name.clone()));
-    let Some(document) = document(name) else {
+fn write_checked_config_file(path: &Path, file: &ConfigTable) -> CmdResult {
+    write_config_file(path, file).map_err(|err| {
         eprintln!(
             "{}",
-            md(t!("jv.fail.docs.not_found", docs_name = docs_name))
+            md(t!(
+                "jv.fail.config.write",
+                path = path.display(),
+                error = err
+            ))
         );
-        return;
-    };
+        CmdError::Failed
+    })
+}
 
-    if args.direct {
-        println!("{}", document.trim());
-    } else {
-        let Some(doc_dir) = current_cfg_dir() else {
-            eprintln!(
-                "{}",
-                md(t!("jv.fail.docs.no_doc_dir", docs_name = docs_name))
-            );
-            return;
-        };
-        let file = doc_dir.join("DOCS.MD");
-        if let Err(e) = show_in_pager(document, file).await {
-            eprintln!(
-                "{}",
-                md(t!(
-                    "jv.fail.docs.open_editor",
-                    err = e,
-                    docs_name = docs_name
-                ))
-            );
-        }
+/// Tell when a value written to a layer is hidden by a layer with higher precedence
+fn print_config_overridden(entry: &ConfigEntry, layer: ConfigLayer) -> CmdResult {
+    let (value, current) =
+        read_current_config()?.resolve_entry(entry, |var| std::env::var(var).ok());
+    if current > layer {
+        println!(
+            "{}",
+            t!(
+                "jv.tip.config_overridden",
+                key = entry.name(),
+                value = value,
+                layer = current.name()
+            )
+            .trim()
+            .yellow()
+        );
     }
+    Ok(())
 }
 
-async fn jv_debug_glob(glob_args: DebugGlobArgs) {
+async fn jv_debug_glob(glob_args: DebugGlobArgs) -> CmdResult {
     let local_dir = match current_local_path() {
         Some(dir) => dir,
         None => {
             // No, dont print anything
             // eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-            return;
+            return Err(CmdError::NotWorkspace);
         }
     };
 
-    for path in glob(glob_args.pattern, &local_dir).await.keys() {
+    for path in glob(glob_args.pattern, &local_dir, false).await.keys() {
         println!("{}", path);
     }
+
+    Ok(())
 }
 
-async fn glob(pattern: impl Into<String>, local_dir: &PathBuf) -> BTreeMap<String, ()> {
+async fn glob(
+    pattern: impl Into<String>,
+    local_dir: &PathBuf,
+    ignore_case: bool,
+) -> BTreeMap<String, ()> {
     let pattern = pattern.into();
-    let globber = match get_globber(&pattern, true).await {
+    let ignore_case = ignore_case || glob_case_insensitive();
+    let globber = match get_globber(&pattern, true, ignore_case).await {
         Ok(g) => g,
-        Err(_) => match get_globber(&pattern, false).await {
+        Err(_) => match get_globber(&pattern, false, ignore_case).await {
             Ok(g) => g,
             Err(_) => return BTreeMap::new(),
         },
     };
 
+    if ignore_case {
+        warn_case_collisions(&globber);
+    }
+
     let result = globber.names();
     let base_dir = globber.base();
 
@@ -5855,12 +9126,120 @@ async fn glob(pattern: impl Into<String>, local_dir: &PathBuf) -> BTreeMap<Strin
     path_map
 }
 
+/// Collect the files of a file command: the matches of the patterns,
+/// plus the paths listed in `from_file` (a `-` pattern or `from_file` reads the list from stdin).
+/// Patterns starting with `!` and the `excludes` remove the files they match,
+/// as well as everything under the directories they match
+async fn collect_files(
+    patterns: &[String],
+    excludes: &[String],
+    from_file: Option<&str>,
+    ignore_case: bool,
+    local_dir: &PathBuf,
+) -> Result<BTreeMap<String, ()>, CmdError> {
+    let mut result = BTreeMap::new();
+    let mut excluded = BTreeMap::new();
+    let mut lists = Vec::new();
+
+    for pattern in patterns {
+        if pattern == "-" {
+            lists.push("-");
+        } else if let Some(exclude) = pattern.strip_prefix('!') {
+            excluded.extend(glob(exclude, local_dir, ignore_case).await);
+        } else {
+            result.extend(glob(pattern, local_dir, ignore_case).await);
+        }
+    }
+    for exclude in excludes {
+        excluded.extend(glob(exclude, local_dir, ignore_case).await);
+    }
+    lists.extend(from_file);
+
+    for list in lists {
+        let paths = match read_path_list(list).await {
+            Ok(paths) => paths,
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    md(t!(
+                        "jv.fail.from_file.read",
+                        path = list,
+                        error = e.to_string()
+                    ))
+                );
+                return Err(CmdError::Failed);
+            }
+        };
+        result.extend(validate_listed_paths(paths, local_dir).await);
+    }
+
+    result.retain(|path, _| {
+        !excluded
+            .keys()
+            .any(|excluded| Path::new(path).starts_with(excluded))
+    });
+    Ok(result)
+}
+
+/// Resolve listed paths (relative to the current directory) to paths relative to the workspace.
+/// Like glob results, only paths mapped in the current sheet or existing locally are kept
+async fn validate_listed_paths(paths: Vec<String>, local_dir: &PathBuf) -> BTreeMap<String, ()> {
+    let current = current_dir().unwrap_or_default();
+    let relative = current.strip_prefix(local_dir).unwrap_or(Path::new(""));
+
+    let cached_sheet = match LocalConfig::read_from(local_dir.join(CLIENT_FILE_WORKSPACE)).await {
+        Ok(local_cfg) => match local_cfg.sheet_in_use() {
+            Some(sheet_name) => CachedSheet::cached_sheet_data(sheet_name).await.ok(),
+            None => None,
+        },
+        Err(_) => None,
+    };
+
+    let mut result = BTreeMap::new();
+    for path in paths {
+        let mapping = match format_path(relative.join(&path)) {
+            Ok(mapping) if !mapping.as_os_str().is_empty() => mapping,
+            _ => {
+                eprintln!("{}", md(t!("jv.fail.from_file.invalid_path", path = path)));
+                continue;
+            }
+        };
+
+        let mapped = cached_sheet
+            .as_ref()
+            .is_some_and(|sheet| sheet.mapping().contains_key(&mapping));
+        if !mapped && !local_dir.join(&mapping).exists() {
+            eprintln!("{}", md(t!("jv.fail.from_file.not_found", path = path)));
+            continue;
+        }
+
+        result.insert(mapping.display().to_string(), ());
+    }
+    result
+}
+
+/// Warn about the matched names that only differ in case, they may be different mappings
+fn warn_case_collisions(globber: &Globber) {
+    for names in globber.case_collisions() {
+        let names = names
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        eprintln!(
+            "{}",
+            t!("jv.tip.case_collision", names = names).trim().yellow()
+        );
+    }
+}
+
 async fn get_globber(
     pattern: impl Into<String>,
     with_current_sheet: bool,
+    ignore_case: bool,
 ) -> Result<Globber, std::io::Error> {
     // Build globber
-    let globber = Globber::from(pattern.into());
+    let globber = Globber::from(pattern.into()).with_ignore_case(ignore_case);
 
     let globber = if with_current_sheet {
         // Get necessary informations
@@ -5898,12 +9277,17 @@ async fn get_globber(
             ));
         }
 
+        let ignore = IgnoreRules::new(&local_dir);
+
+        // Index the mappings once, instead of scanning the whole sheet for every directory
+        let sheet_files: PathTrie = cached_sheet.mapping().keys().collect();
+
         // Sheet mode
         globber.glob(|current_dir| {
             let mut result = HashSet::new();
 
             // First, add local files
-            get_local_files(&current_dir, &mut result);
+            get_local_files(&current_dir, &mut result, &ignore);
 
             // Start collecting sheet files
             // Check if we're in the workspace directory (get current path relative to local workspace)
@@ -5911,41 +9295,19 @@ async fn get_globber(
                 return result.into_iter().collect();
             };
 
-            let mut dirs = HashSet::new();
-
-            cached_sheet.mapping().iter().for_each(|(path, _)| {
-                let left = relative_path_to_local;
-
-                // Skip: files that don't start with the current directory
-                let Ok(right) = path.strip_prefix(left) else {
-                    return;
-                };
-
-                // File: starts with current directory and doesn't contain "/"
-                // (since we already filtered out files that don't start with current directory,
-                // here we just check if it contains a path separator)
-                let file_name = right.display().to_string();
-                if !file_name.contains("/") {
-                    result.insert(GlobItem::File(file_name));
-                } else {
-                    // Directory: contains separator, take the first part, add to dirs set
-                    if let Some(first_part) = file_name.split('/').next() {
-                        dirs.insert(first_part.to_string());
-                    }
-                }
-            });
-
-            dirs.into_iter().for_each(|dir| {
-                result.insert(GlobItem::Directory(dir));
-            });
+            result.extend(sheet_files.items(relative_path_to_local));
 
             result.into_iter().collect()
         })
     } else {
+        // Outside a workspace, ignore files are looked up from the base directory of the pattern
+        let ignore =
+            IgnoreRules::new(current_local_path().unwrap_or_else(|| globber.base().clone()));
+
         // Local mode
         globber.glob(|current| {
             let mut items = HashSet::new();
-            get_local_files(&current, &mut items);
+            get_local_files(&current, &mut items, &ignore);
             items.iter().cloned().collect()
         })
     }?;
@@ -5953,7 +9315,92 @@ async fn get_globber(
     Ok(globber)
 }
 
-fn get_local_files(current: &PathBuf, items: &mut HashSet<GlobItem>) {
+/// Glob the mappings of a cached sheet, regardless of the local files.
+/// Only names that exist in the sheet are returned, relative to the workspace root
+async fn glob_sheet(
+    pattern: impl Into<String>,
+    local_dir: &PathBuf,
+    sheet_name: &String,
+) -> BTreeMap<String, ()> {
+    let Ok(cached_sheet) = CachedSheet::cached_sheet_data(sheet_name).await else {
+        return BTreeMap::new();
+    };
+
+    let sheet_files: PathTrie = cached_sheet.mapping().keys().collect();
+
+    let ignore_case = glob_case_insensitive();
+    let globber = match Globber::from(pattern.into())
+        .with_ignore_case(ignore_case)
+        .glob(|current_dir| match current_dir.strip_prefix(local_dir) {
+            Ok(relative_path_to_local) => sheet_files.items(relative_path_to_local),
+            Err(_) => Vec::new(),
+        }) {
+        Ok(g) => g,
+        Err(_) => return BTreeMap::new(),
+    };
+
+    let relative_path = globber
+        .base()
+        .strip_prefix(local_dir)
+        .unwrap_or(local_dir.as_path());
+
+    globber
+        .names()
+        .into_iter()
+        .map(|name| relative_path.join(name))
+        .filter(|path| cached_sheet.mapping().contains_key(path))
+        .map(|path| (path.display().to_string(), ()))
+        .collect()
+}
+
+/// Remove the files matching `.jvignore` from an analysis. A mapped file paired with an ignored file
+/// is lost again, unless a file that is not ignored has its content, as told by `base_hash`.
+/// The analyzer has already hashed the ignored files, only the pairing is corrected here
+async fn filter_ignored(
+    local_dir: &Path,
+    analyzed: &mut AnalyzeResult,
+    base_hash: impl Fn(&PathBuf) -> Option<String>,
+) {
+    let ignore = IgnoreRules::new(local_dir);
+    analyzed
+        .created
+        .retain(|path| !ignore.is_ignored(path, false));
+
+    let unpaired: Vec<String> = analyzed
+        .moved
+        .iter()
+        .filter(|(_, (_, to))| ignore.is_ignored(to, false))
+        .map(|(id, _)| id.clone())
+        .collect();
+    if unpaired.is_empty() {
+        return;
+    }
+
+    let mut created_hashes: HashMap<String, PathBuf> = HashMap::new();
+    for created in &analyzed.created {
+        if let Ok(hash_calc) = sha1_hash::calc_sha1(local_dir.join(created), 4096usize).await {
+            created_hashes.insert(hash_calc.hash, created.clone());
+        }
+    }
+
+    for id in unpaired {
+        let Some((from, _)) = analyzed.moved.remove(&id) else {
+            continue;
+        };
+        match base_hash(&from).and_then(|hash| created_hashes.remove(&hash)) {
+            Some(to) => {
+                analyzed.created.remove(&to);
+                analyzed.moved.insert(id, (from, to));
+            }
+            None => {
+                analyzed.lost.insert(from);
+            }
+        }
+    }
+}
+
+/// Collect the local files and directories directly under `current`, except the ignored ones
+fn get_local_files(current: &PathBuf, items: &mut HashSet<GlobItem>, ignore: &IgnoreRules) {
     if let Ok(entries) = std::fs::read_dir(&current) {
         for entry in entries.flatten() {
             let path = entry.path();
@@ -5963,6 +9410,10 @@ fn get_local_files(current: &PathBuf, items: &mut HashSet<GlobItem>) {
                 .map(|s| s.to_string())
                 .unwrap_or_default();
 
+            if ignore.is_ignored(&path, path.is_dir()) {
+                continue;
+            }
+
             if path.is_file() {
                 items.insert(GlobItem::File(name));
             } else if path.is_dir() {
@@ -5974,18 +9425,19 @@ fn get_local_files(current: &PathBuf, items: &mut HashSet<GlobItem>) {
     }
 }
 
-pub fn handle_err(err: TcpTargetError) {
-    eprintln!("{}", md(t!("jv.fail.from_core", err = err)))
+pub fn handle_err(err: TcpTargetError) -> CmdError {
+    eprintln!("{}", md(t!("jv.fail.from_core", err = err)));
+    CmdError::ConnectionFailed
 }
 
-async fn connect(upstream: SocketAddr) -> Option<ConnectionInstance> {
+async fn connect(upstream: SocketAddr) -> Result<ConnectionInstance, CmdError> {
     // Create Socket
     let socket = if upstream.is_ipv4() {
         match TcpSocket::new_v4() {
             Ok(socket) => socket,
             Err(_) => {
                 eprintln!("{}", t!("jv.fail.create_socket").trim());
-                return None;
+                return Err(CmdError::Failed);
             }
         }
     } else {
@@ -5993,7 +9445,7 @@ async fn connect(upstream: SocketAddr) -> Option<ConnectionInstance> {
             Ok(socket) => socket,
             Err(_) => {
                 eprintln!("{}", t!("jv.fail.create_socket").trim());
-                return None;
+                return Err(CmdError::Failed);
             }
         }
     };
@@ -6001,18 +9453,272 @@ async fn connect(upstream: SocketAddr) -> Option<ConnectionInstance> {
     // Connect
     let Ok(stream) = socket.connect(upstream).await else {
         eprintln!("{}", t!("jv.fail.connection_failed").trim());
-        return None;
+        return Err(CmdError::ConnectionFailed);
+    };
+
+    Ok(ConnectionInstance::from(stream))
+}
+
+/// Print the reason a command line failed to parse
+fn print_parse_error(err: &clap::Error) {
+    use clap::error::{ContextKind, ContextValue, ErrorKind};
+
+    let context = |kind: ContextKind| match err.get(kind) {
+        Some(ContextValue::String(value)) => value.clone(),
+        Some(ContextValue::Strings(values)) => values.join(", "),
+        Some(value) => value.to_string(),
+        None => String::default(),
+    };
+
+    let message = match err.kind() {
+        ErrorKind::InvalidSubcommand => t!(
+            "jv.fail.parse.unknown_command",
+            name = context(ContextKind::InvalidSubcommand)
+        ),
+        ErrorKind::UnknownArgument => t!(
+            "jv.fail.parse.unknown_argument",
+            arg = context(ContextKind::InvalidArg)
+        ),
+        ErrorKind::MissingRequiredArgument => t!(
+            "jv.fail.parse.missing_argument",
+            args = context(ContextKind::InvalidArg)
+        ),
+        ErrorKind::MissingSubcommand | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand => {
+            t!("jv.fail.parse.missing_command")
+        }
+        ErrorKind::InvalidValue | ErrorKind::ValueValidation => t!(
+            "jv.fail.parse.invalid_value",
+            value = context(ContextKind::InvalidValue),
+            arg = context(ContextKind::InvalidArg)
+        ),
+        ErrorKind::ArgumentConflict => t!(
+            "jv.fail.parse.argument_conflict",
+            arg = context(ContextKind::InvalidArg),
+            other = context(ContextKind::PriorArg)
+        ),
+        // Other errors are rare, show the message of clap
+        _ => {
+            let rendered = err.render().to_string();
+            let first_line = rendered.lines().next().unwrap_or_default();
+            t!(
+                "jv.fail.parse.other",
+                error = first_line.trim_start_matches("error:").trim()
+            )
+        }
+    };
+    eprintln!("{}", md(message));
+}
+
+/// Suggest the closest subcommand or alias when a subcommand on the command line is unknown
+fn suggest_subcommand(args: &[String], aliases: &Aliases) {
+    use clap::CommandFactory;
+
+    let mut command = JustEnoughVcsWorkspace::command();
+    for (depth, arg) in args.iter().skip(1).enumerate() {
+        if !command.has_subcommands() {
+            return;
+        }
+        if let Some(subcommand) = command.find_subcommand(arg) {
+            command = subcommand.clone();
+            continue;
+        }
+
+        // User-defined aliases only stand for top-level commands
+        let is_root = depth == 0;
+        let candidates: Vec<&str> = command
+            .get_subcommands()
+            .flat_map(|subcommand| {
+                std::iter::once(subcommand.get_name()).chain(subcommand.get_all_aliases())
+            })
+            .filter(|name| !name.starts_with('-'))
+            .chain(aliases.names().filter(|_| is_root))
+            .collect();
+        print_did_you_mean(arg, candidates);
+        return;
+    }
+}
+
+/// Whether a name is a built-in command or one of its aliases, which user-defined aliases can't replace
+fn is_builtin_command(name: &str) -> bool {
+    use clap::CommandFactory;
+
+    JustEnoughVcsWorkspace::command()
+        .find_subcommand(name)
+        .is_some()
+}
+
+/// Names of the sheets known to the workspace, from the latest information of the current account
+async fn known_sheet_names(local_cfg: &LocalConfig) -> Vec<String> {
+    let Some(local_dir) = current_local_path() else {
+        return Vec::new();
+    };
+    let Ok(latest_info) = LatestInfo::read_from(LatestInfo::latest_info_path(
+        &local_dir,
+        &local_cfg.current_account(),
+    ))
+    .await
+    else {
+        return Vec::new();
+    };
+
+    latest_info
+        .visible_sheets
+        .iter()
+        .chain(latest_info.reference_sheets.iter())
+        .chain(latest_info.invisible_sheets.iter().map(|s| &s.sheet_name))
+        .cloned()
+        .collect()
+}
+
+/// Print a tip if one of the candidates looks like what the user meant to type
+fn print_did_you_mean<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) {
+    if let Some(name) = closest_match(input, candidates) {
+        eprintln!("{}", t!("jv.tip.did_you_mean", name = name).trim().yellow());
+    }
+}
+
+/// Run the pre hook of a command from the workspace root, the command is aborted if the hook fails
+async fn run_pre_hook(command: &str, paths: &[PathBuf]) -> CmdResult {
+    let Some(local_dir) = current_local_path() else {
+        return Ok(());
+    };
+
+    let context = HookContext {
+        hook: HookStage::Pre.hook_name(command),
+        command: command.to_string(),
+        workspace: local_dir.clone(),
+        paths: paths.to_vec(),
+        exit_code: None,
+    };
+    match run_hook(&local_dir, &context).await {
+        Ok(None) => Ok(()),
+        Ok(Some(output)) if output.success => {
+            print_hook_output(&context.hook, &output);
+            Ok(())
+        }
+        Ok(Some(output)) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.hook.pre_failed",
+                    hook = &context.hook,
+                    command = command,
+                    code = hook_exit_code_str(&output),
+                    output = output.output
+                ))
+            );
+            Err(CmdError::Rejected)
+        }
+        Err(e) => {
+            eprintln!(
+                "{}",
+                md(t!(
+                    "jv.fail.hook.run_failed",
+                    hook = &context.hook,
+                    error = e
+                ))
+            );
+            Err(CmdError::Failed)
+        }
+    }
+}
+
+/// Files of the sheet in use with their virtual file and its latest version, to tell what `jv update` changed
+async fn sheet_file_versions() -> HashMap<PathBuf, (VirtualFileId, Option<VirtualFileVersion>)> {
+    let _ = correct_current_dir();
+    let Ok(local_cfg) = LocalConfig::read().await else {
+        return HashMap::new();
+    };
+    let Some(sheet_name) = local_cfg.sheet_in_use().clone() else {
+        return HashMap::new();
+    };
+    let Ok(cached_sheet) = CachedSheet::cached_sheet_data(&sheet_name).await else {
+        return HashMap::new();
+    };
+    let latest_file_data = match LatestFileData::data_path(&local_cfg.current_account()) {
+        Ok(path) => LatestFileData::read_from(&path).await.ok(),
+        Err(_) => None,
+    };
+
+    cached_sheet
+        .mapping()
+        .iter()
+        .map(|(path, mapping)| {
+            let version = latest_file_data
+                .as_ref()
+                .and_then(|data| data.file_version(&mapping.id).cloned());
+            (path.clone(), (mapping.id.clone(), version))
+        })
+        .collect()
+}
+
+/// Run the post hook of a command from the workspace root with the result of the command,
+/// a failed post hook is only reported
+async fn run_post_hook(command: &str, paths: &[PathBuf], result: &CmdResult) {
+    let Some(local_dir) = current_local_path() else {
+        return;
     };
 
-    Some(ConnectionInstance::from(stream))
+    let context = HookContext {
+        hook: HookStage::Post.hook_name(command),
+        command: command.to_string(),
+        workspace: local_dir.clone(),
+        paths: paths.to_vec(),
+        exit_code: Some(match result {
+            Ok(()) => 0,
+            Err(e) => e.exit_code(),
+        }),
+    };
+    match run_hook(&local_dir, &context).await {
+        Ok(None) => {}
+        Ok(Some(output)) if output.success => print_hook_output(&context.hook, &output),
+        Ok(Some(output)) => eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.hook.post_failed",
+                hook = &context.hook,
+                code = hook_exit_code_str(&output),
+                output = output.output
+            ))
+        ),
+        Err(e) => eprintln!(
+            "{}",
+            md(t!(
+                "jv.fail.hook.run_failed",
+                hook = &context.hook,
+                error = e
+            ))
+        ),
+    }
+}
+
+/// Print the output of a successful hook, to stderr to keep the json output clean
+fn print_hook_output(hook: &str, output: &HookOutput) {
+    if !output.output.is_empty() {
+        eprintln!(
+            "{}",
+            md(t!(
+                "jv.result.hook.output",
+                hook = hook,
+                output = output.output
+            ))
+        );
+    }
+}
+
+fn hook_exit_code_str(output: &HookOutput) -> String {
+    output
+        .code
+        .map(|code| code.to_string())
+        .unwrap_or("?".to_string())
 }
 
-// Check if the workspace is stained and has a valid configuration
-// Returns LocalConfig if valid, None otherwise
-async fn precheck() -> Option<LocalConfig> {
+/// Check if the workspace is stained and has a valid configuration
+/// Returns LocalConfig if valid, the reason of the failure otherwise
+async fn precheck() -> Result<LocalConfig, CmdError> {
     let Some(local_dir) = current_local_path() else {
         eprintln!("{}", t!("jv.fail.workspace_not_found").trim());
-        return None;
+        return Err(CmdError::NotWorkspace);
     };
 
     if let Err(e) = set_current_dir(&local_dir) {
@@ -6024,27 +9730,27 @@ async fn precheck() -> Option<LocalConfig> {
                 error = e
             )
         );
-        return None;
+        return Err(CmdError::Failed);
     }
 
     let Ok(local_config) = LocalConfig::read().await else {
         eprintln!("{}", md(t!("jv.fail.cfg_not_found.local_config")));
-        return None;
+        return Err(CmdError::ConfigNotFound);
     };
 
     if !local_config.stained() {
         eprintln!("{}", md(t!("jv.fail.not_stained")));
-        return None;
+        return Err(CmdError::NotStained);
     }
 
-    Some(local_config)
+    Ok(local_config)
 }
 
 /// Build action pool and context for upstream communication
-/// Returns Some((ActionPool, ActionContext)) if successful, None otherwise
+/// Returns (ActionPool, ActionContext) if successful, the reason of the failure otherwise
 async fn build_pool_and_ctx(
     local_config: &LocalConfig,
-) -> Option<(ActionPool, ActionContext, Receiver<String>)> {
+) -> Result<(ActionPool, ActionContext, Receiver<String>), CmdError> {
     let pool = client_registry::client_action_pool();
     let upstream = local_config.upstream_addr();
 
@@ -6057,7 +9763,7 @@ async fn build_pool_and_ctx(
     let (tx, rx) = mpsc::channel::<String>(100);
     ctx.insert_arc_data(Arc::new(tx));
 
-    Some((pool, ctx, rx))
+    Ok((pool, ctx, rx))
 }
 
 /// Sort paths in a vector of strings.
@@ -6163,6 +9869,98 @@ fn truncate_first_line(content: String) -> String {
     }
 }
 
+/// Print the operations a command would perform, without performing them
+fn print_dry_run(operations: Vec<DryRunOperation>, json_output: bool, pretty: bool) {
+    if json_output {
+        print_json(DryRunJsonResult { operations }, pretty);
+        return;
+    }
+
+    if operations.is_empty() {
+        println!("{}", md(t!("jv.dry_run.nothing")));
+        return;
+    }
+
+    let mut lines = vec![t!("jv.dry_run.header").trim().to_string()];
+    for operation in operations {
+        let line = match operation {
+            DryRunOperation::MoveMapping { from, to } => t!(
+                "jv.dry_run.move_mapping",
+                from = from.display(),
+                to = to.display()
+            ),
+            DryRunOperation::EraseMapping { mapping } => {
+                t!("jv.dry_run.erase_mapping", mapping = mapping.display())
+            }
+            DryRunOperation::MoveLocal { from, to } => t!(
+                "jv.dry_run.move_local",
+                from = from.display(),
+                to = to.display()
+            ),
+            DryRunOperation::BreakMove { mapping } => {
+                t!("jv.dry_run.break_move", mapping = mapping.display())
+            }
+            DryRunOperation::RemoveMapping { mapping } => {
+                t!("jv.dry_run.remove_mapping", mapping = mapping.display())
+            }
+            DryRunOperation::MatchLost { lost, created } => t!(
+                "jv.dry_run.match_lost",
+                lost = lost.display(),
+                created = created.display()
+            ),
+            DryRunOperation::CreateFile { path } => {
+                t!("jv.dry_run.create_file", path = path.display())
+            }
+            DryRunOperation::UpdateFile {
+                path,
+                current_version,
+                next_version,
+                description,
+            } => {
+                lines.push(
+                    t!(
+                        "jv.dry_run.update_file",
+                        path = path.display(),
+                        current_version = current_version,
+                        next_version = next_version.unwrap_or("?".to_string())
+                    )
+                    .to_string(),
+                );
+                match description {
+                    Some(description) => t!(
+                        "jv.dry_run.update_file_description",
+                        description = truncate_first_line(description)
+                    ),
+                    None => t!("jv.dry_run.update_file_no_description"),
+                }
+            }
+            DryRunOperation::SyncFile { path, version } => t!(
+                "jv.dry_run.sync_file",
+                path = path.display(),
+                version = version.unwrap_or("?".to_string())
+            ),
+            DryRunOperation::SkipSync { path } => {
+                t!("jv.dry_run.skip_sync", path = path.display())
+            }
+            DryRunOperation::ShareMapping { mapping, to_sheet } => t!(
+                "jv.dry_run.share_mapping",
+                mapping = mapping.display(),
+                to_sheet = to_sheet
+            ),
+            DryRunOperation::ImportMapping { mapping, from } => t!(
+                "jv.dry_run.import_mapping",
+                mapping = mapping.display(),
+                from = from
+            ),
+            DryRunOperation::RejectShare { share_id } => {
+                t!("jv.dry_run.reject_share", share_id = share_id)
+            }
+        };
+        lines.push(line.to_string());
+    }
+    println!("{}", md(lines.join("\n")));
+}
+
 fn print_json<T: serde::Serialize>(obj: T, pretty: bool) {
     let result = if pretty {
         serde_json::to_string_pretty(&obj)
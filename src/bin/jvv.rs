@@ -18,6 +18,7 @@ use just_enough_vcs::{
 use just_enough_vcs_cli::{
     data::compile_info::CompileInfo,
     utils::{
+        cmd_error::{CmdError, CmdResult},
         display::{md, size_str},
         env::current_locales,
         logger::build_env_logger,
@@ -188,6 +189,12 @@ struct ListenArgs {
 
 #[tokio::main]
 async fn main() {
+    if let Err(err) = run().await {
+        std::process::exit(err.exit_code());
+    }
+}
+
+async fn run() -> CmdResult {
     // Init i18n
     set_locale(&current_locales());
 
@@ -197,7 +204,7 @@ async fn main() {
 
     let Ok(parser) = JustEnoughVcsVault::try_parse() else {
         println!("{}", md(t!("jvv.help")));
-        return;
+        return Err(CmdError::Usage);
     };
 
     match parser.command {
@@ -236,30 +243,30 @@ async fn main() {
         JustEnoughVcsVaultCommand::Here(here_args) => {
             if here_args.help {
                 println!("{}", md(t!("jvv.here")));
-                return;
+                return Ok(());
             }
-            jvv_here(here_args).await;
+            jvv_here(here_args).await?;
         }
         JustEnoughVcsVaultCommand::Create(create_vault_args) => {
             if create_vault_args.help {
                 println!("{}", md(t!("jvv.create")));
-                return;
+                return Ok(());
             }
-            jvv_create(create_vault_args).await;
+            jvv_create(create_vault_args).await?;
         }
         JustEnoughVcsVaultCommand::Init(init_vault_args) => {
             if init_vault_args.help {
                 println!("{}", md(t!("jvv.init")));
-                return;
+                return Ok(());
             }
-            jvv_init(init_vault_args).await;
+            jvv_init(init_vault_args).await?;
         }
         JustEnoughVcsVaultCommand::Member(member_manage) => {
             let vault_cfg = match VaultConfig::read().await {
                 Ok(cfg) => cfg,
                 Err(_) => {
                     eprintln!("{}", t!("jvv.fail.no_vault_here").trim());
-                    return;
+                    return Err(CmdError::NotWorkspace);
                 }
             };
 
@@ -270,7 +277,7 @@ async fn main() {
                         "{}",
                         t!("jvv.fail.jvcs", err = "Failed to initialize vault")
                     );
-                    return;
+                    return Err(CmdError::Failed);
                 }
             };
 
@@ -278,27 +285,27 @@ async fn main() {
                 MemberManage::Register(member_register_args) => {
                     if member_register_args.help {
                         println!("{}", md(t!("jvv.member")));
-                        return;
+                        return Ok(());
                     }
-                    jvv_member_register(vault, member_register_args).await;
+                    jvv_member_register(vault, member_register_args).await?;
                 }
                 MemberManage::Remove(member_remove_args) => {
                     if member_remove_args.help {
                         println!("{}", md(t!("jvv.member")));
-                        return;
+                        return Ok(());
                     }
-                    jvv_member_remove(vault, member_remove_args).await;
+                    jvv_member_remove(vault, member_remove_args).await?;
                 }
                 MemberManage::List(member_list_args) => {
                     if member_list_args.help {
                         println!("{}", md(t!("jvv.member")));
-                        return;
+                        return Ok(());
                     }
-                    jvv_member_list(vault, member_list_args).await;
+                    jvv_member_list(vault, member_list_args).await?;
                 }
                 MemberManage::Help => {
                     println!("{}", md(t!("jvv.member")));
-                    return;
+                    return Ok(());
                 }
             }
         }
@@ -306,29 +313,29 @@ async fn main() {
             ServiceManage::Listen(listen_args) => {
                 if listen_args.help {
                     println!("{}", md(t!("jvv.service")));
-                    return;
+                    return Ok(());
                 }
-                jvv_service_listen(listen_args).await;
+                jvv_service_listen(listen_args).await?;
             }
             ServiceManage::Help => {
                 println!("{}", md(t!("jvv.service")));
-                return;
+                return Ok(());
             }
         },
         // Short commands
         JustEnoughVcsVaultCommand::ServiceListen(listen_args) => {
             if listen_args.help {
                 println!("{}", md(t!("jvv.service")));
-                return;
+                return Ok(());
             }
-            jvv_service_listen(listen_args).await;
+            jvv_service_listen(listen_args).await?;
         }
         JustEnoughVcsVaultCommand::MemberList => {
             let vault_cfg = match VaultConfig::read().await {
                 Ok(cfg) => cfg,
                 Err(_) => {
                     eprintln!("{}", t!("jvv.fail.no_vault_here").trim());
-                    return;
+                    return Err(CmdError::NotWorkspace);
                 }
             };
 
@@ -339,7 +346,7 @@ async fn main() {
                         "{}",
                         t!("jvv.fail.jvcs", err = "Failed to initialize vault")
                     );
-                    return;
+                    return Err(CmdError::Failed);
                 }
             };
             jvv_member_list(
@@ -349,29 +356,31 @@ async fn main() {
                     raw: false,
                 },
             )
-            .await;
+            .await?;
         }
     }
+
+    Ok(())
 }
 
-async fn jvv_here(_args: HereArgs) {
+async fn jvv_here(_args: HereArgs) -> CmdResult {
     let Some(current_vault) = current_vault_path() else {
         eprintln!("{}", t!("jvv.fail.here.cfg_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     // Read vault cfg
     let vault_cfg_file = current_vault.join(SERVER_FILE_VAULT);
     if !vault_cfg_file.exists() {
         eprintln!("{}", t!("jvv.fail.here.cfg_not_found").trim());
-        return;
+        return Err(CmdError::ConfigNotFound);
     }
 
     let vault_cfg = match VaultConfig::read().await {
         Ok(cfg) => cfg,
         Err(_) => {
             eprintln!("{}", t!("jvv.fail.here.cfg_not_found").trim());
-            return;
+            return Err(CmdError::ConfigNotFound);
         }
     };
 
@@ -381,7 +390,7 @@ async fn jvv_here(_args: HereArgs) {
     // Get vault
     let Some(vault) = Vault::init(vault_cfg, current_vault) else {
         eprintln!("{}", t!("jvv.fail.here.vault_init_failed").trim());
-        return;
+        return Err(CmdError::Failed);
     };
 
     // Get sheet count
@@ -436,7 +445,7 @@ async fn jvv_here(_args: HereArgs) {
                 "{}",
                 t!("jvv.fail.here.size_calc_error", error = e.to_string()).trim()
             );
-            return;
+            return Err(CmdError::Failed);
         }
         Err(_) => {
             // Timeout occurred
@@ -450,7 +459,7 @@ async fn jvv_here(_args: HereArgs) {
         Ok(ids) => ids.len(),
         Err(_) => {
             eprintln!("{}", t!("jvv.fail.here.member_ids_failed").trim());
-            return;
+            return Err(CmdError::Failed);
         }
     };
 
@@ -460,7 +469,7 @@ async fn jvv_here(_args: HereArgs) {
         Ok(ids) => ids,
         Err(_) => {
             eprintln!("{}", t!("jvv.fail.here.member_ids_failed").trim());
-            return;
+            return Err(CmdError::Failed);
         }
     };
 
@@ -475,7 +484,7 @@ async fn jvv_here(_args: HereArgs) {
         Ok(sheet) => sheet,
         Err(_) => {
             eprintln!("{}", t!("jvv.fail.here.ref_sheet_not_found").trim());
-            return;
+            return Err(CmdError::NotFound);
         }
     };
     let num_ref_sheet_managed_files = ref_sheet.mapping().len();
@@ -493,22 +502,24 @@ async fn jvv_here(_args: HereArgs) {
             num_ref_sheet_managed_files = num_ref_sheet_managed_files,
             total_size = size_str(total_size as usize)
         ))
-    )
+    );
+
+    Ok(())
 }
 
-async fn jvv_init(_args: InitVaultArgs) {
+async fn jvv_init(_args: InitVaultArgs) -> CmdResult {
     let current_dir = match std::env::current_dir() {
         Ok(dir) => dir,
         Err(_) => {
             eprintln!("{}", t!("jvv.fail.std.current_dir").trim());
-            return;
+            return Err(CmdError::Failed);
         }
     };
     if let Ok(mut entries) = current_dir.read_dir()
         && entries.next().is_some()
     {
         eprintln!("{}", t!("jvv.fail.init.not_empty"));
-        return;
+        return Err(CmdError::Rejected);
     }
 
     // Setup vault
@@ -516,29 +527,31 @@ async fn jvv_init(_args: InitVaultArgs) {
         Some(name) => name.to_string_lossy().to_string(),
         None => {
             eprintln!("{}", t!("jvv.fail.std.current_dir_name").trim());
-            return;
+            return Err(CmdError::Failed);
         }
     };
     let vault_name = pascal_case!(vault_name);
 
     if let Err(err) = Vault::setup_vault(current_dir.clone(), vault_name).await {
         eprintln!("{}", t!("jvv.fail.jvcs", err = err.to_string()));
-        return;
+        return Err(CmdError::Failed);
     }
 
     // Success
     println!(
         "{}",
         t!("jvv.success.init", name = current_dir.to_string_lossy())
-    )
+    );
+
+    Ok(())
 }
 
-async fn jvv_create(args: CreateVaultArgs) {
+async fn jvv_create(args: CreateVaultArgs) -> CmdResult {
     let current_dir = match std::env::current_dir() {
         Ok(dir) => dir,
         Err(_) => {
             eprintln!("{}", t!("jvv.fail.std.current_dir").trim());
-            return;
+            return Err(CmdError::Failed);
         }
     };
     let target_dir = current_dir.join(args.vault_name.clone());
@@ -552,56 +565,62 @@ async fn jvv_create(args: CreateVaultArgs) {
                 dir = target_dir.to_string_lossy()
             )
         );
-        return;
+        return Err(CmdError::Failed);
     }
 
     if let Ok(mut entries) = target_dir.read_dir()
         && entries.next().is_some()
     {
         eprintln!("{}", t!("jvv.fail.create.not_empty"));
-        return;
+        return Err(CmdError::Rejected);
     }
 
     // Setup vault
     let vault_name = pascal_case!(args.vault_name);
     if let Err(err) = Vault::setup_vault(target_dir.clone(), vault_name).await {
         eprintln!("{}", t!("jvv.fail.jvcs", err = err.to_string()));
-        return;
+        return Err(CmdError::Failed);
     }
 
     // Success
     println!(
         "{}",
         t!("jvv.success.create", name = target_dir.to_string_lossy())
-    )
+    );
+
+    Ok(())
 }
 
-async fn jvv_member_register(vault: Vault, args: MemberRegisterArgs) {
+async fn jvv_member_register(vault: Vault, args: MemberRegisterArgs) -> CmdResult {
     let register = vault
         .register_member_to_vault(Member::new(args.member_name.clone()))
         .await;
 
     if register.is_err() {
         eprintln!("{}", t!("jvv.fail.member.register").trim());
-        return;
+        return Err(CmdError::Failed);
     }
 
     println!(
         "{}",
         t!("jvv.success.member.register", member = args.member_name)
-    )
+    );
+
+    Ok(())
 }
 
-async fn jvv_member_remove(vault: Vault, args: MemberRemoveArgs) {
+async fn jvv_member_remove(vault: Vault, args: MemberRemoveArgs) -> CmdResult {
     let _ = vault.remove_member_from_vault(&args.member_name);
 
     println!(
         "{}",
         t!("jvv.success.member.remove", member = args.member_name)
-    )
+    );
+
+    Ok(())
 }
 
-async fn jvv_member_list(vault: Vault, args: MemberListArgs) {
+async fn jvv_member_list(vault: Vault, args: MemberListArgs) -> CmdResult {
     // Get id list
     let ids = match vault.member_ids() {
         Ok(ids) => ids,
@@ -609,7 +628,7 @@ async fn jvv_member_list(vault: Vault, args: MemberListArgs) {
             if !args.raw {
                 eprintln!("{}", t!("jvv.fail.member.list").trim());
             }
-            return;
+            return Err(CmdError::Failed);
         }
     };
 
@@ -658,17 +677,19 @@ async fn jvv_member_list(vault: Vault, args: MemberListArgs) {
             md(t!("jvv.success.member.list.footer", num = has_pubkey))
         );
     }
+
+    Ok(())
 }
 
-async fn jvv_service_listen(args: ListenArgs) {
+async fn jvv_service_listen(args: ListenArgs) -> CmdResult {
     let Some(current_vault) = current_vault_path() else {
         eprintln!("{}", t!("jvv.fail.here.cfg_not_found").trim());
-        return;
+        return Err(CmdError::NotWorkspace);
     };
 
     let Ok(vault_cfg) = VaultConfig::read().await else {
         eprintln!("{}", t!("jvv.fail.here.cfg_not_found").trim());
-        return;
+        return Err(CmdError::ConfigNotFound);
     };
 
     let show_logger = if args.no_log && !args.show_log {
@@ -680,7 +701,7 @@ async fn jvv_service_listen(args: ListenArgs) {
         vault_cfg.server_config().is_logger_enabled()
     } else {
         eprintln!("{}", md(t!("jvv.fail.service.wtf_show_log_and_no_log")));
-        return;
+        return Err(CmdError::Usage);
     };
 
     if show_logger {
@@ -693,7 +714,7 @@ async fn jvv_service_listen(args: ListenArgs) {
                     dir = logs_dir.to_string_lossy()
                 )
             );
-            return;
+            return Err(CmdError::Failed);
         }
         let now = chrono::Local::now();
         let log_filename = format!("log_{}.txt", now.format("%Y-%m-%d-%H-%M-%S"));
@@ -717,12 +738,14 @@ async fn jvv_service_listen(args: ListenArgs) {
     match server_entry(current_vault, port).await {
         Ok(_) => {
             info!("{}", t!("jvv.success.service.listen_done").trim());
+            Ok(())
         }
         Err(e) => {
             error!(
                 "{}",
                 t!("jvv.fail.service.listen_done", error = e.to_string()).trim()
             );
+            Err(CmdError::Failed)
         }
     }
 }
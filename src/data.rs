@@ -1,4 +1,6 @@
 #[allow(dead_code)]
 pub mod compile_info;
 
+pub mod base_snapshot;
+pub mod config;
 pub mod ipaddress_history;
@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use just_enough_vcs::vcs::{
+    constants::CLIENT_PATH_WORKSPACE_ROOT,
+    data::vault::virtual_file::{VirtualFileId, VirtualFileVersion},
+};
+
+const BASE_SNAPSHOT_DIR: &str = ".base";
+
+/// Path of the snapshot of a virtual file at the given version
+pub fn base_snapshot_path(
+    local_dir: &Path,
+    vfid: &VirtualFileId,
+    version: &VirtualFileVersion,
+) -> PathBuf {
+    local_dir
+        .join(CLIENT_PATH_WORKSPACE_ROOT)
+        .join(BASE_SNAPSHOT_DIR)
+        .join(vfid)
+        .join(version)
+}
+
+/// Number of snapshots kept for each virtual file, the least recently saved ones are removed
+const BASE_SNAPSHOT_KEEP: usize = 5;
+
+/// Keep a copy of `file` as the snapshot of the version it was synced at.
+/// Snapshots of a few earlier versions are kept, so they can still be checked out
+pub async fn save_base_snapshot(
+    local_dir: &Path,
+    vfid: &VirtualFileId,
+    version: &VirtualFileVersion,
+    file: &Path,
+) -> Result<(), std::io::Error> {
+    let snapshot = base_snapshot_path(local_dir, vfid, version);
+    let Some(dir) = snapshot.parent() else {
+        return Ok(());
+    };
+    tokio::fs::create_dir_all(dir).await?;
+    tokio::fs::copy(file, &snapshot).await?;
+
+    // Copying doesn't keep the modification time, so the new snapshot is the most recent one
+    let mut snapshots = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let modified = entry.metadata().await?.modified()?;
+        snapshots.push((modified, entry.path()));
+    }
+    snapshots.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    for (_, path) in snapshots.into_iter().skip(BASE_SNAPSHOT_KEEP) {
+        if path != snapshot {
+            tokio::fs::remove_file(path).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Read the snapshot content of a virtual file at the given version, if it was kept
+pub async fn read_base_snapshot(
+    local_dir: &Path,
+    vfid: &VirtualFileId,
+    version: &VirtualFileVersion,
+) -> Option<Vec<u8>> {
+    tokio::fs::read(base_snapshot_path(local_dir, vfid, version))
+        .await
+        .ok()
+}
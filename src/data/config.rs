@@ -0,0 +1,54 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use just_enough_vcs::vcs::{
+    constants::CLIENT_PATH_WORKSPACE_ROOT,
+    current::{current_cfg_dir, current_local_path},
+};
+
+use crate::utils::config::{Config, ConfigTable};
+
+const CONFIG_FILE: &str = "config.toml";
+
+static CURRENT_CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Path of the configuration of the current user, in the config directory
+pub fn user_config_path() -> Option<PathBuf> {
+    current_cfg_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+/// Path of the configuration of a workspace
+pub fn workspace_config_path(local_dir: &Path) -> PathBuf {
+    local_dir.join(CLIENT_PATH_WORKSPACE_ROOT).join(CONFIG_FILE)
+}
+
+/// Read a configuration file, an empty configuration if the file doesn't exist
+pub fn read_config_file(path: &Path) -> Result<ConfigTable, toml::de::Error> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => ConfigTable::parse(&content),
+        Err(_) => Ok(ConfigTable::default()),
+    }
+}
+
+/// Write a configuration file, creating its directory if needed
+pub fn write_config_file(path: &Path, config: &ConfigTable) -> Result<(), std::io::Error> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, config.to_string())
+}
+
+/// Configuration of the current user and the current workspace, read once per process.
+/// Invalid configuration files are skipped, `jv config` reports them
+pub fn current_config() -> &'static Config {
+    CURRENT_CONFIG.get_or_init(|| Config {
+        user: user_config_path()
+            .and_then(|path| read_config_file(&path).ok())
+            .unwrap_or_default(),
+        workspace: current_local_path()
+            .and_then(|dir| read_config_file(&workspace_config_path(&dir)).ok())
+            .unwrap_or_default(),
+    })
+}
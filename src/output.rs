@@ -1,7 +1,16 @@
 pub mod accounts;
 pub mod align;
 pub mod analyzer_result;
+pub mod config;
+pub mod diff;
+pub mod direct;
+pub mod dry_run;
+pub mod edit_right;
 pub mod here;
 pub mod info;
+pub mod log;
+pub mod mapping_edit;
 pub mod share;
 pub mod sheets;
+pub mod track;
+pub mod update;
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ConfigListJsonResult {
+    pub result: Vec<ConfigItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ConfigItem {
+    pub key: String,
+    pub value: String,
+
+    /// Layer the value comes from: `default`, `system`, `user`, `workspace` or `env`
+    pub layer: String,
+}
@@ -0,0 +1,32 @@
+use just_enough_vcs::vcs::data::vault::virtual_file::{VirtualFileId, VirtualFileVersion};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DiffJsonResult {
+    pub items: Vec<DiffItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DiffItem {
+    pub mapping: String,
+    pub vfid: VirtualFileId,
+    pub base_version: VirtualFileVersion,
+    pub status: DiffStatus,
+    pub is_binary: bool,
+    pub base_hash: String,
+    pub current_hash: String,
+    pub base_size: Option<u64>,
+    pub current_size: Option<u64>,
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub enum DiffStatus {
+    Unchanged,
+    Modified,
+    Lost,
+    BaseNotCached,
+}
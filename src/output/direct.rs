@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DirectJsonResult {
+    pub upstream: String,
+    pub result: DirectResult,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum DirectResult {
+    #[default]
+    DirectedAndStained,
+    Redirected,
+    AlreadyStained,
+    AuthorizeFailed(String),
+    RedirectFailed(String),
+    SameUpstream,
+    Unknown,
+}
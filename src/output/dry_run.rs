@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use just_enough_vcs::vcs::data::vault::virtual_file::VirtualFileVersion;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DryRunJsonResult {
+    pub operations: Vec<DryRunOperation>,
+}
+
+/// An operation a command would perform, computed without connecting to the upstream vault
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", rename_all_fields = "PascalCase")]
+pub enum DryRunOperation {
+    /// Move an upstream mapping
+    MoveMapping { from: PathBuf, to: PathBuf },
+
+    /// Erase an upstream mapping
+    EraseMapping { mapping: PathBuf },
+
+    /// Move a local file
+    MoveLocal { from: PathBuf, to: PathBuf },
+
+    /// Break the detected move of a local file
+    BreakMove { mapping: PathBuf },
+
+    /// Remove a mapping from the local sheet
+    RemoveMapping { mapping: PathBuf },
+
+    /// Match a lost mapping with a created file
+    MatchLost { lost: PathBuf, created: PathBuf },
+
+    /// Create a new file in the upstream vault
+    CreateFile { path: PathBuf },
+
+    /// Upload a new version of a held file
+    UpdateFile {
+        path: PathBuf,
+        current_version: VirtualFileVersion,
+        next_version: Option<VirtualFileVersion>,
+        description: Option<String>,
+    },
+
+    /// Download the latest version of a file
+    SyncFile {
+        path: PathBuf,
+        version: Option<VirtualFileVersion>,
+    },
+
+    /// Skip syncing a file, because it has local edits
+    SkipSync { path: PathBuf },
+
+    /// Share a mapping to another sheet
+    ShareMapping { mapping: PathBuf, to_sheet: String },
+
+    /// Import a mapping into the current sheet, from a sheet or a share
+    ImportMapping { mapping: PathBuf, from: String },
+
+    /// Reject a share
+    RejectShare { share_id: String },
+}
@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use just_enough_vcs::vcs::data::member::MemberId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EditRightJsonResult {
+    pub result: EditRightResult,
+    pub held: Vec<PathBuf>,
+    pub thrown: Vec<PathBuf>,
+    pub failed: Vec<EditRightFailedItem>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EditRightResult {
+    #[default]
+    Success,
+    CheckFailed,
+    AuthorizeFailed(String),
+    DoNothing,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EditRightFailedItem {
+    pub path: PathBuf,
+    pub reason: EditRightFailReason,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum EditRightFailReason {
+    NotFoundInSheet,
+    NotATrackedFile,
+    BaseVersionUnmatch,
+    HasHolder(MemberId),
+    AlreadyHeld,
+    NotHolder,
+    AlreadyModified,
+}
@@ -0,0 +1,22 @@
+use just_enough_vcs::vcs::data::{
+    member::MemberId,
+    vault::virtual_file::{VirtualFileId, VirtualFileVersion},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LogJsonResult {
+    pub entries: Vec<LogEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LogEntry {
+    pub mapping: String,
+    pub vfid: VirtualFileId,
+    pub version: VirtualFileVersion,
+    pub creator: MemberId,
+    pub description: String,
+    pub is_current_version: bool,
+}
@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MappingEditJsonResult {
+    pub result: MappingEditResult,
+    pub operations: Vec<MappingEditItem>,
+    pub local_rename_failed: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MappingEditItem {
+    pub from: PathBuf,
+    pub to: Option<PathBuf>,
+    pub erase: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum MappingEditResult {
+    #[default]
+    Success,
+    AuthorizeFailed(String),
+    MappingNotFound(PathBuf),
+    MoveOperationButNoTarget(PathBuf),
+    ContainsDuplicateMapping(PathBuf),
+    EditNotAllowed,
+    Unknown,
+}
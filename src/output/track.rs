@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use just_enough_vcs::vcs::data::vault::virtual_file::{VirtualFileId, VirtualFileVersion};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TrackJsonResult {
+    pub result: TrackResult,
+    pub created: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+    pub synced: Vec<PathBuf>,
+    pub skipped: Vec<PathBuf>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum TrackResult {
+    #[default]
+    Done,
+    AuthorizeFailed(String),
+    StructureChangesNotSolved,
+    CreateFileOnExistPath(PathBuf),
+    SheetNotFound(String),
+    MappingNotFound(PathBuf),
+    VirtualFileNotFound(VirtualFileId),
+    VirtualFileReadFailed(VirtualFileId),
+    NotHeld(PathBuf),
+    #[serde(rename_all = "PascalCase")]
+    VersionDismatch {
+        path: PathBuf,
+        current: VirtualFileVersion,
+        latest: VirtualFileVersion,
+    },
+    UpdateButNoDescription(PathBuf),
+    #[serde(rename_all = "PascalCase")]
+    VersionAlreadyExist {
+        path: PathBuf,
+        version: VirtualFileVersion,
+    },
+}
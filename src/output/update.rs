@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UpdateJsonResult {
+    pub result: UpdateResult,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum UpdateResult {
+    #[default]
+    Success,
+    AuthorizeFailed(String),
+    PathAlreadyExist(PathBuf),
+}
@@ -1,8 +1,17 @@
+pub mod alias;
+pub mod cmd_error;
+pub mod config;
+pub mod diff;
 pub mod display;
 pub mod env;
 pub mod fs;
 pub mod globber;
+pub mod hooks;
+pub mod ignore;
 pub mod input;
 pub mod logger;
+pub mod progress;
 pub mod push_version;
+pub mod similarity;
 pub mod socket_addr_helper;
+pub mod update_desc;
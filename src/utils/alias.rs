@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, HashSet};
+
+/// Command aliases, from `name = "command"` entries, e.g. `sync = "update && status"`.
+/// Later entries take precedence
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Aliases {
+    aliases: BTreeMap<String, String>,
+}
+
+/// Why the aliases of a command line couldn't be expanded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasError {
+    /// The alias expands to itself, directly or through other aliases
+    Recursive(String),
+
+    /// The command of the alias is empty, or has an unclosed quote
+    Invalid(String),
+}
+
+/// Whether a name can be used for an alias, it must not look like an option or contain spaces or dots
+pub fn is_alias_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && !name.contains(|c: char| c.is_whitespace() || c == '.')
+}
+
+impl FromIterator<(String, String)> for Aliases {
+    /// Collect the aliases, skipping the ones with an invalid name
+    fn from_iter<T: IntoIterator<Item = (String, String)>>(iter: T) -> Self {
+        let aliases = iter
+            .into_iter()
+            .filter(|(name, _)| is_alias_name(name))
+            .map(|(name, command)| (name, command.trim().to_string()))
+            .collect();
+        Self { aliases }
+    }
+}
+
+impl Aliases {
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.aliases.keys().map(|name| name.as_str())
+    }
+
+    /// Expand the alias used as the command of `args` into the command lines to run in order.
+    /// `args` starts with the program name, which is kept for every command line,
+    /// and the arguments after the alias are appended to its last command.
+    /// Aliases named like a built-in command, as told by `is_builtin`, are never used
+    pub fn expand(
+        &self,
+        args: Vec<String>,
+        is_builtin: impl Fn(&str) -> bool,
+    ) -> Result<Vec<Vec<String>>, AliasError> {
+        let mut args = args.into_iter();
+        let Some(program) = args.next() else {
+            return Ok(vec![Vec::new()]);
+        };
+
+        let commands = self.expand_command(args.collect(), &is_builtin, &mut HashSet::new())?;
+        Ok(commands
+            .into_iter()
+            .map(|command| std::iter::once(program.clone()).chain(command).collect())
+            .collect())
+    }
+
+    fn expand_command(
+        &self,
+        args: Vec<String>,
+        is_builtin: &impl Fn(&str) -> bool,
+        expanding: &mut HashSet<String>,
+    ) -> Result<Vec<Vec<String>>, AliasError> {
+        let Some(name) = args.first().filter(|name| !is_builtin(name)) else {
+            return Ok(vec![args]);
+        };
+        let Some(command) = self.aliases.get(name) else {
+            return Ok(vec![args]);
+        };
+        let name = name.clone();
+
+        if !expanding.insert(name.clone()) {
+            return Err(AliasError::Recursive(name));
+        }
+
+        let mut commands = match split_commands(command) {
+            Some(commands) if commands.iter().all(|command| !command.is_empty()) => commands,
+            _ => return Err(AliasError::Invalid(name)),
+        };
+        if let Some(last) = commands.last_mut() {
+            last.extend(args.into_iter().skip(1));
+        }
+
+        let mut expanded = Vec::new();
+        for command in commands {
+            expanded.extend(self.expand_command(command, is_builtin, expanding)?);
+        }
+        expanding.remove(&name);
+        Ok(expanded)
+    }
+}
+
+/// Split a command line into commands separated by `&&`, and each command into words.
+/// Words may be quoted with `"` or `'`. Returns `None` if a quote is not closed
+pub fn split_commands(line: &str) -> Option<Vec<Vec<String>>> {
+    let mut commands = vec![Vec::new()];
+    let mut word: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_default().push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_default();
+            }
+            (None, '&') if chars.peek() == Some(&'&') => {
+                chars.next();
+                if let Some(word) = word.take() {
+                    commands.last_mut()?.push(word);
+                }
+                commands.push(Vec::new());
+            }
+            (None, c) if c.is_whitespace() => {
+                if let Some(word) = word.take() {
+                    commands.last_mut()?.push(word);
+                }
+            }
+            (None, c) => word.get_or_insert_default().push(c),
+        }
+    }
+
+    if quote.is_some() {
+        return None;
+    }
+    if let Some(word) = word {
+        commands.last_mut()?.push(word);
+    }
+    Some(commands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_split_commands() {
+        assert_eq!(
+            split_commands("update&& status --json"),
+            Some(vec![args("update"), args("status --json")])
+        );
+        assert_eq!(
+            split_commands("track -d 'fix the && in names' a.png"),
+            Some(vec![vec![
+                "track".to_string(),
+                "-d".to_string(),
+                "fix the && in names".to_string(),
+                "a.png".to_string(),
+            ]])
+        );
+        assert_eq!(split_commands("track -d \"unclosed"), None);
+    }
+
+    #[test]
+    fn test_expand_aliases() {
+        let aliases: Aliases = [
+            ("sync", "update && status"),
+            ("mine", "here --desc"),
+            ("status", "here"),
+            ("loop", "again"),
+            ("again", "loop"),
+            ("empty", ""),
+            ("-bad", "update"),
+        ]
+        .into_iter()
+        .map(|(name, command)| (name.to_string(), command.to_string()))
+        .collect();
+        assert!(!aliases.names().any(|name| name == "-bad"));
+        let is_builtin = |name: &str| ["update", "status", "here"].contains(&name);
+
+        assert_eq!(
+            aliases.expand(args("jv sync --json"), is_builtin),
+            Ok(vec![args("jv update"), args("jv status --json")])
+        );
+        assert_eq!(
+            aliases.expand(args("jv mine src"), is_builtin),
+            Ok(vec![args("jv here --desc src")])
+        );
+        // Built-in commands can't be replaced
+        assert_eq!(
+            aliases.expand(args("jv status"), is_builtin),
+            Ok(vec![args("jv status")])
+        );
+        assert_eq!(
+            aliases.expand(args("jv loop"), is_builtin),
+            Err(AliasError::Recursive("loop".to_string()))
+        );
+        assert_eq!(
+            aliases.expand(args("jv empty"), is_builtin),
+            Err(AliasError::Invalid("empty".to_string()))
+        );
+    }
+}
@@ -0,0 +1,72 @@
+/// Reason a command failed, mapped to the exit code of the process
+///
+/// Codes are grouped by category:
+/// - 1 ~ 9: general failures
+/// - 10 ~ 19: workspace / vault state
+/// - 20 ~ 29: upstream communication
+/// - 30 ~ 39: rejected operations
+/// - 40 ~ 49: batch operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum CmdError {
+    /// Unexpected failure, such as a file system error
+    Failed = 1,
+
+    /// Invalid arguments or input
+    Usage = 2,
+
+    /// The operation was cancelled by the user
+    Cancelled = 3,
+
+    /// Not inside a workspace (or a vault)
+    NotWorkspace = 10,
+
+    /// Configuration is missing or unreadable
+    ConfigNotFound = 11,
+
+    /// The workspace is not stained by any upstream vault
+    NotStained = 12,
+
+    /// No sheet is in use
+    NoSheetInUse = 13,
+
+    /// Failed to connect to the upstream vault
+    ConnectionFailed = 20,
+
+    /// The upstream vault refused to authorize the current account
+    AuthorizeFailed = 21,
+
+    /// The target (file, sheet, account, share...) was not found
+    NotFound = 30,
+
+    /// The operation was rejected, such as a conflict or missing edit rights
+    Rejected = 31,
+
+    /// Some items of the operation succeeded, but others failed
+    PartialFailure = 40,
+}
+
+/// Result of a command
+pub type CmdResult = Result<(), CmdError>;
+
+impl CmdError {
+    /// Exit code of the process when the command fails for this reason
+    pub fn exit_code(self) -> i32 {
+        self as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_stable() {
+        assert_eq!(CmdError::Failed.exit_code(), 1);
+        assert_eq!(CmdError::Usage.exit_code(), 2);
+        assert_eq!(CmdError::NotWorkspace.exit_code(), 10);
+        assert_eq!(CmdError::ConnectionFailed.exit_code(), 20);
+        assert_eq!(CmdError::AuthorizeFailed.exit_code(), 21);
+        assert_eq!(CmdError::PartialFailure.exit_code(), 40);
+    }
+}
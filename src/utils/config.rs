@@ -0,0 +1,570 @@
+use crate::utils::alias::{Aliases, is_alias_name};
+
+/// Table of the command aliases in the configuration files, one `name = "command"` entry per alias
+pub const ALIASES_TABLE: &str = "aliases";
+
+/// Where the value of a configuration key comes from, later layers take precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigLayer {
+    /// Built-in default
+    Default,
+
+    /// Environment variable shared with other programs, e.g. `LANG`
+    System,
+
+    /// Configuration file in the config directory of the user
+    User,
+
+    /// Configuration file in the workspace directory
+    Workspace,
+
+    /// Environment variable
+    Env,
+}
+
+impl ConfigLayer {
+    pub fn name(self) -> &'static str {
+        match self {
+            ConfigLayer::Default => "default",
+            ConfigLayer::System => "system",
+            ConfigLayer::User => "user",
+            ConfigLayer::Workspace => "workspace",
+            ConfigLayer::Env => "env",
+        }
+    }
+}
+
+/// A configuration key known to the CLI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKey {
+    /// Language of the messages
+    Lang,
+
+    /// Update the workspace automatically when the vault was modified
+    AutoUpdate,
+
+    /// Minutes after which the workspace info is outdated, requires `AutoUpdate`
+    OutdatedMinutes,
+
+    /// Editor of descriptions and other texts
+    TextEditor,
+
+    /// Match glob patterns case-insensitively
+    GlobCaseInsensitive,
+
+    /// Keep the content of the versions synced by `jv track`, for `jv diff`, `restore` and `checkout`
+    BaseCache,
+
+    /// Version scheme rules of the files, `<pattern> <scheme>` per item
+    VersionSchemes,
+
+    /// Text pre-filling the description in the update editor
+    DescTemplate,
+
+    /// Regexes every update description must match
+    DescRules,
+
+    /// Ignore patterns applying to the whole workspace, in the syntax of `.jvignore`
+    IgnorePatterns,
+}
+
+impl ConfigKey {
+    pub const ALL: [ConfigKey; 10] = [
+        ConfigKey::Lang,
+        ConfigKey::AutoUpdate,
+        ConfigKey::OutdatedMinutes,
+        ConfigKey::TextEditor,
+        ConfigKey::GlobCaseInsensitive,
+        ConfigKey::BaseCache,
+        ConfigKey::VersionSchemes,
+        ConfigKey::DescTemplate,
+        ConfigKey::DescRules,
+        ConfigKey::IgnorePatterns,
+    ];
+
+    pub fn parse(key: &str) -> Option<Self> {
+        let key = key.trim().to_lowercase().replace('-', "_");
+        Self::ALL.into_iter().find(|known| known.name() == key)
+    }
+
+    /// Name of the key in the configuration files, keys in a table are named `<table>.<key>`
+    pub fn name(self) -> &'static str {
+        match self {
+            ConfigKey::Lang => "lang",
+            ConfigKey::AutoUpdate => "auto_update",
+            ConfigKey::OutdatedMinutes => "outdated_minutes",
+            ConfigKey::TextEditor => "text_editor",
+            ConfigKey::GlobCaseInsensitive => "glob_case_insensitive",
+            ConfigKey::BaseCache => "base_cache",
+            ConfigKey::VersionSchemes => "version_schemes.rules",
+            ConfigKey::DescTemplate => "desc.template",
+            ConfigKey::DescRules => "desc.rules",
+            ConfigKey::IgnorePatterns => "ignore.patterns",
+        }
+    }
+
+    /// Environment variables overriding the configuration files, in order
+    pub fn env_vars(self) -> &'static [&'static str] {
+        match self {
+            ConfigKey::Lang => &["JV_LANG", "APP_LANG"],
+            ConfigKey::AutoUpdate => &["JV_AUTO_UPDATE"],
+            ConfigKey::OutdatedMinutes => &["JV_OUTDATED_MINUTES"],
+            ConfigKey::TextEditor => &["JV_TEXT_EDITOR"],
+            ConfigKey::GlobCaseInsensitive => &["JV_GLOB_CASE_INSENSITIVE"],
+            ConfigKey::BaseCache => &["JV_BASE_CACHE"],
+            _ => &[],
+        }
+    }
+
+    /// Environment variables shared with other programs, only used when the key is set nowhere else
+    pub fn fallback_env_vars(self) -> &'static [&'static str] {
+        match self {
+            ConfigKey::Lang => &["LANG"],
+            ConfigKey::TextEditor => &["EDITOR"],
+            _ => &[],
+        }
+    }
+
+    pub fn default_value(self) -> &'static str {
+        match self {
+            ConfigKey::Lang => "en",
+            ConfigKey::AutoUpdate => "false",
+            ConfigKey::OutdatedMinutes => "-1",
+            ConfigKey::TextEditor => "jvii",
+            ConfigKey::GlobCaseInsensitive => "false",
+            ConfigKey::BaseCache => "false",
+            ConfigKey::DescTemplate => "",
+            ConfigKey::VersionSchemes | ConfigKey::DescRules | ConfigKey::IgnorePatterns => "[]",
+        }
+    }
+
+    /// Whether the value of the key is a list of texts
+    pub fn is_list(self) -> bool {
+        matches!(
+            self,
+            ConfigKey::VersionSchemes | ConfigKey::DescRules | ConfigKey::IgnorePatterns
+        )
+    }
+
+    /// Convert a value given as text to the type of this key, `None` if it is invalid.
+    /// Lists are given as a TOML array, any other text is a list of one item
+    pub fn parse_value(self, value: &str) -> Option<toml::Value> {
+        if self.is_list() {
+            return match value.trim().parse::<toml::Value>() {
+                Ok(toml::Value::Array(items)) => items
+                    .iter()
+                    .all(|item| item.is_str())
+                    .then_some(toml::Value::Array(items)),
+                _ => Some(toml::Value::Array(vec![toml::Value::String(
+                    value.to_string(),
+                )])),
+            };
+        }
+        match self {
+            ConfigKey::AutoUpdate | ConfigKey::GlobCaseInsensitive | ConfigKey::BaseCache => {
+                parse_bool(value).map(toml::Value::Boolean)
+            }
+            ConfigKey::OutdatedMinutes => value.trim().parse().ok().map(toml::Value::Integer),
+            _ => Some(toml::Value::String(value.to_string())),
+        }
+    }
+
+    /// Bring a value to the form the CLI uses, e.g. `zh_CN.UTF-8` to `zh-CN` for the language
+    fn normalize(self, value: String) -> String {
+        match self {
+            ConfigKey::Lang => value
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .replace('_', "-"),
+            _ => value,
+        }
+    }
+}
+
+/// An entry of the configuration files, a known key or a command alias
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigEntry {
+    Key(ConfigKey),
+
+    /// Alias in the `[aliases]` table, named `aliases.<name>`
+    Alias(String),
+}
+
+impl ConfigEntry {
+    pub fn parse(name: &str) -> Option<Self> {
+        if let Some(alias) = name.trim().strip_prefix(&format!("{}.", ALIASES_TABLE)) {
+            return is_alias_name(alias).then(|| ConfigEntry::Alias(alias.to_string()));
+        }
+        ConfigKey::parse(name).map(ConfigEntry::Key)
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            ConfigEntry::Key(key) => key.name().to_string(),
+            ConfigEntry::Alias(alias) => format!("{}.{}", ALIASES_TABLE, alias),
+        }
+    }
+
+    /// Convert a value given as text to the type of this entry, `None` if it is invalid
+    pub fn parse_value(&self, value: &str) -> Option<toml::Value> {
+        match self {
+            ConfigEntry::Key(key) => key.parse_value(value),
+            ConfigEntry::Alias(_) => Some(toml::Value::String(value.to_string())),
+        }
+    }
+}
+
+/// Parse a yes/no value, `yes`, `y` and `true` or `no`, `n` and `false`, in any case
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "yes" | "y" | "true" => Some(true),
+        "no" | "n" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// A TOML configuration file, keys other than the known ones are kept as they are
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConfigTable {
+    table: toml::Table,
+}
+
+impl ConfigTable {
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        Ok(Self {
+            table: content.parse()?,
+        })
+    }
+
+    /// Value of a key, `<table>.<key>` reaches into a table
+    fn value(&self, name: &str) -> Option<&toml::Value> {
+        let (tables, key) = split_name(name);
+        let mut table = &self.table;
+        for name in tables {
+            table = table.get(name)?.as_table()?;
+        }
+        table.get(key)
+    }
+
+    /// Value of a key as text, lists are written as a TOML array
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.value(name).map(|value| match value {
+            toml::Value::String(text) => text.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// Items of a list key, a single text is a list of one item
+    pub fn list(&self, name: &str) -> Option<Vec<String>> {
+        match self.value(name)? {
+            toml::Value::Array(items) => Some(
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect(),
+            ),
+            toml::Value::String(text) => Some(vec![text.clone()]),
+            _ => None,
+        }
+    }
+
+    /// Texts of a table, e.g. the aliases
+    pub fn texts(&self, name: &str) -> Vec<(String, String)> {
+        match self.value(name) {
+            Some(toml::Value::Table(table)) => table
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Set a key, creating its tables if needed
+    pub fn set(&mut self, name: &str, value: toml::Value) {
+        let (tables, key) = split_name(name);
+        let mut table = &mut self.table;
+        for name in tables {
+            let entry = table
+                .entry(name)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !entry.is_table() {
+                *entry = toml::Value::Table(toml::Table::new());
+            }
+            let Some(inner) = entry.as_table_mut() else {
+                return;
+            };
+            table = inner;
+        }
+        table.insert(key.to_string(), value);
+    }
+
+    /// Remove a key, returns whether it was set. Tables left empty are removed too
+    pub fn unset(&mut self, name: &str) -> bool {
+        fn remove(table: &mut toml::Table, tables: &[&str], key: &str) -> bool {
+            let Some((first, rest)) = tables.split_first() else {
+                return table.remove(key).is_some();
+            };
+            let Some(inner) = table.get_mut(*first).and_then(|value| value.as_table_mut()) else {
+                return false;
+            };
+            let removed = remove(inner, rest, key);
+            if inner.is_empty() {
+                table.remove(*first);
+            }
+            removed
+        }
+
+        let (tables, key) = split_name(name);
+        remove(&mut self.table, &tables, key)
+    }
+}
+
+/// Split the name of a key into the names of its tables and its own name
+fn split_name(name: &str) -> (Vec<&str>, &str) {
+    let mut names: Vec<&str> = name.split('.').collect();
+    let key = names.pop().unwrap_or_default();
+    (names, key)
+}
+
+impl std::fmt::Display for ConfigTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.table)
+    }
+}
+
+/// Configuration layered from the configuration files and the environment variables
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Config {
+    pub user: ConfigTable,
+    pub workspace: ConfigTable,
+}
+
+impl Config {
+    /// Value of a key and the layer it comes from, the default value if it is set nowhere.
+    /// `env` reads an environment variable
+    pub fn resolve(
+        &self,
+        key: ConfigKey,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> (String, ConfigLayer) {
+        if key.is_list() {
+            let (items, layer) = self.list(key);
+            let items = items.into_iter().map(toml::Value::String).collect();
+            return (toml::Value::Array(items).to_string(), layer);
+        }
+
+        let from_env = |vars: &[&str]| vars.iter().find_map(|var| env(var));
+
+        let (value, layer) = if let Some(value) = from_env(key.env_vars()) {
+            (value, ConfigLayer::Env)
+        } else if let Some(value) = self.workspace.get(key.name()) {
+            (value, ConfigLayer::Workspace)
+        } else if let Some(value) = self.user.get(key.name()) {
+            (value, ConfigLayer::User)
+        } else if let Some(value) = from_env(key.fallback_env_vars()) {
+            (value, ConfigLayer::System)
+        } else {
+            (key.default_value().to_string(), ConfigLayer::Default)
+        };
+        (key.normalize(value), layer)
+    }
+
+    /// Value of an entry and the layer it comes from, like `resolve`.
+    /// An alias set nowhere is empty
+    pub fn resolve_entry(
+        &self,
+        entry: &ConfigEntry,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> (String, ConfigLayer) {
+        if let ConfigEntry::Key(key) = entry {
+            return self.resolve(*key, env);
+        }
+
+        let name = entry.name();
+        if let Some(value) = self.workspace.get(&name) {
+            (value, ConfigLayer::Workspace)
+        } else if let Some(value) = self.user.get(&name) {
+            (value, ConfigLayer::User)
+        } else {
+            (String::new(), ConfigLayer::Default)
+        }
+    }
+
+    /// Items of a list key and the highest layer setting it,
+    /// the items of the user come first and the ones of the workspace after them
+    pub fn list(&self, key: ConfigKey) -> (Vec<String>, ConfigLayer) {
+        let user = self.user.list(key.name());
+        let workspace = self.workspace.list(key.name());
+        let layer = if workspace.is_some() {
+            ConfigLayer::Workspace
+        } else if user.is_some() {
+            ConfigLayer::User
+        } else {
+            ConfigLayer::Default
+        };
+        let items = user.into_iter().chain(workspace).flatten().collect();
+        (items, layer)
+    }
+
+    /// Aliases of the user and of the workspace, the workspace's ones replace the user's ones
+    pub fn aliases(&self) -> Aliases {
+        self.user
+            .texts(ALIASES_TABLE)
+            .into_iter()
+            .chain(self.workspace.texts(ALIASES_TABLE))
+            .collect()
+    }
+
+    /// All known keys followed by the aliases
+    pub fn entries(&self) -> Vec<ConfigEntry> {
+        let aliases = self.aliases();
+        ConfigKey::ALL
+            .into_iter()
+            .map(ConfigEntry::Key)
+            .chain(
+                aliases
+                    .names()
+                    .map(|name| ConfigEntry::Alias(name.to_string())),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file() {
+        let mut file =
+            ConfigTable::parse("lang = \"zh-CN\"\nauto_update = true\nother = 1\n").unwrap();
+        assert_eq!(file.get(ConfigKey::Lang.name()), Some("zh-CN".to_string()));
+        assert_eq!(
+            file.get(ConfigKey::AutoUpdate.name()),
+            Some("true".to_string())
+        );
+
+        let minutes = ConfigKey::OutdatedMinutes.parse_value(" 30 ").unwrap();
+        file.set(ConfigKey::OutdatedMinutes.name(), minutes);
+        assert!(file.unset(ConfigKey::Lang.name()));
+        assert!(!file.unset(ConfigKey::TextEditor.name()));
+        assert_eq!(
+            ConfigTable::parse(&file.to_string())
+                .unwrap()
+                .get(ConfigKey::OutdatedMinutes.name()),
+            Some("30".to_string())
+        );
+
+        assert_eq!(ConfigKey::AutoUpdate.parse_value("maybe"), None);
+        assert_eq!(
+            ConfigKey::parse("outdated-minutes"),
+            Some(ConfigKey::OutdatedMinutes)
+        );
+        assert!(ConfigTable::parse("lang = ").is_err());
+    }
+
+    #[test]
+    fn test_config_layers() {
+        let config = Config {
+            user: ConfigTable::parse("lang = \"zh-CN\"\ntext_editor = \"vim\"\n").unwrap(),
+            workspace: ConfigTable::parse("text_editor = \"code --wait\"\n").unwrap(),
+        };
+        let no_env = |_: &str| None;
+        let env = |var: &str| match var {
+            "JV_LANG" => Some("en".to_string()),
+            "EDITOR" => Some("nano".to_string()),
+            _ => None,
+        };
+
+        assert_eq!(
+            config.resolve(ConfigKey::Lang, no_env),
+            ("zh-CN".to_string(), ConfigLayer::User)
+        );
+        assert_eq!(
+            config.resolve(ConfigKey::Lang, env),
+            ("en".to_string(), ConfigLayer::Env)
+        );
+        // Variables shared with other programs don't override the files
+        assert_eq!(
+            config.resolve(ConfigKey::TextEditor, env),
+            ("code --wait".to_string(), ConfigLayer::Workspace)
+        );
+        assert_eq!(
+            Config::default().resolve(ConfigKey::TextEditor, env),
+            ("nano".to_string(), ConfigLayer::System)
+        );
+        assert_eq!(
+            Config::default().resolve(ConfigKey::Lang, |var: &str| {
+                (var == "LANG").then(|| "fr_FR.UTF-8".to_string())
+            }),
+            ("fr-FR".to_string(), ConfigLayer::System)
+        );
+        assert_eq!(
+            config.resolve(ConfigKey::OutdatedMinutes, env),
+            ("-1".to_string(), ConfigLayer::Default)
+        );
+    }
+
+    #[test]
+    fn test_config_tables() {
+        let mut file =
+            ConfigTable::parse("desc = \"old\"\n[aliases]\nsync = \"update\"\n").unwrap();
+        let rules = ConfigKey::DescRules.parse_value("PROJ-\\d+").unwrap();
+        file.set(ConfigKey::DescRules.name(), rules);
+        let patterns = ConfigKey::IgnorePatterns.parse_value("[\"*.tmp\", \"build/\"]");
+        file.set(ConfigKey::IgnorePatterns.name(), patterns.unwrap());
+        assert_eq!(ConfigKey::IgnorePatterns.parse_value("[1, 2]"), None);
+
+        let file = ConfigTable::parse(&file.to_string()).unwrap();
+        assert_eq!(
+            file.list(ConfigKey::DescRules.name()),
+            Some(vec!["PROJ-\\d+".to_string()])
+        );
+        assert_eq!(
+            file.get(ConfigKey::IgnorePatterns.name()),
+            Some("[\"*.tmp\", \"build/\"]".to_string())
+        );
+
+        let mut empty = file.clone();
+        assert!(empty.unset(ConfigKey::IgnorePatterns.name()));
+        assert!(empty.unset(ConfigKey::DescRules.name()));
+        assert!(!empty.unset(ConfigKey::DescTemplate.name()));
+        assert!(!empty.to_string().contains("[ignore]"));
+
+        let config = Config {
+            user: file,
+            workspace: ConfigTable::parse(
+                "[aliases]\nsync = \"update && status\"\nmine = \"here\"\n[ignore]\npatterns = []\n",
+            )
+            .unwrap(),
+        };
+        let aliases = ConfigEntry::parse("aliases.sync").unwrap();
+        assert_eq!(
+            config.resolve_entry(&aliases, |_| None),
+            ("update && status".to_string(), ConfigLayer::Workspace)
+        );
+        assert_eq!(ConfigEntry::parse("aliases.a b"), None);
+        // Lists of the workspace come after the ones of the user
+        assert_eq!(
+            config.list(ConfigKey::IgnorePatterns),
+            (
+                vec!["*.tmp".to_string(), "build/".to_string()],
+                ConfigLayer::Workspace
+            )
+        );
+        assert_eq!(
+            config.resolve(ConfigKey::IgnorePatterns, |_| None),
+            (
+                "[\"*.tmp\", \"build/\"]".to_string(),
+                ConfigLayer::Workspace
+            )
+        );
+        assert_eq!(config.list(ConfigKey::DescRules).1, ConfigLayer::User);
+        assert_eq!(
+            config.aliases().names().collect::<Vec<_>>(),
+            ["mine", "sync"]
+        );
+        assert_eq!(config.entries().len(), ConfigKey::ALL.len() + 2);
+    }
+}
@@ -0,0 +1,303 @@
+/// Operation of a single line in a diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine<'a> {
+    pub op: DiffOp,
+    pub text: &'a str,
+}
+
+/// Check whether the content should be treated as binary.
+/// Content containing NUL bytes in the first 8000 bytes, or not valid UTF-8, is binary
+pub fn is_binary(content: &[u8]) -> bool {
+    let head = &content[..content.len().min(8000)];
+    head.contains(&0) || std::str::from_utf8(content).is_err()
+}
+
+/// Diff two texts line by line (Myers algorithm)
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Trim the common prefix and suffix, most edits only touch a small part of the file
+    let prefix = a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let mut result = Vec::with_capacity(a.len().max(b.len()));
+    a[..prefix].iter().for_each(|text| {
+        result.push(DiffLine {
+            op: DiffOp::Equal,
+            text,
+        })
+    });
+    result.extend(myers(
+        &a[prefix..a.len() - suffix],
+        &b[prefix..b.len() - suffix],
+    ));
+    a[a.len() - suffix..].iter().for_each(|text| {
+        result.push(DiffLine {
+            op: DiffOp::Equal,
+            text,
+        })
+    });
+    result
+}
+
+/// Edit distance above which two texts are no longer diffed line by line,
+/// the search keeps O(D²) lines of state
+const MAX_EDIT_DISTANCE: usize = 1000;
+
+fn myers<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = (a.len() + b.len()).min(MAX_EDIT_DISTANCE) as isize;
+
+    // Furthest x on each diagonal k after step d, `trace[d][k + d]` for k in [-d, d]
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let furthest =
+        |trace: &[Vec<isize>], d: isize, k: isize| trace[(d - 1) as usize][(k + d - 1) as usize];
+    let goes_down = |trace: &[Vec<isize>], d: isize, k: isize| {
+        k == -d || (k != d && furthest(trace, d, k - 1) < furthest(trace, d, k + 1))
+    };
+
+    let mut found = false;
+    'search: for d in 0..=max {
+        let mut current = vec![0isize; (2 * d + 1) as usize];
+        for k in (-d..=d).step_by(2) {
+            let mut x = if d == 0 {
+                0
+            } else if goes_down(&trace, d, k) {
+                furthest(&trace, d, k + 1)
+            } else {
+                furthest(&trace, d, k - 1) + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            current[(k + d) as usize] = x;
+            if x >= n && y >= m {
+                trace.push(current);
+                found = true;
+                break 'search;
+            }
+        }
+        trace.push(current);
+    }
+
+    // Too many differences, replace the whole range
+    if !found {
+        return a
+            .iter()
+            .map(|text| DiffLine {
+                op: DiffOp::Delete,
+                text,
+            })
+            .chain(b.iter().map(|text| DiffLine {
+                op: DiffOp::Insert,
+                text,
+            }))
+            .collect();
+    }
+
+    // Backtrack from the end to build the edit script
+    let mut result = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for d in (0..trace.len() as isize).rev() {
+        let (prev_x, prev_y) = if d == 0 {
+            (0, 0)
+        } else {
+            let k = x - y;
+            let prev_k = if goes_down(&trace, d, k) {
+                k + 1
+            } else {
+                k - 1
+            };
+            let prev_x = furthest(&trace, d, prev_k);
+            (prev_x, prev_x - prev_k)
+        };
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            result.push(DiffLine {
+                op: DiffOp::Equal,
+                text: a[x as usize],
+            });
+        }
+        if d > 0 {
+            if x == prev_x {
+                result.push(DiffLine {
+                    op: DiffOp::Insert,
+                    text: b[(y - 1) as usize],
+                });
+            } else {
+                result.push(DiffLine {
+                    op: DiffOp::Delete,
+                    text: a[(x - 1) as usize],
+                });
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+
+    result.reverse();
+    result
+}
+
+/// Render a unified diff of two texts, with `context` lines around each change.
+/// Returns an empty string when the texts have no line differences
+pub fn unified_diff(
+    old: &str,
+    new: &str,
+    old_name: &str,
+    new_name: &str,
+    context: usize,
+) -> String {
+    let lines = diff_lines(old, new);
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.op != DiffOp::Equal)
+        .map(|(i, _)| i)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // Group changes whose distance is small enough to share context
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for i in changes {
+        match groups.last_mut() {
+            Some((_, end)) if i - *end <= context * 2 + 1 => *end = i,
+            _ => groups.push((i, i)),
+        }
+    }
+
+    // Line numbers before each diff line
+    let mut old_no = Vec::with_capacity(lines.len() + 1);
+    let mut new_no = Vec::with_capacity(lines.len() + 1);
+    let (mut o, mut n) = (0usize, 0usize);
+    for line in &lines {
+        old_no.push(o);
+        new_no.push(n);
+        match line.op {
+            DiffOp::Equal => {
+                o += 1;
+                n += 1;
+            }
+            DiffOp::Delete => o += 1,
+            DiffOp::Insert => n += 1,
+        }
+    }
+    old_no.push(o);
+    new_no.push(n);
+
+    let mut result = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (first, last) in groups {
+        let start = first.saturating_sub(context);
+        let end = (last + context + 1).min(lines.len());
+
+        let old_count = old_no[end] - old_no[start];
+        let new_count = new_no[end] - new_no[start];
+        let old_start = if old_count == 0 {
+            old_no[start]
+        } else {
+            old_no[start] + 1
+        };
+        let new_start = if new_count == 0 {
+            new_no[start]
+        } else {
+            new_no[start] + 1
+        };
+
+        result.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_count, new_start, new_count
+        ));
+        for line in &lines[start..end] {
+            let mark = match line.op {
+                DiffOp::Equal => ' ',
+                DiffOp::Delete => '-',
+                DiffOp::Insert => '+',
+            };
+            result.push(mark);
+            result.push_str(line.text);
+            result.push('\n');
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines_minimal() {
+        let lines = diff_lines("a\nb\nc\n", "a\nx\nc\n");
+        let ops: Vec<DiffOp> = lines.iter().map(|l| l.op).collect();
+        assert_eq!(
+            ops,
+            vec![DiffOp::Equal, DiffOp::Delete, DiffOp::Insert, DiffOp::Equal]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_from_empty() {
+        let lines = diff_lines("", "a\nb\n");
+        assert!(lines.iter().all(|l| l.op == DiffOp::Insert));
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_unified_diff_hunks() {
+        let old = (1..=20).map(|i| format!("{}\n", i)).collect::<String>();
+        let new = (1..=20)
+            .filter(|i| *i != 18)
+            .map(|i| match i {
+                3 => "three\n".to_string(),
+                _ => format!("{}\n", i),
+            })
+            .collect::<String>();
+        let diff = unified_diff(&old, &new, "a", "b", 2);
+        assert!(diff.starts_with("--- a\n+++ b\n"));
+        assert!(diff.contains("@@ -1,5 +1,5 @@\n"));
+        assert!(diff.contains("@@ -16,5 +16,4 @@\n"));
+        assert!(diff.contains("-3\n+three\n"));
+        assert!(diff.contains("-18\n"));
+    }
+
+    #[test]
+    fn test_unified_diff_equal() {
+        assert!(unified_diff("a\nb\n", "a\nb\n", "a", "b", 3).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lines_too_different() {
+        let old = (0..800).map(|i| format!("old {}\n", i)).collect::<String>();
+        let new = (0..800).map(|i| format!("new {}\n", i)).collect::<String>();
+        let lines = diff_lines(&old, &new);
+        assert_eq!(lines.len(), 1600);
+        assert!(lines[..800].iter().all(|l| l.op == DiffOp::Delete));
+        assert!(lines[800..].iter().all(|l| l.op == DiffOp::Insert));
+    }
+
+    #[test]
+    fn test_is_binary() {
+        assert!(is_binary(&[0x89, b'P', b'N', b'G', 0, 0]));
+        assert!(!is_binary("plain text".as_bytes()));
+    }
+}
@@ -329,6 +329,26 @@ pub fn md(text: impl AsRef<str>) -> String {
     result
 }
 
+/// Colorize a unified diff produced by `utils::diff::unified_diff`
+pub fn render_unified_diff(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            if line.starts_with("---") || line.starts_with("+++") {
+                line.bold().to_string()
+            } else if line.starts_with("@@") {
+                apply_color(line, "cyan")
+            } else if line.starts_with('-') {
+                apply_color(line, "red")
+            } else if line.starts_with('+') {
+                apply_color(line, "green")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 // Helper function to apply color to text
 fn apply_color(text: &str, color_name: &str) -> String {
     match color_name {
@@ -1,56 +1,75 @@
-/// Returns the current locale string based on environment variables.
+use crate::{
+    data::config::current_config,
+    utils::config::{ConfigKey, parse_bool},
+};
+
+/// Value of a configuration key, from its environment variables, the workspace
+/// configuration, the user configuration or its default, in that order
+pub fn config_value(key: ConfigKey) -> String {
+    current_config()
+        .resolve(key, |var| std::env::var(var).ok())
+        .0
+}
+
+/// Items of a list configuration key, from the workspace configuration or else the user configuration
+pub fn config_list(key: ConfigKey) -> Vec<String> {
+    current_config().list(key).0
+}
+
+/// Returns the current locale string based on environment variables and the configuration.
 ///
 /// The function checks for locale settings in the following order:
 /// 1. JV_LANG environment variable
 /// 2. APP_LANG environment variable
-/// 3. LANG environment variable (extracts base language before dot and replaces underscores with hyphens)
-/// 4. Defaults to "en" if no locale environment variables are found
+/// 3. `lang` of the workspace configuration, then of the user configuration
+/// 4. LANG environment variable (extracts base language before dot and replaces underscores with hyphens)
+/// 5. Defaults to "en" if no locale is set
 ///
 /// # Returns
 /// A String containing the detected locale code
 pub fn current_locales() -> String {
-    if let Ok(lang) = std::env::var("JV_LANG") {
-        return lang;
-    }
-
-    if let Ok(lang) = std::env::var("APP_LANG") {
-        return lang;
-    }
-
-    if let Ok(lang) = std::env::var("LANG") {
-        if let Some(base_lang) = lang.split('.').next() {
-            return base_lang.replace('_', "-");
-        }
-        return lang;
-    }
-
-    "en".to_string()
+    config_value(ConfigKey::Lang)
 }
 
-/// Checks if auto update is enabled based on environment variables.
+/// Checks if auto update is enabled based on environment variables and the configuration.
 ///
-/// The function checks the JV_AUTO_UPDATE environment variable and compares
-/// its value (after trimming and converting to lowercase) against known
-/// positive and negative values.
+/// The function checks the JV_AUTO_UPDATE environment variable, then `auto_update`
+/// of the configuration, and compares its value (after trimming and converting to lowercase)
+/// against known positive and negative values.
 ///
 /// # Returns
 /// `true` if the value matches "yes", "y", or "true"
-/// `false` if the value matches "no", "n", or "false", or if the variable is not set
+/// `false` if the value matches "no", "n", or "false", or if the value is not set
 pub fn enable_auto_update() -> bool {
-    if let Ok(auto_update) = std::env::var("JV_AUTO_UPDATE") {
-        let normalized = auto_update.trim().to_lowercase();
-        match normalized.as_str() {
-            "yes" | "y" | "true" => return true,
-            "no" | "n" | "false" => return false,
-            _ => {}
-        }
-    }
-    false
+    parse_bool(&config_value(ConfigKey::AutoUpdate)).unwrap_or(false)
 }
 
-/// Gets the auto update expiration time based on environment variables.
+/// Checks if glob patterns match names case-insensitively based on environment variables and the configuration.
+///
+/// The function checks the JV_GLOB_CASE_INSENSITIVE environment variable, then
+/// `glob_case_insensitive` of the configuration, the same values as JV_AUTO_UPDATE are accepted.
+/// `--ignore-case` of the file commands enables it for a single command.
 ///
-/// The function checks the JV_OUTDATED_MINUTES environment variable.
+/// # Returns
+/// `true` if the value matches "yes", "y", or "true", otherwise `false`
+pub fn glob_case_insensitive() -> bool {
+    parse_bool(&config_value(ConfigKey::GlobCaseInsensitive)).unwrap_or(false)
+}
+
+/// Checks if the content of the synced versions is kept locally, based on environment variables and the configuration.
+///
+/// The function checks the JV_BASE_CACHE environment variable, then `base_cache` of the configuration,
+/// the same values as JV_AUTO_UPDATE are accepted.
+///
+/// # Returns
+/// `true` if the value matches "yes", "y", or "true", otherwise `false`
+pub fn base_cache_enabled() -> bool {
+    parse_bool(&config_value(ConfigKey::BaseCache)).unwrap_or(false)
+}
+
+/// Gets the auto update expiration time based on environment variables and the configuration.
+///
+/// The function checks the JV_OUTDATED_MINUTES environment variable, then `outdated_minutes` of the configuration.
 /// Requires JV_AUTO_UPDATE to be enabled.
 /// Next time the `jv` command is used, if the content is outdated, `jv update` will be automatically executed.
 ///
@@ -64,31 +83,20 @@ pub fn auto_update_outdate() -> i64 {
         return -1;
     }
 
-    match std::env::var("JV_OUTDATED_MINUTES") {
-        Ok(value) => match value.trim().parse::<i64>() {
-            Ok(num) => num,
-            Err(_) => -1,
-        },
-        Err(_) => -1,
-    }
+    config_value(ConfigKey::OutdatedMinutes)
+        .trim()
+        .parse::<i64>()
+        .unwrap_or(-1)
 }
 
-/// Gets the default text editor based on environment variables.
+/// Gets the default text editor based on environment variables and the configuration.
 ///
-/// The function checks the JV_TEXT_EDITOR and EDITOR environment variables
-/// and returns their values if they are set. If neither variable is set,
-/// it returns "jvii" as the default editor.
+/// The function checks the JV_TEXT_EDITOR environment variable, `text_editor` of the
+/// configuration and the EDITOR environment variable, and returns the first one set.
+/// If none is set, it returns "jvii" as the default editor.
 ///
 /// # Returns
 /// A String containing the default text editor
 pub async fn get_default_editor() -> String {
-    if let Ok(editor) = std::env::var("JV_TEXT_EDITOR") {
-        return editor;
-    }
-
-    if let Ok(editor) = std::env::var("EDITOR") {
-        return editor;
-    }
-
-    "jvii".to_string()
+    config_value(ConfigKey::TextEditor)
 }
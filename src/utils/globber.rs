@@ -1,4 +1,10 @@
-use std::{io::Error, path::PathBuf, str::FromStr};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    io::Error,
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
 
 use just_enough_vcs::utils::string_proc::format_path::format_path_str;
 
@@ -8,6 +14,7 @@ pub struct Globber {
     pattern: String,
     base: PathBuf,
     names: Vec<String>,
+    ignore_case: bool,
 }
 
 #[allow(dead_code)]
@@ -17,9 +24,16 @@ impl Globber {
             pattern,
             base,
             names: Vec::new(),
+            ignore_case: false,
         }
     }
 
+    /// Match the names case-insensitively, only patterns with wildcards are affected
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
     pub fn names(&self) -> Vec<&String> {
         self.names.iter().collect()
     }
@@ -36,26 +50,56 @@ impl Globber {
         self.names.iter().map(|n| self.base.join(n)).collect()
     }
 
+    /// Groups of matched names that only differ in case
+    pub fn case_collisions(&self) -> Vec<Vec<&String>> {
+        let mut groups: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+        for name in &self.names {
+            groups.entry(name.to_lowercase()).or_default().push(name);
+        }
+        groups
+            .into_values()
+            .filter(|names| names.len() > 1)
+            .collect()
+    }
+
+    /// Glob the pattern under the base directory, `get_names` lists the items of a directory.
+    ///
+    /// Supports `*` and `?` within a name, `**` for any number of directories,
+    /// `[abc]` / `[!a-z]` character classes, `{png,psd}` alternatives and `\` escapes
     pub fn glob<F>(mut self, get_names: F) -> Result<Self, std::io::Error>
     where
         F: Fn(PathBuf) -> Vec<GlobItem>,
     {
-        let full_path = format!("{}{}{}", self.base.display(), SPLIT_STR, self.pattern);
-
-        let (path, pattern) = if let Some(last_split) = full_path.rfind(SPLIT_STR) {
-            let (path_part, pattern_part) = full_path.split_at(last_split);
-            let mut path = path_part.to_string();
-            if !path.ends_with(SPLIT_STR) {
-                path.push_str(SPLIT_STR);
-            }
-            (
-                format_path_str(path)?,
-                pattern_part[SPLIT_STR.len()..].to_string(),
-            )
+        let pattern = if self.pattern.is_empty() || self.pattern == "." {
+            "**".to_string()
+        } else if self.pattern.ends_with(is_separator) {
+            format!("{}**", self.pattern)
         } else {
-            (String::default(), full_path)
+            self.pattern.clone()
+        };
+
+        // Directories before the first segment with wildcards belong to the base directory
+        let segments: Vec<&str> = pattern.split(is_separator).collect();
+        let last = segments.len() - 1;
+        let first_wildcard = segments.iter().position(|segment| has_wildcard(segment));
+        let literal_count = match first_wildcard {
+            // Directories may differ in case too, so only `.` and `..` are resolved directly
+            Some(_) if self.ignore_case => segments
+                .iter()
+                .take_while(|segment| matches!(**segment, "" | "." | ".."))
+                .count(),
+            Some(first_wildcard) => first_wildcard,
+            None => last,
         };
 
+        let mut path = self.base.display().to_string();
+        for segment in &segments[..literal_count] {
+            path.push_str(SPLIT_STR);
+            path.push_str(&unescape(segment));
+        }
+        path.push_str(SPLIT_STR);
+        let path = format_path_str(path)?;
+
         self.base = match PathBuf::from_str(&path) {
             Ok(r) => r,
             Err(_) => {
@@ -66,112 +110,301 @@ impl Globber {
             }
         };
 
-        let pattern = if pattern.is_empty() {
-            "*".to_string()
-        } else if pattern == "." {
-            "*".to_string()
-        } else if pattern.ends_with(SPLIT_STR) {
-            format!("{}*", pattern)
-        } else {
-            pattern
-        };
-
-        if !pattern.contains('*') && !pattern.contains('?') {
-            self.names = vec![pattern];
+        if first_wildcard.is_none() {
+            self.names = vec![unescape(segments[last])];
             return Ok(self);
         }
 
+        let pattern = CompiledPattern::new(&segments[literal_count..].join("/"), self.ignore_case);
+
         let mut collected = Vec::new();
 
-        collect_files(&path.into(), "./".to_string(), &mut collected, &get_names);
+        collect_files(
+            &path.into(),
+            &mut Vec::new(),
+            &pattern,
+            &mut collected,
+            &get_names,
+        );
         fn collect_files<F>(
             base: &PathBuf,
-            current: String,
+            current: &mut Vec<String>,
+            pattern: &CompiledPattern,
             file_names: &mut Vec<String>,
             get_names: &F,
         ) where
             F: Fn(PathBuf) -> Vec<GlobItem>,
         {
-            let current_path = if current.is_empty() {
-                base.clone()
-            } else {
-                base.join(&current)
-            };
+            let current_path = current
+                .iter()
+                .fold(base.clone(), |path, name| path.join(name));
 
             let items = get_names(current_path);
             for item in items {
                 match item {
                     GlobItem::File(file_name) => {
-                        let relative_path = {
-                            format_path_str(format!("{}{}{}", current, SPLIT_STR, file_name))
-                                .unwrap_or_default()
-                        };
-                        file_names.push(relative_path)
+                        current.push(file_name);
+                        if pattern.matches(current) {
+                            file_names.push(current.join(SPLIT_STR));
+                        }
+                        current.pop();
                     }
                     GlobItem::Directory(dir_name) => {
-                        let new_current = {
-                            format_path_str(format!("{}{}{}", current, SPLIT_STR, dir_name))
-                                .unwrap_or_default()
-                        };
-                        collect_files(base, new_current, file_names, get_names);
+                        current.push(dir_name);
+                        // Only descend into directories that may contain matching files
+                        if pattern.may_match_inside(current) {
+                            collect_files(base, current, pattern, file_names, get_names);
+                        }
+                        current.pop();
                     }
                 }
             }
         }
 
-        self.names = collected
-            .iter()
-            .filter_map(|name| match_pattern(name, &pattern))
-            .collect();
+        self.names = collected;
 
         Ok(self)
     }
 }
 
-fn match_pattern(name: &str, pattern: &str) -> Option<String> {
-    if pattern.is_empty() {
-        return None;
+/// Pattern relative to the base directory, compiled once before walking the directories
+struct CompiledPattern {
+    /// Segments of each alternative after brace expansion
+    alternatives: Vec<Vec<String>>,
+
+    /// The segments are lowercase, and names are lowercased before matching
+    ignore_case: bool,
+}
+
+impl CompiledPattern {
+    fn new(pattern: &str, ignore_case: bool) -> Self {
+        let pattern = if ignore_case {
+            pattern.to_lowercase()
+        } else {
+            pattern.to_string()
+        };
+        let alternatives = expand_braces(&pattern)
+            .iter()
+            .map(|pattern| {
+                pattern
+                    .split(is_separator)
+                    .filter(|segment| !segment.is_empty())
+                    .map(|segment| segment.to_string())
+                    .collect()
+            })
+            .collect();
+        Self {
+            alternatives,
+            ignore_case,
+        }
     }
 
-    let name_chars: Vec<char> = name.chars().collect();
-    let pattern_chars: Vec<char> = pattern.chars().collect();
+    /// Check if a file, given as names relative to the base directory, matches the pattern
+    fn matches(&self, names: &[String]) -> bool {
+        let names = self.fold_case(names);
+        self.alternatives
+            .iter()
+            .any(|segments| match_segments(segments, &names))
+    }
 
-    let mut name_idx = 0;
-    let mut pattern_idx = 0;
-    let mut star_idx = -1;
-    let mut match_idx = -1;
+    /// Check if files inside a directory, given as names relative to the base directory,
+    /// may match the pattern
+    fn may_match_inside(&self, names: &[String]) -> bool {
+        let names = self.fold_case(names);
+        self.alternatives
+            .iter()
+            .any(|segments| match_dir_prefix(segments, &names))
+    }
 
-    while name_idx < name_chars.len() {
-        if pattern_idx < pattern_chars.len()
-            && (pattern_chars[pattern_idx] == '?'
-                || pattern_chars[pattern_idx] == name_chars[name_idx])
-        {
-            name_idx += 1;
-            pattern_idx += 1;
-        } else if pattern_idx < pattern_chars.len() && pattern_chars[pattern_idx] == '*' {
-            star_idx = pattern_idx as i32;
-            match_idx = name_idx as i32;
-            pattern_idx += 1;
-        } else if star_idx != -1 {
-            pattern_idx = (star_idx + 1) as usize;
-            match_idx += 1;
-            name_idx = match_idx as usize;
+    fn fold_case<'a>(&self, names: &'a [String]) -> Cow<'a, [String]> {
+        if self.ignore_case {
+            Cow::Owned(names.iter().map(|name| name.to_lowercase()).collect())
         } else {
-            return None;
+            Cow::Borrowed(names)
         }
     }
+}
 
-    while pattern_idx < pattern_chars.len() && pattern_chars[pattern_idx] == '*' {
-        pattern_idx += 1;
+/// Check if a relative path matches a pattern, in the same syntax as `glob`
+pub(crate) fn match_path(pattern: &str, path: &str) -> bool {
+    let names: Vec<String> = path
+        .split(is_separator)
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .collect();
+    CompiledPattern::new(pattern, false).matches(&names)
+}
+
+/// Check if the path names can be the leading directories of a path matching the segments
+fn match_dir_prefix(segments: &[String], names: &[String]) -> bool {
+    match (segments.split_first(), names.split_first()) {
+        (_, None) => true,
+        (None, Some(_)) => false,
+        (Some((segment, _)), Some(_)) if segment == "**" => true,
+        (Some((segment, rest)), Some((name, names))) => {
+            match_name(segment, name) && match_dir_prefix(rest, names)
+        }
+    }
+}
+
+/// Path separators accepted in patterns, `/` is accepted on every platform
+fn is_separator(c: char) -> bool {
+    c == '/' || SPLIT_STR.contains(c)
+}
+
+/// `\` escapes the next character, unless it is the path separator of the platform
+fn is_escape(c: char) -> bool {
+    c == '\\' && !is_separator(c)
+}
+
+/// Check if a pattern contains unescaped wildcards, classes or alternatives
+fn has_wildcard(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if is_escape(c) => {
+                chars.next();
+            }
+            '*' | '?' | '[' | '{' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Remove the escapes of a pattern without wildcards
+fn unescape(pattern: &str) -> String {
+    let mut result = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if is_escape(c) => result.extend(chars.next()),
+            c => result.push(c),
+        }
     }
+    result
+}
+
+/// Expand the `{a,b}` alternatives of a pattern, nested alternatives are supported.
+/// Unbalanced braces are kept literally
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut depth = 0;
+    let mut bounds = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            c if is_escape(c) => i += 1,
+            '{' => {
+                if depth == 0 {
+                    bounds = vec![i];
+                }
+                depth += 1;
+            }
+            ',' if depth == 1 => bounds.push(i),
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    bounds.push(i);
+                    let prefix: String = chars[..bounds[0]].iter().collect();
+                    let suffix: String = chars[i + 1..].iter().collect();
+                    return bounds
+                        .windows(2)
+                        .flat_map(|w| {
+                            let alternative: String = chars[w[0] + 1..w[1]].iter().collect();
+                            expand_braces(&format!("{}{}{}", prefix, alternative, suffix))
+                        })
+                        .collect();
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    vec![pattern.to_string()]
+}
 
-    if pattern_idx == pattern_chars.len() {
-        Some(name.to_string())
-    } else {
-        None
+/// Match path names against pattern segments, `**` matches any number of directories
+pub(crate) fn match_segments<S: AsRef<str>>(segments: &[String], names: &[S]) -> bool {
+    match segments.split_first() {
+        None => names.is_empty(),
+        Some((segment, rest)) if segment == "**" => {
+            // A trailing `**` matches everything inside, but not the directory itself
+            let min = if rest.is_empty() { 1 } else { 0 };
+            (min..=names.len()).any(|skip| match_segments(rest, &names[skip..]))
+        }
+        Some((segment, rest)) => match names.split_first() {
+            Some((name, names)) => {
+                match_name(segment, name.as_ref()) && match_segments(rest, names)
+            }
+            None => false,
+        },
     }
 }
 
+/// Match a single name against a pattern with `*`, `?`, `[...]` and `\` escapes
+pub(crate) fn match_name(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    match_chars(&pattern, &name)
+}
+
+fn match_chars(pattern: &[char], name: &[char]) -> bool {
+    let Some((&p, rest)) = pattern.split_first() else {
+        return name.is_empty();
+    };
+
+    match p {
+        '*' => (0..=name.len()).any(|skip| match_chars(rest, &name[skip..])),
+        '?' => !name.is_empty() && match_chars(rest, &name[1..]),
+        '[' => match (name.first(), match_class(rest)) {
+            (Some(&c), Some((matcher, rest))) => matcher(c) && match_chars(rest, &name[1..]),
+            // Unclosed bracket, match it literally
+            (Some(&'['), None) => match_chars(rest, &name[1..]),
+            _ => false,
+        },
+        '\\' if !rest.is_empty() => {
+            name.first() == Some(&rest[0]) && match_chars(&rest[1..], &name[1..])
+        }
+        _ => name.first() == Some(&p) && match_chars(rest, &name[1..]),
+    }
+}
+
+/// Parse a character class after `[`, returns the matcher and the remaining pattern
+fn match_class(pattern: &[char]) -> Option<(impl Fn(char) -> bool, &[char])> {
+    let (negated, pattern) = match pattern.first() {
+        Some('!') | Some('^') => (true, &pattern[1..]),
+        _ => (false, pattern),
+    };
+
+    // `]` right after `[` is a literal
+    let end = pattern
+        .iter()
+        .skip(1)
+        .position(|c| *c == ']')
+        .map(|i| i + 1)?;
+    let class: Vec<char> = pattern[..end].to_vec();
+
+    let matcher = move |c: char| {
+        let mut i = 0;
+        let mut matched = false;
+        while i < class.len() {
+            if i + 2 < class.len() && class[i + 1] == '-' {
+                matched |= class[i] <= c && c <= class[i + 2];
+                i += 3;
+            } else {
+                matched |= class[i] == c;
+                i += 1;
+            }
+        }
+        matched != negated
+    };
+
+    Some((matcher, &pattern[end + 1..]))
+}
+
 impl<T: AsRef<str>> From<T> for Globber {
     fn from(pattern: T) -> Self {
         let (base_dir, pattern) = get_base_dir_current(pattern.as_ref().to_string());
@@ -206,6 +439,61 @@ impl std::fmt::Display for GlobItem {
 
 impl Eq for GlobItem {}
 
+/// Paths indexed by their names, such as the mappings of a sheet,
+/// so that the items of a directory are listed without scanning every path
+#[derive(Debug, Default)]
+pub struct PathTrie {
+    children: HashMap<String, PathTrie>,
+    is_file: bool,
+}
+
+impl PathTrie {
+    /// Insert the path of a file
+    pub fn insert(&mut self, path: &Path) {
+        let node = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .fold(self, |node, name| node.children.entry(name).or_default());
+        node.is_file = true;
+    }
+
+    /// List the files and directories directly under a directory
+    pub fn items(&self, dir: &Path) -> Vec<GlobItem> {
+        let node = dir
+            .components()
+            .try_fold(self, |node, component| match component {
+                Component::Normal(name) => node.children.get(name.to_string_lossy().as_ref()),
+                _ => Some(node),
+            });
+
+        let Some(node) = node else {
+            return Vec::new();
+        };
+
+        let mut items = Vec::new();
+        for (name, child) in &node.children {
+            if child.is_file {
+                items.push(GlobItem::File(name.clone()));
+            }
+            if !child.children.is_empty() {
+                items.push(GlobItem::Directory(name.clone()));
+            }
+        }
+        items
+    }
+}
+
+impl<P: AsRef<Path>> FromIterator<P> for PathTrie {
+    fn from_iter<T: IntoIterator<Item = P>>(iter: T) -> Self {
+        let mut trie = Self::default();
+        iter.into_iter().for_each(|path| trie.insert(path.as_ref()));
+        trie
+    }
+}
+
 pub mod constants {
     use std::{env::current_dir, path::PathBuf};
 
@@ -274,3 +562,209 @@ pub mod constants {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    const SHEET_FILES: &[&str] = &[
+        "README.md",
+        "src/main.rs",
+        "src/lib.rs",
+        "src/utils/fs.rs",
+        "assets/icon.png",
+        "assets/icon.psd",
+        "assets/raw/bg.png",
+        "docs/[draft].md",
+    ];
+
+    /// List the items directly under `dir` from the mappings of a sheet
+    fn sheet_names(root: &Path, dir: PathBuf) -> Vec<GlobItem> {
+        let Ok(relative) = dir.strip_prefix(root) else {
+            return Vec::new();
+        };
+        SHEET_FILES.iter().collect::<PathTrie>().items(relative)
+    }
+
+    /// List the items directly under `dir` from the file system, like `get_local_files`
+    fn local_names(dir: PathBuf) -> Vec<GlobItem> {
+        std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| {
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        if entry.path().is_dir() {
+                            GlobItem::Directory(name)
+                        } else {
+                            GlobItem::File(name)
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Glob and return the sorted paths relative to `root`, like `glob` of `jv`
+    fn glob_names<F>(root: &Path, pattern: &str, get_names: F) -> Vec<String>
+    where
+        F: Fn(PathBuf) -> Vec<GlobItem>,
+    {
+        let globber = Globber::new(pattern.to_string(), root.to_path_buf())
+            .glob(get_names)
+            .unwrap();
+        let relative = globber.base().strip_prefix(root).unwrap().to_path_buf();
+        let mut names: Vec<String> = globber
+            .names()
+            .into_iter()
+            .map(|name| relative.join(name).display().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn glob_sheet(pattern: &str) -> Vec<String> {
+        let root = PathBuf::from("/workspace");
+        glob_names(&root, pattern, |dir| sheet_names(&root, dir))
+    }
+
+    #[test]
+    fn test_star_stops_at_separator() {
+        assert_eq!(glob_sheet("*.md"), vec!["README.md"]);
+        assert_eq!(glob_sheet("src/*.rs"), vec!["src/lib.rs", "src/main.rs"]);
+        assert_eq!(glob_sheet("*/*.png"), vec!["assets/icon.png"]);
+    }
+
+    #[test]
+    fn test_double_star() {
+        assert_eq!(
+            glob_sheet("**/*.png"),
+            vec!["assets/icon.png", "assets/raw/bg.png"]
+        );
+        assert_eq!(
+            glob_sheet("src/**"),
+            vec!["src/lib.rs", "src/main.rs", "src/utils/fs.rs"]
+        );
+        assert_eq!(glob_sheet("src/"), glob_sheet("src/**"));
+        assert_eq!(glob_sheet(".").len(), SHEET_FILES.len());
+    }
+
+    #[test]
+    fn test_classes_and_braces() {
+        assert_eq!(
+            glob_sheet("src/[lm]*.rs"),
+            vec!["src/lib.rs", "src/main.rs"]
+        );
+        assert_eq!(glob_sheet("src/[!l]*.rs"), vec!["src/main.rs"]);
+        assert_eq!(
+            glob_sheet("assets/icon.{png,psd}"),
+            vec!["assets/icon.png", "assets/icon.psd"]
+        );
+        assert_eq!(
+            glob_sheet("{src/utils,assets/raw}/*"),
+            vec!["assets/raw/bg.png", "src/utils/fs.rs"]
+        );
+        assert_eq!(expand_braces("a{b,c{d,e}}f"), vec!["abf", "acdf", "acef"]);
+        assert_eq!(expand_braces("a{b,c"), vec!["a{b,c"]);
+    }
+
+    #[test]
+    fn test_path_trie_items() {
+        let trie: PathTrie = SHEET_FILES.iter().collect();
+        let mut items = trie.items(Path::new("src"));
+        items.sort_by_key(|item| item.to_string());
+        assert_eq!(
+            items,
+            vec![
+                GlobItem::File("lib.rs".to_string()),
+                GlobItem::File("main.rs".to_string()),
+                GlobItem::Directory("utils".to_string()),
+            ]
+        );
+        assert!(trie.items(Path::new("missing")).is_empty());
+        assert_eq!(trie.items(Path::new("")).len(), 4);
+    }
+
+    #[test]
+    fn test_walk_skips_unmatched_directories() {
+        let root = PathBuf::from("/workspace");
+        let listed = std::cell::RefCell::new(Vec::new());
+        let glob_listed = |pattern: &str| {
+            listed.borrow_mut().clear();
+            glob_names(&root, pattern, |dir| {
+                let relative = dir.strip_prefix(&root).unwrap().display().to_string();
+                listed.borrow_mut().push(relative);
+                sheet_names(&root, dir)
+            });
+            let mut listed = listed.borrow().clone();
+            listed.sort();
+            listed
+        };
+
+        assert_eq!(glob_listed("src/*.rs"), vec!["src"]);
+        assert_eq!(
+            glob_listed("*/raw/*.png"),
+            vec!["", "assets", "assets/raw", "docs", "src"]
+        );
+        assert_eq!(glob_listed("src/**/*.rs"), vec!["src", "src/utils"]);
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        let root = PathBuf::from("/workspace");
+        let glob_ignore_case = |pattern: &str| {
+            let globber = Globber::new(pattern.to_string(), root.clone())
+                .with_ignore_case(true)
+                .glob(|dir| sheet_names(&root, dir))
+                .unwrap();
+            let mut names: Vec<String> = globber.into_names();
+            names.sort();
+            names
+        };
+
+        assert!(glob_sheet("*.PNG").is_empty());
+        assert!(glob_sheet("ASSETS/*.png").is_empty());
+        assert_eq!(glob_ignore_case("ASSETS/*.PNG"), vec!["assets/icon.png"]);
+        assert_eq!(glob_ignore_case("readme.*"), vec!["README.md"]);
+        assert_eq!(glob_ignore_case("SRC/[L]*"), vec!["src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_case_collisions() {
+        let mut globber = Globber::new("*".to_string(), PathBuf::new());
+        globber.names = vec!["a.png".into(), "A.PNG".into(), "b.png".into()];
+        assert_eq!(globber.case_collisions(), vec![vec!["a.png", "A.PNG"]]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_escapes() {
+        assert_eq!(glob_sheet("docs/\\[draft\\].md"), vec!["docs/[draft].md"]);
+        assert_eq!(glob_sheet("docs/\\[draft\\]*"), vec!["docs/[draft].md"]);
+        assert!(glob_sheet("docs/[draft].md").is_empty());
+    }
+
+    #[test]
+    fn test_local_mode() {
+        let root = std::env::temp_dir().join(format!("jv_globber_{}", std::process::id()));
+        for file in ["a.txt", "b.txt", "sub/c.txt", "sub/deep/d.txt", "img/x.png"] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let glob_local = |pattern: &str| glob_names(&root, pattern, local_names);
+        assert_eq!(glob_local("*.txt"), vec!["a.txt", "b.txt"]);
+        assert_eq!(glob_local("**/*.txt").len(), 4);
+        assert_eq!(
+            glob_local("sub/**/*.txt"),
+            vec!["sub/c.txt", "sub/deep/d.txt"]
+        );
+        assert_eq!(glob_local("img/x.{png,jpg}"), vec!["img/x.png"]);
+        assert_eq!(glob_local("?.txt"), vec!["a.txt", "b.txt"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
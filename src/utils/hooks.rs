@@ -0,0 +1,191 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use just_enough_vcs::vcs::constants::CLIENT_PATH_WORKSPACE_ROOT;
+use serde::Serialize;
+use tokio::{io::AsyncWriteExt, process::Command};
+
+/// Directory of the hook scripts, inside the workspace directory
+pub const HOOKS_DIR: &str = "hooks";
+
+/// Extensions tried when looking for a hook script on Windows
+#[cfg(windows)]
+const HOOK_EXTENSIONS: &[&str] = &["exe", "bat", "cmd"];
+
+/// When a hook runs, relative to its command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookStage {
+    /// Before the command, the command is aborted if the hook fails
+    Pre,
+
+    /// After the command, with its result
+    Post,
+}
+
+impl HookStage {
+    /// Name of the hook script of a command at this stage, e.g. `pre-track`
+    pub fn hook_name(self, command: &str) -> String {
+        match self {
+            HookStage::Pre => format!("pre-{}", command),
+            HookStage::Post => format!("post-{}", command),
+        }
+    }
+}
+
+/// Context written to the stdin of a hook as JSON
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct HookContext {
+    pub hook: String,
+    pub command: String,
+    pub workspace: PathBuf,
+    pub paths: Vec<PathBuf>,
+
+    /// Exit code of the command, only for post hooks
+    pub exit_code: Option<i32>,
+}
+
+/// Result of a hook that ran
+pub struct HookOutput {
+    pub success: bool,
+
+    /// Exit code of the script, `None` if it was killed by a signal
+    pub code: Option<i32>,
+
+    /// Stdout followed by stderr of the script
+    pub output: String,
+}
+
+/// Find the script of a hook in the workspace, `None` if the workspace has no such hook
+pub fn hook_path(local_dir: &Path, hook: &str) -> Option<PathBuf> {
+    let dir = local_dir.join(CLIENT_PATH_WORKSPACE_ROOT).join(HOOKS_DIR);
+
+    #[cfg(windows)]
+    {
+        HOOK_EXTENSIONS
+            .iter()
+            .map(|ext| dir.join(format!("{}.{}", hook, ext)))
+            .chain(std::iter::once(dir.join(hook)))
+            .find(|path| path.is_file())
+    }
+
+    #[cfg(not(windows))]
+    {
+        Some(dir.join(hook)).filter(|path| path.is_file())
+    }
+}
+
+/// Run a hook from the workspace root, with the context, including the affected paths, as JSON on stdin.
+/// Returns `None` if the workspace has no such hook
+pub async fn run_hook(
+    local_dir: &Path,
+    context: &HookContext,
+) -> Result<Option<HookOutput>, std::io::Error> {
+    let Some(script) = hook_path(local_dir, &context.hook) else {
+        return Ok(None);
+    };
+
+    let mut child = Command::new(script)
+        .current_dir(local_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Written while the output is read, the hook may fill its stdout before reading stdin
+    let json = serde_json::to_vec(context).unwrap_or_default();
+    let stdin = child.stdin.take().map(|mut stdin| {
+        tokio::spawn(async move {
+            // The hook may exit without reading its stdin
+            let _ = stdin.write_all(&json).await;
+        })
+    });
+
+    let output = child.wait_with_output().await?;
+    if let Some(stdin) = stdin {
+        let _ = stdin.await;
+    }
+    let text = [output.stdout, output.stderr]
+        .iter()
+        .map(|bytes| String::from_utf8_lossy(bytes).trim().to_string())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(Some(HookOutput {
+        success: output.status.success(),
+        code: output.status.code(),
+        output: text,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_hook() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = std::env::temp_dir().join(format!("jv_hooks_{}", std::process::id()));
+        let dir = root.join(CLIENT_PATH_WORKSPACE_ROOT).join(HOOKS_DIR);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Echo the stdin, fail when it mentions `bad.png`
+        let script = dir.join("pre-track");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\ninput=$(cat)\necho \"$input\"\ncase \"$input\" in *bad.png*) exit 1;; esac\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        // Fill stdout before reading stdin
+        let script = dir.join("pre-hold");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nhead -c 200000 /dev/zero | tr '\\0' x\ncat > /dev/null\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let context = |paths: &[&str]| HookContext {
+            hook: HookStage::Pre.hook_name("track"),
+            command: "track".to_string(),
+            workspace: root.clone(),
+            paths: paths.iter().map(PathBuf::from).collect(),
+            exit_code: None,
+        };
+
+        let output = run_hook(&root, &context(&["a.png", "b.png"]))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(output.success);
+        assert!(output.output.starts_with("{\"Hook\":\"pre-track\""));
+        assert!(output.output.contains("\"Paths\":[\"a.png\",\"b.png\"]"));
+
+        let output = run_hook(&root, &context(&["bad.png"]))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!output.success);
+        assert_eq!(output.code, Some(1));
+
+        let paths: Vec<String> = (0..10000).map(|i| format!("{}.png", i)).collect();
+        let mut hold = context(&paths.iter().map(String::as_str).collect::<Vec<_>>());
+        hold.hook = HookStage::Pre.hook_name("hold");
+        let output = run_hook(&root, &hold).await.unwrap().unwrap();
+        assert!(output.success);
+        assert_eq!(output.output.len(), 200000);
+
+        let mut post = context(&[]);
+        post.hook = HookStage::Post.hook_name("track");
+        assert!(run_hook(&root, &post).await.unwrap().is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
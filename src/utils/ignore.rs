@@ -0,0 +1,274 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::utils::{
+    config::ConfigKey,
+    env::config_list,
+    globber::{match_name, match_segments},
+};
+
+/// Name of the ignore file, it can be placed in any directory of the workspace
+pub const IGNORE_FILE_NAME: &str = ".jvignore";
+
+/// Rules of the `.jvignore` files of a workspace, in the same syntax as `.gitignore`
+///
+/// Each directory may contain a `.jvignore`, whose patterns are relative to that directory.
+/// Rules of deeper files and later lines take precedence, and `!` re-includes a path.
+/// The patterns of `ignore.patterns` in the configuration of the user, then of the workspace,
+/// apply to the whole workspace with the lowest precedence.
+///
+/// Ignore files are read when a path under their directory is checked for the first time
+pub struct IgnoreRules {
+    root: PathBuf,
+    global: Vec<IgnoreRule>,
+    dirs: RefCell<HashMap<PathBuf, Vec<IgnoreRule>>>,
+}
+
+impl IgnoreRules {
+    /// Load the ignore rules of the workspace at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self::with_patterns(root, &config_list(ConfigKey::IgnorePatterns))
+    }
+
+    /// Load the ignore rules of the workspace at `root`, with `patterns` applying to the whole workspace
+    pub fn with_patterns(root: impl Into<PathBuf>, patterns: &[String]) -> Self {
+        let global = patterns
+            .iter()
+            .filter_map(|pattern| IgnoreRule::parse(pattern))
+            .collect();
+
+        Self {
+            root: root.into(),
+            global,
+            dirs: RefCell::default(),
+        }
+    }
+
+    /// Check if a path is ignored, the path is either absolute or relative to the workspace root.
+    /// Paths inside an ignored directory are ignored too
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let names: Vec<&str> = relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+
+        (1..=names.len()).any(|depth| {
+            let is_dir = depth < names.len() || is_dir;
+            self.match_rules(&names[..depth], is_dir)
+        })
+    }
+
+    fn match_rules(&self, names: &[&str], is_dir: bool) -> bool {
+        let mut ignored = false;
+
+        for rule in &self.global {
+            if rule.matches(names, is_dir) {
+                ignored = !rule.negated;
+            }
+        }
+
+        // Ignore files from the workspace root to the parent directory of the path
+        for depth in 0..names.len() {
+            let dir: PathBuf = names[..depth].iter().collect();
+            self.load_dir_rules(&dir);
+            for rule in &self.dirs.borrow()[&dir] {
+                if rule.matches(&names[depth..], is_dir) {
+                    ignored = !rule.negated;
+                }
+            }
+        }
+
+        ignored
+    }
+
+    fn load_dir_rules(&self, dir: &Path) {
+        if self.dirs.borrow().contains_key(dir) {
+            return;
+        }
+
+        let rules = std::fs::read_to_string(self.root.join(dir).join(IGNORE_FILE_NAME))
+            .map(|content| parse_rules(&content))
+            .unwrap_or_default();
+        self.dirs.borrow_mut().insert(dir.to_path_buf(), rules);
+    }
+}
+
+fn parse_rules(content: &str) -> Vec<IgnoreRule> {
+    content.lines().filter_map(IgnoreRule::parse).collect()
+}
+
+/// A single line of an ignore file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IgnoreRule {
+    /// Pattern, split by `/`
+    segments: Vec<String>,
+
+    /// Re-include the matched paths (`!pattern`)
+    negated: bool,
+
+    /// Only match directories (`pattern/`)
+    dir_only: bool,
+
+    /// Match the whole path relative to the ignore file (the pattern contains `/`),
+    /// otherwise match the name at any depth
+    anchored: bool,
+}
+
+impl IgnoreRule {
+    fn parse(line: &str) -> Option<Self> {
+        // Trailing spaces are ignored unless escaped
+        let mut line = line.trim_end_matches(['\r', '\n']);
+        while line.ends_with(' ') && !line.ends_with("\\ ") {
+            line = &line[..line.len() - 1];
+        }
+
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        let anchored = line.contains('/');
+        let segments: Vec<String> = line
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| segment.to_string())
+            .collect();
+
+        if segments.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            segments,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    /// Check if the rule matches a path, given as names relative to the ignore file
+    fn matches(&self, names: &[&str], is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+
+        if self.anchored {
+            match_segments(&self.segments, names)
+        } else {
+            names
+                .last()
+                .is_some_and(|name| match_name(&self.segments[0], name))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(line: &str) -> IgnoreRule {
+        IgnoreRule::parse(line).unwrap()
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_blanks() {
+        assert!(IgnoreRule::parse("").is_none());
+        assert!(IgnoreRule::parse("   ").is_none());
+        assert!(IgnoreRule::parse("# comment").is_none());
+        assert_eq!(rule("\\#file").segments, vec!["#file"]);
+    }
+
+    #[test]
+    fn test_unanchored_matches_any_depth() {
+        let r = rule("*.tmp");
+        assert!(r.matches(&["a.tmp"], false));
+        assert!(r.matches(&["dir", "sub", "a.tmp"], false));
+        assert!(!r.matches(&["a.tmp.txt"], false));
+    }
+
+    #[test]
+    fn test_anchored_and_double_star() {
+        assert!(rule("/build").matches(&["build"], true));
+        assert!(!rule("/build").matches(&["src", "build"], true));
+        assert!(rule("docs/*.md").matches(&["docs", "a.md"], false));
+        assert!(!rule("docs/*.md").matches(&["docs", "sub", "a.md"], false));
+        assert!(rule("**/cache").matches(&["a", "b", "cache"], true));
+        assert!(rule("**/cache").matches(&["cache"], true));
+        assert!(rule("out/**").matches(&["out", "a", "b.o"], false));
+        assert!(!rule("out/**").matches(&["out"], true));
+    }
+
+    #[test]
+    fn test_dir_only_and_negation() {
+        let r = rule("target/");
+        assert!(r.matches(&["target"], true));
+        assert!(!r.matches(&["target"], false));
+        let r = rule("!keep.log");
+        assert!(r.negated);
+        assert!(r.matches(&["keep.log"], false));
+    }
+
+    #[test]
+    fn test_match_name_wildcards() {
+        assert!(match_name("?.swp", "a.swp"));
+        assert!(match_name(".*.sw[op]", ".main.rs.swo"));
+        assert!(!match_name(".*.sw[op]", ".main.rs.swx"));
+        assert!(match_name("[!a-c]x", "dx"));
+        assert!(!match_name("[!a-c]x", "bx"));
+        assert!(match_name("\\*literal", "*literal"));
+        assert!(!match_name("\\*literal", "xliteral"));
+    }
+
+    #[test]
+    fn test_ignore_files() {
+        let root = std::env::temp_dir().join(format!("jv_ignore_{}", std::process::id()));
+        for (file, content) in [
+            (".jvignore", "*.log\nbuild/\n"),
+            ("assets/.jvignore", "!keep.log\n/raw\n"),
+            ("assets/sub/.jvignore", "*.psd\n"),
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        let patterns = ["*.tmp".to_string(), "!important.tmp".to_string()];
+        let rules = IgnoreRules::with_patterns(&root, &patterns);
+        let ignored = |path: &str| rules.is_ignored(Path::new(path), false);
+
+        assert!(ignored("a.log"));
+        assert!(ignored("assets/other.log"));
+        // Deeper files re-include what the files above ignore
+        assert!(!ignored("assets/keep.log"));
+        assert!(!ignored("assets/sub/keep.log"));
+        assert!(ignored("build/out.bin"));
+        assert!(!ignored("src/build"));
+        assert!(rules.is_ignored(&root.join("src/build"), true));
+        // Anchored patterns are relative to their ignore file
+        assert!(ignored("assets/raw/a.png"));
+        assert!(!ignored("raw/a.png"));
+        assert!(ignored("assets/sub/a.psd"));
+        assert!(!ignored("a.psd"));
+        // Patterns of the configuration apply everywhere, in order
+        assert!(ignored("assets/sub/cache.tmp"));
+        assert!(!ignored("important.tmp"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -55,6 +55,35 @@ where
     confirmed
 }
 
+/// Read the content of a file, or of stdin if the path is `-`
+pub async fn read_text(from: &str) -> Result<String, std::io::Error> {
+    use tokio::io::AsyncReadExt;
+
+    if from == "-" {
+        let mut content = String::new();
+        tokio::io::stdin().read_to_string(&mut content).await?;
+        Ok(content)
+    } else {
+        fs::read_to_string(from).await
+    }
+}
+
+/// Read a list of paths from a file, or from stdin if the path is `-`
+/// Paths are separated by newlines, or by NUL characters if the content contains any
+pub async fn read_path_list(from: &str) -> Result<Vec<String>, std::io::Error> {
+    Ok(split_path_list(&read_text(from).await?))
+}
+
+fn split_path_list(content: &str) -> Vec<String> {
+    let separator = if content.contains('\0') { '\0' } else { '\n' };
+    content
+        .split(separator)
+        .map(|path| path.trim_end_matches(['\r', '\n']))
+        .filter(|path| !path.trim().is_empty())
+        .map(|path| path.to_string())
+        .collect()
+}
+
 /// Input text using the system editor
 /// Opens the system editor (from EDITOR environment variable) with default text in a cache file,
 /// then reads back the modified content after the editor closes, removing comment lines
@@ -137,3 +166,20 @@ pub async fn show_in_pager(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_path_list() {
+        assert_eq!(
+            split_path_list("a.png\r\nb/c.png\n\n"),
+            vec!["a.png", "b/c.png"]
+        );
+        assert_eq!(
+            split_path_list("with space.png\0line\nbreak.png\0"),
+            vec!["with space.png", "line\nbreak.png"]
+        );
+    }
+}
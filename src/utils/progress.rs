@@ -0,0 +1,161 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// Progress of a batch of file transfers, reported by the messages of the action
+#[derive(Debug, Clone, Default)]
+pub struct TransferProgress {
+    /// Size of each file in bytes, 0 if unknown
+    sizes: HashMap<PathBuf, u64>,
+    done: HashSet<PathBuf>,
+
+    /// File finished last
+    last: Option<PathBuf>,
+}
+
+impl TransferProgress {
+    pub fn new(files: impl IntoIterator<Item = (PathBuf, u64)>) -> Self {
+        Self {
+            sizes: files.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn mark_done(&mut self, path: PathBuf) {
+        self.sizes.entry(path.clone()).or_default();
+        self.done.insert(path.clone());
+        self.last = Some(path);
+    }
+
+    /// Mark the files quoted in a message of the action, e.g. ``Synced `a.png` ``, as done.
+    /// Only whole quoted paths count, `a.png` doesn't match `data.png` or `sub/a.png`
+    pub fn apply_message(&mut self, message: &str) {
+        let quoted: HashSet<String> = message
+            .split('`')
+            .skip(1)
+            .step_by(2)
+            .map(normalize_path)
+            .collect();
+        let mentioned: Vec<PathBuf> = self
+            .sizes
+            .keys()
+            .filter(|path| !self.done.contains(*path))
+            .filter(|path| quoted.contains(&normalize_path(&path.display().to_string())))
+            .cloned()
+            .collect();
+        for path in mentioned {
+            self.mark_done(path);
+        }
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.sizes.values().sum()
+    }
+
+    pub fn sent_bytes(&self) -> u64 {
+        self.done
+            .iter()
+            .filter_map(|path| self.sizes.get(path))
+            .sum()
+    }
+
+    pub fn files_total(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn files_done(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn last_file(&self) -> Option<&PathBuf> {
+        self.last.as_ref()
+    }
+
+    /// Finished part, from 0.0 to 1.0. Counted in bytes, or in files if the sizes are unknown
+    pub fn fraction(&self) -> f64 {
+        let total_bytes = self.total_bytes();
+        if total_bytes > 0 {
+            self.sent_bytes() as f64 / total_bytes as f64
+        } else if self.files_total() > 0 {
+            self.files_done() as f64 / self.files_total() as f64
+        } else {
+            1.0
+        }
+    }
+
+    /// Estimated remaining time, from the time spent so far
+    pub fn eta(&self, elapsed: Duration) -> Option<Duration> {
+        let fraction = self.fraction();
+        if fraction <= 0.0 || fraction >= 1.0 {
+            return None;
+        }
+        Some(elapsed.mul_f64((1.0 - fraction) / fraction))
+    }
+}
+
+/// Compare paths of messages regardless of the separator
+fn normalize_path(path: &str) -> String {
+    path.trim().replace('\\', "/")
+}
+
+/// Render a progress bar of the given width, e.g. `[#####-----]`
+pub fn progress_bar(fraction: f64, width: usize) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * width as f64) as usize).min(width);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+/// Render a duration briefly, e.g. `1h02m`, `3m05s` or `12s`
+pub fn duration_str(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_progress() {
+        let mut progress = TransferProgress::new([
+            (PathBuf::from("a.psd"), 300),
+            (PathBuf::from("b.png"), 100),
+            (PathBuf::from("sub/b.png"), 100),
+        ]);
+        assert_eq!(progress.total_bytes(), 500);
+
+        progress.apply_message("Uploaded `a.psd` as 1.0.1");
+        assert_eq!(progress.sent_bytes(), 300);
+        assert_eq!(progress.last_file(), Some(&PathBuf::from("a.psd")));
+        assert_eq!(
+            progress.eta(Duration::from_secs(30)),
+            Some(Duration::from_secs(20))
+        );
+
+        // Paths contained in other paths are not matched
+        progress.apply_message("Synced `data.b.png` and `sub/b.png`");
+        assert_eq!(progress.files_done(), 2);
+        assert_eq!(progress.sent_bytes(), 400);
+
+        progress.apply_message("Updated `b.png` to 1.0.1");
+        assert_eq!(progress.files_done(), 3);
+        assert_eq!(progress.fraction(), 1.0);
+        assert_eq!(progress.eta(Duration::from_secs(10)), None);
+    }
+
+    #[test]
+    fn test_progress_rendering() {
+        assert_eq!(progress_bar(0.5, 10), "[#####-----]");
+        assert_eq!(progress_bar(1.5, 4), "[####]");
+        assert_eq!(duration_str(Duration::from_secs(12)), "12s");
+        assert_eq!(duration_str(Duration::from_secs(185)), "3m05s");
+        assert_eq!(duration_str(Duration::from_secs(3720)), "1h02m");
+    }
+}
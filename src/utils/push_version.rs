@@ -1,30 +1,202 @@
-pub fn push_version(current_version: impl Into<String>) -> Option<String> {
-    let version_str = current_version.into();
-    let parts: Vec<&str> = version_str.split('.').collect();
+use chrono::{Datelike, NaiveDate};
 
-    if parts.len() != 3 {
-        return None;
+use crate::utils::globber::match_path;
+
+/// Part of a semantic version to increase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VersionBump {
+    Major,
+    Minor,
+    #[default]
+    Patch,
+}
+
+impl VersionBump {
+    pub fn parse(bump: &str) -> Option<Self> {
+        match bump.trim().to_lowercase().as_str() {
+            "major" => Some(Self::Major),
+            "minor" => Some(Self::Minor),
+            "patch" => Some(Self::Patch),
+            _ => None,
+        }
     }
+}
 
-    let major: Result<u32, _> = parts[0].parse();
-    let minor: Result<u32, _> = parts[1].parse();
-    let patch: Result<u32, _> = parts[2].parse();
+/// How the next version of a file is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VersionScheme {
+    /// `X.Y.Z`, the bump decides which part is increased
+    #[default]
+    Semver,
 
-    if let (Ok(mut major), Ok(mut minor), Ok(mut patch)) = (major, minor, patch) {
-        patch += 1;
+    /// `YYYY.MM.DD.n`, `n` counts the versions of the same day
+    Date,
 
-        if patch > 99 {
-            patch = 0;
-            minor += 1;
+    /// A plain integer counter
+    Counter,
+}
+
+impl VersionScheme {
+    pub fn parse(scheme: &str) -> Option<Self> {
+        match scheme.trim().to_lowercase().as_str() {
+            "semver" => Some(Self::Semver),
+            "date" => Some(Self::Date),
+            "counter" => Some(Self::Counter),
+            _ => None,
+        }
+    }
 
-            if minor > 99 {
-                minor = 0;
-                major += 1;
+    /// Next version after `current`, `None` if `current` can't be continued by this scheme
+    pub fn next_version(
+        self,
+        current: &str,
+        bump: VersionBump,
+        today: NaiveDate,
+    ) -> Option<String> {
+        let numbers: Vec<Option<u64>> = current
+            .split(['.', '-'])
+            .map(|part| part.trim().parse().ok())
+            .collect();
+
+        match self {
+            Self::Semver => {
+                let [Some(major), Some(minor), Some(patch)] = numbers[..] else {
+                    return None;
+                };
+                let (major, minor, patch) = match bump {
+                    VersionBump::Major => (major + 1, 0, 0),
+                    VersionBump::Minor => (major, minor + 1, 0),
+                    VersionBump::Patch => (major, minor, patch + 1),
+                };
+                Some(format!("{}.{}.{}", major, minor, patch))
+            }
+            Self::Date => {
+                let today_parts = [
+                    today.year() as u64,
+                    today.month() as u64,
+                    today.day() as u64,
+                ];
+                // Continue the count of the current day, or of a later day if the clock is behind
+                match numbers[..] {
+                    [Some(year), Some(month), Some(day), Some(n)]
+                        if [year, month, day] >= today_parts =>
+                    {
+                        Some(format!("{}.{:02}.{:02}.{}", year, month, day, n + 1))
+                    }
+                    _ => Some(format!("{}.1", today.format("%Y.%m.%d"))),
+                }
             }
+            Self::Counter => numbers
+                .first()
+                .copied()
+                .flatten()
+                .map(|counter| (counter + 1).to_string()),
         }
+    }
+}
+
+/// Version schemes chosen by path patterns, one `<pattern> <scheme>` rule per line.
+/// Lines starting with `#` and lines with an unknown scheme are skipped.
+/// Later rules take precedence, and files matching no rule use semver
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VersionSchemeRules {
+    rules: Vec<(String, VersionScheme)>,
+}
+
+impl VersionSchemeRules {
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (pattern, scheme) = line.rsplit_once(char::is_whitespace)?;
+                Some((pattern.trim().to_string(), VersionScheme::parse(scheme)?))
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Scheme of a file, given as a path relative to the workspace
+    pub fn scheme_of(&self, path: &str) -> VersionScheme {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(pattern, _)| match_path(pattern, path))
+            .map(|(_, scheme)| *scheme)
+            .unwrap_or_default()
+    }
+}
+
+/// Compare two versions segment by segment.
+/// Segments are split by `.` or `-`, numeric segments are compared as numbers,
+/// and missing segments are treated as `0`. (e.g. `1.10.0` > `1.9.2`, `2025-06` > `2025.5.30`)
+pub fn compare_versions(a: impl AsRef<str>, b: impl AsRef<str>) -> std::cmp::Ordering {
+    let split =
+        |v: &str| -> Vec<String> { v.split(['.', '-']).map(|s| s.trim().to_string()).collect() };
+    let (a, b) = (split(a.as_ref()), split(b.as_ref()));
+
+    for i in 0..a.len().max(b.len()) {
+        let left = a.get(i).map(|s| s.as_str()).unwrap_or("0");
+        let right = b.get(i).map(|s| s.as_str()).unwrap_or("0");
+        let ordering = match (left.parse::<u64>(), right.parse::<u64>()) {
+            (Ok(l), Ok(r)) => l.cmp(&r),
+            _ => left.cmp(right),
+        };
+        if ordering != std::cmp::Ordering::Equal {
+            return ordering;
+        }
+    }
+    std::cmp::Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_semver_bumps_without_rollover() {
+        let today = day(2026, 1, 1);
+        let next = |current, bump| VersionScheme::Semver.next_version(current, bump, today);
+        assert_eq!(
+            next("1.2.99", VersionBump::Patch),
+            Some("1.2.100".to_string())
+        );
+        assert_eq!(
+            next("1.99.3", VersionBump::Minor),
+            Some("1.100.0".to_string())
+        );
+        assert_eq!(next("1.2.3", VersionBump::Major), Some("2.0.0".to_string()));
+        assert_eq!(next("2026.01.01.1", VersionBump::Patch), None);
+    }
+
+    #[test]
+    fn test_date_and_counter() {
+        let today = day(2026, 3, 7);
+        let date = |current| VersionScheme::Date.next_version(current, VersionBump::Patch, today);
+        assert_eq!(date("2026.03.07.2"), Some("2026.03.07.3".to_string()));
+        assert_eq!(date("2026.03.06.5"), Some("2026.03.07.1".to_string()));
+        assert_eq!(date("2026.03.08.1"), Some("2026.03.08.2".to_string()));
+        assert_eq!(date("1.2.3"), Some("2026.03.07.1".to_string()));
+
+        let counter =
+            |current| VersionScheme::Counter.next_version(current, VersionBump::Major, today);
+        assert_eq!(counter("41"), Some("42".to_string()));
+        assert_eq!(counter("1.2.3"), Some("2".to_string()));
+        assert_eq!(counter("beta"), None);
+    }
 
-        Some(format!("{}.{}.{}", major, minor, patch))
-    } else {
-        None
+    #[test]
+    fn test_scheme_rules() {
+        let rules = VersionSchemeRules::parse(
+            "# assets use dates\nassets/** date\nassets/raw/** counter\nsrc/** unknown\n",
+        );
+        assert_eq!(rules.scheme_of("assets/a.png"), VersionScheme::Date);
+        assert_eq!(rules.scheme_of("assets/raw/a.psd"), VersionScheme::Counter);
+        assert_eq!(rules.scheme_of("src/main.rs"), VersionScheme::Semver);
     }
 }
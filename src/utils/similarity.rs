@@ -0,0 +1,106 @@
+use std::path::Path;
+
+/// Edit distance between two strings, counted in characters
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Similarity of two strings, from 0.0 (nothing in common) to 1.0 (equal)
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / max_len as f64
+}
+
+/// Similarity of two file paths, from 0.0 to 1.0.
+/// The file name weighs more than the directory, and files with different extensions never match
+pub fn path_similarity(a: &Path, b: &Path) -> f64 {
+    if a.extension() != b.extension() {
+        return 0.0;
+    }
+
+    let name = |path: &Path| {
+        path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+    let dir = |path: &Path| {
+        path.parent()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default()
+    };
+
+    similarity(&name(a), &name(b)) * 0.8 + similarity(&dir(a), &dir(b)) * 0.2
+}
+
+/// The candidate closest to a mistyped input, if it is close enough to be a likely typo.
+/// Candidates equal to the input are ignored
+pub fn closest_match<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let lowercase = input.to_lowercase();
+    let max_distance = input.chars().count().div_ceil(3);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != input)
+        .map(|candidate| {
+            (
+                levenshtein(&lowercase, &candidate.to_lowercase()),
+                candidate,
+            )
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_path_similarity() {
+        let moved = path_similarity(Path::new("art/hero.png"), Path::new("chars/hero.png"));
+        let renamed = path_similarity(Path::new("art/hero.png"), Path::new("art/hero_v2.png"));
+        let other = path_similarity(Path::new("art/hero.png"), Path::new("art/tree.png"));
+        assert!(moved >= 0.8);
+        assert!(renamed > other);
+        assert_eq!(
+            path_similarity(Path::new("hero.png"), Path::new("hero.psd")),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let sheets = ["main", "art", "design-v2"];
+        assert_eq!(closest_match("mian", sheets), Some("main"));
+        assert_eq!(closest_match("desing-v2", sheets), Some("design-v2"));
+        assert_eq!(closest_match("main", sheets), None);
+        assert_eq!(closest_match("Main", sheets), Some("main"));
+        assert_eq!(closest_match("audio", sheets), None);
+    }
+}
@@ -0,0 +1,158 @@
+use regex::Regex;
+
+use crate::utils::globber::match_path;
+
+/// Descriptions written below the separator of the update editor
+///
+/// Text before the first section is shared by all files. A `[path]` line starts a section
+/// for the files matching the path or pattern, consecutive section lines share one description.
+/// When several sections match a file, the last one wins
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UpdateDescriptions {
+    /// Description used by files without a section
+    pub shared: String,
+
+    /// Patterns of each section, and their description
+    pub sections: Vec<(Vec<String>, String)>,
+}
+
+impl UpdateDescriptions {
+    pub fn parse(text: &str) -> Self {
+        let mut shared = Vec::new();
+        let mut sections: Vec<(Vec<String>, Vec<&str>)> = Vec::new();
+
+        for line in text.lines() {
+            let trimmed = line.trim();
+            let header = trimmed
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .map(|pattern| pattern.trim())
+                .filter(|pattern| !pattern.is_empty());
+
+            match (header, sections.last_mut()) {
+                // Consecutive section lines form one group
+                (Some(pattern), Some((patterns, body)))
+                    if body.iter().all(|l| l.trim().is_empty()) =>
+                {
+                    patterns.push(pattern.to_string());
+                }
+                (Some(pattern), _) => sections.push((vec![pattern.to_string()], Vec::new())),
+                (None, Some((_, body))) => body.push(line),
+                (None, None) => shared.push(line),
+            }
+        }
+
+        Self {
+            shared: shared.join("\n").trim().to_string(),
+            sections: sections
+                .into_iter()
+                .map(|(patterns, body)| (patterns, body.join("\n").trim().to_string()))
+                .collect(),
+        }
+    }
+
+    /// Description of a file, `None` if it is empty
+    pub fn description_of(&self, path: &str) -> Option<&str> {
+        let description = self
+            .sections
+            .iter()
+            .rev()
+            .find(|(patterns, _)| patterns.iter().any(|pattern| match_path(pattern, path)))
+            .map(|(_, description)| description.as_str())
+            .unwrap_or(&self.shared);
+        (!description.is_empty()).then_some(description)
+    }
+
+    /// Section patterns that match none of the paths
+    pub fn unused_patterns<'a>(&'a self, paths: &[&str]) -> Vec<&'a String> {
+        self.sections
+            .iter()
+            .flat_map(|(patterns, _)| patterns)
+            .filter(|pattern| !paths.iter().any(|path| match_path(pattern, path)))
+            .collect()
+    }
+}
+
+/// Rules that every update description must follow, one regex per line.
+/// Empty lines and lines starting with `#` are skipped
+#[derive(Debug, Default, Clone)]
+pub struct DescriptionRules {
+    rules: Vec<Regex>,
+}
+
+impl DescriptionRules {
+    /// Parse the rules, or return the line number and the error of the first invalid regex
+    pub fn parse(content: &str) -> Result<Self, (usize, regex::Error)> {
+        let mut rules = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            rules.push(Regex::new(line).map_err(|err| (i + 1, err))?);
+        }
+        Ok(Self { rules })
+    }
+
+    /// The first rule the description doesn't match
+    pub fn first_violation(&self, description: &str) -> Option<&Regex> {
+        self.rules.iter().find(|rule| !rule.is_match(description))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_override_shared() {
+        let text = "Shared text\n\n[src/main.rs]\nFix the entry\n\n[assets/*.png]\n[docs/**]\nNew art\nand docs\n";
+        let descriptions = UpdateDescriptions::parse(text);
+        assert_eq!(descriptions.shared, "Shared text");
+        assert_eq!(
+            descriptions.description_of("src/main.rs"),
+            Some("Fix the entry")
+        );
+        assert_eq!(
+            descriptions.description_of("assets/a.png"),
+            Some("New art\nand docs")
+        );
+        assert_eq!(
+            descriptions.description_of("docs/guide/a.md"),
+            Some("New art\nand docs")
+        );
+        assert_eq!(
+            descriptions.description_of("src/lib.rs"),
+            Some("Shared text")
+        );
+        assert_eq!(
+            descriptions.unused_patterns(&["src/main.rs", "assets/a.png"]),
+            vec!["docs/**"]
+        );
+    }
+
+    #[test]
+    fn test_missing_descriptions() {
+        let descriptions = UpdateDescriptions::parse("[a.txt]\nOnly a\n[b.txt]\n\n");
+        assert_eq!(descriptions.description_of("a.txt"), Some("Only a"));
+        assert_eq!(descriptions.description_of("b.txt"), None);
+        assert_eq!(descriptions.description_of("c.txt"), None);
+    }
+
+    #[test]
+    fn test_description_rules() {
+        let rules = DescriptionRules::parse("# ticket id\nPROJ-\\d+\n\n^.{10,}").unwrap();
+        assert!(rules.first_violation("PROJ-42 Fix the texture").is_none());
+        assert_eq!(
+            rules
+                .first_violation("Fix the texture")
+                .map(|rule| rule.as_str()),
+            Some("PROJ-\\d+")
+        );
+        assert_eq!(
+            rules.first_violation("PROJ-42").map(|rule| rule.as_str()),
+            Some("^.{10,}")
+        );
+        assert_eq!(DescriptionRules::parse("ok\n(unclosed").unwrap_err().0, 2);
+    }
+}